@@ -0,0 +1,22 @@
+//! Process-wide notifications of account store saves, so every view of the
+//! accounts follows changes made elsewhere: a sign-in on the account screen,
+//! a token refresh during a launch, a skin refresh in maintenance.
+
+use crate::AccountStore;
+use std::sync::OnceLock;
+use tokio::sync::watch;
+
+fn sender() -> &'static watch::Sender<AccountStore> {
+    static CHANGES: OnceLock<watch::Sender<AccountStore>> = OnceLock::new();
+    CHANGES.get_or_init(|| watch::channel(AccountStore::load().unwrap_or_default()).0)
+}
+
+/// Receives the account store as saved, every time it is saved. The store at
+/// the time of subscribing counts as seen.
+pub fn subscribe() -> watch::Receiver<AccountStore> {
+    sender().subscribe()
+}
+
+pub(crate) fn publish(store: &AccountStore) {
+    sender().send_replace(store.clone());
+}
@@ -0,0 +1,280 @@
+//! Accounts stored by other launchers: the official Minecraft Launcher and
+//! MultiMC/Prism Launcher, which share a format.
+
+use crate::{Account, AccountError, AccountKind, AccountService, AccountStore, offline_uuid};
+use directories::BaseDirs;
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportSource {
+    MinecraftLauncher,
+    Prism,
+}
+
+impl fmt::Display for ImportSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportSource::MinecraftLauncher => write!(f, "Minecraft Launcher"),
+            ImportSource::Prism => write!(f, "MultiMC / Prism"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportedKind {
+    Offline,
+    /// `refresh_token` was issued to the other launcher and usually can't be
+    /// redeemed by this one.
+    Microsoft {
+        uuid: String,
+        refresh_token: Option<String>,
+    },
+}
+
+/// An account found in another launcher's storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedAccount {
+    pub source: ImportSource,
+    pub username: String,
+    pub kind: ImportedKind,
+}
+
+/// Reads the account files of every launcher installed in its default
+/// location. Unreadable files are skipped.
+pub fn find_importable() -> Vec<ImportedAccount> {
+    let mut found: Vec<ImportedAccount> = Vec::new();
+    for path in default_account_files() {
+        for account in read_accounts_file(&path).unwrap_or_default() {
+            if !found.contains(&account) {
+                found.push(account);
+            }
+        }
+    }
+    found
+}
+
+/// Reads one launcher's account file, telling the format from its contents:
+/// Prism's `accounts.json`, or the official launcher's
+/// `launcher_accounts.json` or older `launcher_profiles.json`.
+pub fn read_accounts_file(path: &Path) -> Result<Vec<ImportedAccount>, AccountError> {
+    let json: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let accounts = if let Some(list) = json["accounts"].as_array() {
+        list.iter().filter_map(prism_account).collect()
+    } else if let Some(map) = json["accounts"].as_object() {
+        map.values().filter_map(launcher_account).collect()
+    } else if let Some(map) = json["authenticationDatabase"].as_object() {
+        map.values().flat_map(legacy_profiles).collect()
+    } else {
+        Vec::new()
+    };
+    Ok(accounts)
+}
+
+fn default_account_files() -> Vec<PathBuf> {
+    let Some(dirs) = BaseDirs::new() else {
+        return Vec::new();
+    };
+    let minecraft = if cfg!(target_os = "windows") {
+        dirs.data_dir().join(".minecraft")
+    } else if cfg!(target_os = "macos") {
+        dirs.data_dir().join("minecraft")
+    } else {
+        dirs.home_dir().join(".minecraft")
+    };
+    vec![
+        minecraft.join("launcher_accounts.json"),
+        minecraft.join("launcher_accounts_microsoft_store.json"),
+        minecraft.join("launcher_profiles.json"),
+        dirs.data_dir().join("PrismLauncher").join("accounts.json"),
+        dirs.home_dir()
+            .join(".var/app/org.prismlauncher.PrismLauncher/data/PrismLauncher/accounts.json"),
+    ]
+}
+
+/// `{"type": "MSA" | "Offline" | "Mojang", "profile": {"id", "name"}, "msa": {"refresh_token"}}`
+fn prism_account(entry: &Value) -> Option<ImportedAccount> {
+    let username = entry["profile"]["name"].as_str()?.to_string();
+    let kind = match entry["type"].as_str()? {
+        "Offline" => ImportedKind::Offline,
+        _ => ImportedKind::Microsoft {
+            uuid: entry["profile"]["id"].as_str()?.replace('-', ""),
+            refresh_token: entry["msa"]["refresh_token"].as_str().map(str::to_string),
+        },
+    };
+    Some(ImportedAccount {
+        source: ImportSource::Prism,
+        username,
+        kind,
+    })
+}
+
+/// `{"minecraftProfile": {"id", "name"}, ...}`; the official launcher keeps
+/// Microsoft tokens encrypted elsewhere.
+fn launcher_account(entry: &Value) -> Option<ImportedAccount> {
+    let profile = &entry["minecraftProfile"];
+    Some(ImportedAccount {
+        source: ImportSource::MinecraftLauncher,
+        username: profile["name"].as_str()?.to_string(),
+        kind: ImportedKind::Microsoft {
+            uuid: profile["id"].as_str()?.replace('-', ""),
+            refresh_token: None,
+        },
+    })
+}
+
+/// `{"profiles": {"<uuid>": {"displayName"}}, ...}`, from before accounts
+/// moved to `launcher_accounts.json`. Mojang accounts have all been migrated
+/// to Microsoft since.
+fn legacy_profiles(entry: &Value) -> Vec<ImportedAccount> {
+    let Some(profiles) = entry["profiles"].as_object() else {
+        return Vec::new();
+    };
+    profiles
+        .iter()
+        .filter_map(|(uuid, profile)| {
+            Some(ImportedAccount {
+                source: ImportSource::MinecraftLauncher,
+                username: profile["displayName"].as_str()?.to_string(),
+                kind: ImportedKind::Microsoft {
+                    uuid: uuid.replace('-', ""),
+                    refresh_token: None,
+                },
+            })
+        })
+        .collect()
+}
+
+fn is_same(account: &Account, imported: &ImportedAccount) -> bool {
+    match (&account.kind, &imported.kind) {
+        (AccountKind::Offline { username, .. }, ImportedKind::Offline) => {
+            username == &imported.username
+        }
+        (AccountKind::Microsoft { uuid, .. }, ImportedKind::Microsoft { uuid: id, .. }) => {
+            uuid.replace('-', "") == *id
+        }
+        _ => false,
+    }
+}
+
+impl AccountStore {
+    /// Whether `imported` is already one of the accounts here.
+    pub fn has_imported(&self, imported: &ImportedAccount) -> bool {
+        self.accounts
+            .iter()
+            .any(|account| is_same(account, imported))
+    }
+
+    /// Adds imported accounts that aren't here yet, without tokens:
+    /// Microsoft accounts need to sign in again before they can launch.
+    /// Returns how many were added.
+    pub fn import(&mut self, accounts: &[ImportedAccount]) -> Result<usize, AccountError> {
+        let mut added = 0;
+        for imported in accounts {
+            if self.has_imported(imported) {
+                continue;
+            }
+
+            let (kind, requires_login) = match &imported.kind {
+                ImportedKind::Offline => (
+                    AccountKind::Offline {
+                        username: imported.username.clone(),
+                        uuid: offline_uuid(&imported.username).to_string(),
+                    },
+                    false,
+                ),
+                ImportedKind::Microsoft { uuid, .. } => (
+                    AccountKind::Microsoft {
+                        uuid: uuid.clone(),
+                        username: imported.username.clone(),
+                    },
+                    true,
+                ),
+            };
+            self.accounts.push(Account {
+                id: Uuid::new_v4(),
+                display_name: imported.username.clone(),
+                kind,
+                skin_path: None,
+                requires_login,
+            });
+            added += 1;
+        }
+
+        if self.active.is_none() {
+            self.active = self.accounts.first().map(|account| account.id);
+        }
+        self.save()?;
+        Ok(added)
+    }
+}
+
+/// What [`AccountService::import_accounts`] did.
+#[derive(Debug, Clone, Default)]
+pub struct ImportOutcome {
+    pub added: usize,
+    /// Accounts whose working refresh token couldn't be kept, with why. They
+    /// are imported signed out like the others.
+    pub errors: Vec<(String, String)>,
+}
+
+impl AccountService {
+    /// Imports `accounts`, first trying each Microsoft refresh token against
+    /// this launcher's client; the ones that work come in signed in. The
+    /// active account stays as it was. One account failing doesn't stop the
+    /// others.
+    pub async fn import_accounts(
+        &mut self,
+        accounts: &[ImportedAccount],
+    ) -> Result<ImportOutcome, AccountError> {
+        let mut outcome = ImportOutcome::default();
+        let active = self.store.active;
+        let mut rest = Vec::new();
+        for imported in accounts {
+            let ImportedKind::Microsoft {
+                refresh_token: Some(token),
+                ..
+            } = &imported.kind
+            else {
+                rest.push(imported.clone());
+                continue;
+            };
+            // Refresh tokens are bound to the client they were issued to
+            let session = match self.auth.refresh_access_token(token).await {
+                Ok(tokens) => self.game.minecraft_session(&tokens).await.ok(),
+                Err(_) => None,
+            };
+            let Some(session) = session else {
+                rest.push(imported.clone());
+                continue;
+            };
+            match self.store.upsert_microsoft(&session).await {
+                Ok(_) => outcome.added += 1,
+                Err(e) => {
+                    // It may have been added before its tokens failed to save
+                    if let Some(account) = self
+                        .store
+                        .accounts
+                        .iter_mut()
+                        .find(|account| is_same(account, imported))
+                    {
+                        account.requires_login = true;
+                    }
+                    outcome
+                        .errors
+                        .push((imported.username.clone(), e.to_string()));
+                    rest.push(imported.clone());
+                }
+            }
+        }
+
+        if active.is_some() {
+            self.store.active = active;
+        }
+        outcome.added += self.store.import(&rest)?;
+        Ok(outcome)
+    }
+}
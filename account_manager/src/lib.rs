@@ -1,17 +1,35 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use directories::ProjectDirs;
+use image::imageops;
 use keyring::{Entry, Error as KeyringError};
-use microsoft_auth::{DeviceCodeInfo, MicrosoftAuthenticator, MicrosoftTokens};
+use microsoft_auth::{CancellationToken, DeviceCodeInfo, MicrosoftAuthenticator, MicrosoftTokens};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, SystemTime};
 use thiserror::Error;
 use uuid::Uuid;
 
+mod changes;
+mod import;
+mod yggdrasil;
+
+pub use changes::subscribe;
+pub use import::{
+    ImportOutcome, ImportSource, ImportedAccount, ImportedKind, find_importable, read_accounts_file,
+};
+pub use yggdrasil::{YggdrasilSecrets, resolve_api_root, server_host};
+
 const SERVICE_NAME: &str = "fastmc";
 
+/// Tokens closer than this to expiring are refreshed rather than reused.
+const SESSION_EXPIRY_MARGIN_SECS: u64 = 300;
+
 #[derive(Debug, Error)]
 pub enum AccountError {
     #[error("config directory unavailable")]
@@ -22,14 +40,93 @@ pub enum AccountError {
     Json(#[from] serde_json::Error),
     #[error("http error: {0}")]
     Http(#[from] reqwest::Error),
+    #[error("image error: {0}")]
+    Image(#[from] image::ImageError),
     #[error("keyring error: {0}")]
     Keyring(#[from] KeyringError),
+    #[error("download error: {0}")]
+    Download(#[from] downloader::DownloadError),
     #[error("auth error: {0}")]
     Auth(#[from] microsoft_auth::AuthError),
     #[error("missing xbox user hash")]
     MissingUserHash,
     #[error("minecraft profile unavailable: {0}")]
     ProfileUnavailable(String),
+    #[error("invalid auth server address: {0}")]
+    InvalidServer(String),
+    #[error("auth server refused: {0}")]
+    ServerRejected(String),
+    #[error("this Microsoft account has no Xbox profile yet")]
+    NoXboxProfile,
+    #[error("this Microsoft account has not accepted the Xbox terms of use")]
+    XboxTermsNotAccepted,
+    #[error("Xbox Live is not available in this account's country")]
+    XboxRegionUnavailable,
+    #[error("this account needs adult verification on the Xbox website")]
+    AdultVerificationRequired,
+    #[error("this is a child account that isn't part of a Microsoft family")]
+    ChildAccount,
+    #[error("this child account is not allowed to play online")]
+    OnlinePlayRestricted,
+    #[error("this account is banned from Xbox Live")]
+    XboxBanned,
+    #[error("Xbox Live refused the sign-in (XErr {0})")]
+    XboxDenied(u64),
+    #[error("this account doesn't own Minecraft: Java Edition")]
+    GameNotOwned,
+    #[error("this account has no Minecraft profile yet")]
+    ProfileNotCreated,
+}
+
+impl AccountError {
+    /// What the user can do about an Xbox Live refusal, to show next to the
+    /// error.
+    pub fn remediation(&self) -> Option<&'static str> {
+        match self {
+            Self::NoXboxProfile => Some(
+                "Sign in once at https://www.xbox.com/live with this account to create a profile, then try again.",
+            ),
+            Self::XboxTermsNotAccepted => {
+                Some("Sign in at https://www.xbox.com and accept the terms of use, then try again.")
+            }
+            Self::XboxRegionUnavailable => Some(
+                "Xbox Live can't be used from this account's country. Change the account's region at https://account.microsoft.com if it is wrong.",
+            ),
+            Self::AdultVerificationRequired => {
+                Some("Complete the age verification at https://www.xbox.com, then try again.")
+            }
+            Self::ChildAccount => Some(
+                "An adult needs to add this account to their Microsoft family at https://account.microsoft.com/family before it can sign in.",
+            ),
+            Self::OnlinePlayRestricted => Some(
+                "A parent can allow online play in the Xbox privacy settings of the account's Microsoft family at https://account.microsoft.com/family.",
+            ),
+            Self::XboxBanned => {
+                Some("See https://enforcement.xbox.com for the ban and how to appeal it.")
+            }
+            Self::GameNotOwned => Some(
+                "Buy Minecraft: Java Edition or subscribe to PC Game Pass at https://www.minecraft.net, then sign in again.",
+            ),
+            Self::ProfileNotCreated => Some(
+                "Pick a player name at https://www.minecraft.net/msaprofile, then sign in again. Game Pass accounts need this once before they can play.",
+            ),
+            _ => None,
+        }
+    }
+
+    /// Maps an `XErr` code from XSTS to its error.
+    fn from_xerr(code: u64) -> Self {
+        match code {
+            2148916227 => Self::XboxBanned,
+            2148916229 => Self::OnlinePlayRestricted,
+            2148916233 => Self::NoXboxProfile,
+            2148916234 => Self::XboxTermsNotAccepted,
+            2148916235 => Self::XboxRegionUnavailable,
+            2148916236 | 2148916237 => Self::AdultVerificationRequired,
+            2148916238 => Self::ChildAccount,
+            other => Self::XboxDenied(other),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,10 +136,31 @@ pub struct MicrosoftSecrets {
     pub expires_at: u64,
 }
 
+impl MicrosoftSecrets {
+    /// Whether the Minecraft access token can still be used for a launch.
+    pub fn is_fresh(&self) -> bool {
+        !self.access_token.is_empty()
+            && self.expires_at > unix_timestamp_after(Duration::ZERO) + SESSION_EXPIRY_MARGIN_SECS
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AccountKind {
-    Offline { username: String, uuid: String },
-    Microsoft { uuid: String, username: String },
+    Offline {
+        username: String,
+        uuid: String,
+    },
+    Microsoft {
+        uuid: String,
+        username: String,
+    },
+    /// An account on a third-party Yggdrasil server, launched through
+    /// authlib-injector. `server` is the server's authlib-injector API root.
+    Custom {
+        server: String,
+        uuid: String,
+        username: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,7 +211,9 @@ impl AccountService {
 
     pub fn remove_account(&mut self, account_id: Uuid) -> Result<(), AccountError> {
         if let Some(pos) = self.store.accounts.iter().position(|a| a.id == account_id) {
-            if matches!(self.store.accounts[pos].kind, AccountKind::Microsoft { .. }) {
+            if matches!(self.store.accounts[pos].kind, AccountKind::Offline { .. }) {
+                self.store.set_offline_skin(&account_id, None)?;
+            } else {
                 self.store.clear_microsoft_tokens(&account_id)?;
             }
             self.store.accounts.remove(pos);
@@ -116,8 +236,20 @@ impl AccountService {
     pub async fn complete_microsoft_login(
         &mut self,
         code: &DeviceCodeInfo,
+        cancel: &CancellationToken,
     ) -> Result<&Account, AccountError> {
-        let tokens: MicrosoftTokens = self.auth.poll_device_code(code).await?;
+        let tokens: MicrosoftTokens = self.auth.poll_device_code(code, cancel).await?;
+        let session = self.game.minecraft_session(&tokens).await?;
+        self.store.upsert_microsoft(&session).await
+    }
+
+    /// Signs in through the system browser, opened with `open_browser`,
+    /// instead of a device code.
+    pub async fn login_with_browser(
+        &mut self,
+        open_browser: impl FnOnce(&str) -> Result<(), String>,
+    ) -> Result<&Account, AccountError> {
+        let tokens = self.auth.login_with_browser(open_browser).await?;
         let session = self.game.minecraft_session(&tokens).await?;
         self.store.upsert_microsoft(&session).await
     }
@@ -137,6 +269,26 @@ impl AccountService {
         self.store.upsert_microsoft(&session).await
     }
 
+    /// Minecraft access token to launch `account_id` with. The token is cached
+    /// in memory and the keyring, so consecutive launches only go through the
+    /// Xbox/XSTS chain again once it is about to expire.
+    pub async fn launch_token(&mut self, account_id: &Uuid) -> Result<String, AccountError> {
+        if let Some(secrets) = load_microsoft_tokens(account_id)?
+            && secrets.is_fresh()
+        {
+            return Ok(secrets.access_token);
+        }
+
+        self.refresh_account(account_id).await?;
+        load_microsoft_tokens(account_id)?
+            .map(|secrets| secrets.access_token)
+            .ok_or_else(|| {
+                AccountError::Auth(microsoft_auth::AuthError::OAuth(
+                    "no tokens found".to_string(),
+                ))
+            })
+    }
+
     pub async fn validate_active_account(&mut self) -> Result<&Account, AccountError> {
         let active_id = self.store.active.ok_or(AccountError::ProfileUnavailable(
             "No active account".to_string(),
@@ -156,17 +308,9 @@ impl AccountService {
 
         if is_microsoft {
             // Check if token is still valid before refreshing
-            let should_refresh = match load_microsoft_tokens(&active_id)? {
-                Some(secrets) => {
-                    let now = SystemTime::now()
-                        .duration_since(SystemTime::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs();
-                    // buffer of 5 minutes (300 seconds)
-                    secrets.expires_at < now + 300 || secrets.access_token.is_empty()
-                }
-                None => true,
-            };
+            let should_refresh = !load_microsoft_tokens(&active_id)?
+                .as_ref()
+                .is_some_and(MicrosoftSecrets::is_fresh);
 
             if !should_refresh {
                 return Ok(self
@@ -240,7 +384,17 @@ impl MicrosoftGameClient {
         let (xbl_token, user_hash) = self.xbox_live_token(&microsoft.access_token).await?;
         let (xsts_token, user_hash) = self.xsts_token(&xbl_token, &user_hash).await?;
         let (minecraft_token, expires_in) = self.minecraft_login(&user_hash, &xsts_token).await?;
-        let profile = self.minecraft_profile(&minecraft_token).await?;
+        let profile = match self.minecraft_profile(&minecraft_token).await {
+            // Entitlements are only advisory: they miss some Game Pass and
+            // gifted licenses, so they only decide why a profile is missing
+            Err(AccountError::ProfileNotCreated) => {
+                return Err(match self.owns_game(&minecraft_token).await {
+                    Ok(false) => AccountError::GameNotOwned,
+                    _ => AccountError::ProfileNotCreated,
+                });
+            }
+            result => result?,
+        };
 
         Ok(MinecraftSession {
             access_token: minecraft_token,
@@ -295,15 +449,18 @@ impl MicrosoftGameClient {
             "TokenType": "JWT"
         });
 
-        let response: XboxAuthResponse = self
+        let response = self
             .http
             .post("https://xsts.auth.xboxlive.com/xsts/authorize")
             .json(&payload)
             .send()
-            .await?
-            .error_for_status()?
-            .json()
             .await?;
+        // Refusals come as a 401 whose body says why
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            let denial: XstsDenial = response.json().await?;
+            return Err(AccountError::from_xerr(denial.xerr));
+        }
+        let response: XboxAuthResponse = response.error_for_status()?.json().await?;
 
         let user_hash = response
             .display_claims
@@ -337,6 +494,25 @@ impl MicrosoftGameClient {
         Ok((response.access_token, response.expires_in))
     }
 
+    /// Whether the account's licenses include Minecraft: Java Edition, bought
+    /// or through Game Pass.
+    async fn owns_game(&self, minecraft_token: &str) -> Result<bool, AccountError> {
+        let response: EntitlementsResponse = self
+            .http
+            .get("https://api.minecraftservices.com/entitlements/license")
+            .query(&[("requestId", Uuid::new_v4().to_string())])
+            .bearer_auth(minecraft_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response
+            .items
+            .iter()
+            .any(|item| item.name == "product_minecraft" || item.name == "game_minecraft"))
+    }
+
     async fn minecraft_profile(
         &self,
         minecraft_token: &str,
@@ -348,10 +524,10 @@ impl MicrosoftGameClient {
             .send()
             .await?;
 
+        // Accounts without the game have no profile either; the caller tells
+        // the two apart
         if response.status().as_u16() == 404 {
-            return Err(AccountError::ProfileUnavailable(
-                "Minecraft not purchased for this account".to_string(),
-            ));
+            return Err(AccountError::ProfileNotCreated);
         }
 
         let profile: MinecraftProfileResponse = response.error_for_status()?.json().await?;
@@ -376,6 +552,41 @@ struct XboxAuthResponse {
     display_claims: XboxDisplayClaims,
 }
 
+#[derive(Debug, Deserialize)]
+struct SessionProfile {
+    #[serde(default)]
+    properties: Vec<SessionProperty>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionProperty {
+    name: String,
+    value: String,
+}
+
+/// The base64-decoded `textures` property of a session server profile.
+#[derive(Debug, Deserialize)]
+struct TexturesProperty {
+    textures: ProfileTextures,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileTextures {
+    #[serde(rename = "SKIN")]
+    skin: Option<ProfileTexture>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileTexture {
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct XstsDenial {
+    #[serde(rename = "XErr")]
+    xerr: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct XboxDisplayClaims {
     xui: Vec<XboxUserHash>,
@@ -392,6 +603,17 @@ struct MinecraftLoginResponse {
     expires_in: u64,
 }
 
+#[derive(Debug, Deserialize)]
+struct EntitlementsResponse {
+    #[serde(default)]
+    items: Vec<Entitlement>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Entitlement {
+    name: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct MinecraftProfileResponse {
     id: String,
@@ -427,6 +649,7 @@ impl AccountStore {
         }
         let json = serde_json::to_string_pretty(self)?;
         fs::write(path, json)?;
+        changes::publish(self);
         Ok(())
     }
 
@@ -466,7 +689,13 @@ impl AccountStore {
         session: &MinecraftSession,
     ) -> Result<&Account, AccountError> {
         let profile = &session.profile;
-        let skin_path = cache_skin_head(&profile.id).await?;
+        // A skin that can't be fetched shouldn't fail the sign-in
+        let skin_path = cache_skin_head(&profile.id, profile.skin_url.as_deref())
+            .await
+            .unwrap_or_else(|e| {
+                println!("Failed to cache the skin of {}: {}", profile.name, e);
+                None
+            });
 
         if let Some(idx) = self.accounts.iter().position(|acc| {
             matches!(
@@ -512,6 +741,28 @@ impl AccountStore {
         Ok(&self.accounts[last_index])
     }
 
+    /// Re-downloads the head render of every Microsoft account, picking up
+    /// skin changes made outside the launcher. Returns how many were fetched;
+    /// an account whose skin can't be fetched keeps the head it had.
+    pub async fn refresh_skins(&mut self) -> Result<usize, AccountError> {
+        let mut refreshed = 0;
+        for account in &mut self.accounts {
+            let AccountKind::Microsoft { uuid, username } = &account.kind else {
+                continue;
+            };
+            match cache_skin_head(uuid, None).await {
+                Ok(Some(path)) => {
+                    account.skin_path = Some(path);
+                    refreshed += 1;
+                }
+                Ok(None) => {}
+                Err(e) => println!("Failed to refresh the skin of {}: {}", username, e),
+            }
+        }
+        self.save()?;
+        Ok(refreshed)
+    }
+
     pub fn microsoft_tokens(
         &self,
         account_id: &Uuid,
@@ -519,7 +770,9 @@ impl AccountStore {
         load_microsoft_tokens(account_id)
     }
 
+    /// Also clears a custom server account's tokens, kept in the same entry.
     pub fn clear_microsoft_tokens(&self, account_id: &Uuid) -> Result<(), AccountError> {
+        session_cache().lock().unwrap().remove(account_id);
         let entry = keyring_entry(account_id)?;
         match entry.delete_password() {
             Ok(_) => Ok(()),
@@ -528,6 +781,29 @@ impl AccountStore {
         }
     }
 
+    /// Makes a copy of the PNG at `source` the skin of the offline account
+    /// `account_id`, or removes its skin when `source` is `None`.
+    pub fn set_offline_skin(
+        &self,
+        account_id: &Uuid,
+        source: Option<&Path>,
+    ) -> Result<(), AccountError> {
+        let dest = offline_skin_path(account_id)?;
+        match source {
+            Some(source) => {
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(source, &dest)?;
+                Ok(())
+            }
+            None => match fs::remove_file(&dest) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+                _ => Ok(()),
+            },
+        }
+    }
+
     fn ensure_offline_uuids(&mut self) {
         for account in &mut self.accounts {
             if let AccountKind::Offline { username, uuid } = &mut account.kind
@@ -539,31 +815,126 @@ impl AccountStore {
     }
 }
 
-async fn cache_skin_head(uuid: &str) -> Result<Option<String>, AccountError> {
-    let cache_dir = skin_cache_dir()?;
-    if !cache_dir.exists() {
-        fs::create_dir_all(&cache_dir)?;
+/// Caches the skin texture of `uuid` and renders its 64x64 head, hat layer
+/// included, for the account list. The texture comes from `skin_url`, or from
+/// Mojang's session server when the caller doesn't know it; if that can't be
+/// reached the texture cached last time is used.
+async fn cache_skin_head(
+    uuid: &str,
+    skin_url: Option<&str>,
+) -> Result<Option<String>, AccountError> {
+    let url = match skin_url {
+        Some(url) => Some(url.to_string()),
+        None => session_skin_url(uuid).await.unwrap_or(None),
+    };
+    let texture = skin_texture_path(uuid)?;
+    let available = match &url {
+        Some(url) => cache_skin_texture(uuid, url).await?,
+        None => texture.exists(),
+    };
+    if !available {
+        return Ok(None);
     }
 
-    let url = format!(
-        "https://crafatar.com/avatars/{}?size=64&overlay",
-        uuid.replace('-', "")
-    );
+    let dest = skin_cache_dir()?.join(format!("{}.png", uuid));
+    if !render_head(&texture, &dest)? {
+        return Ok(None);
+    }
+    Ok(Some(dest.to_string_lossy().to_string()))
+}
 
+/// The skin URL in the `textures` property of `uuid`'s profile on the
+/// session server, or `None` for a player without a skin.
+async fn session_skin_url(uuid: &str) -> Result<Option<String>, AccountError> {
     let client = Client::builder().timeout(Duration::from_secs(15)).build()?;
-    let response = client.get(url).send().await?;
-    if !response.status().is_success() {
+    let response = client
+        .get(format!(
+            "https://sessionserver.mojang.com/session/minecraft/profile/{}",
+            uuid.replace('-', "")
+        ))
+        .send()
+        .await?
+        .error_for_status()?;
+    // Unknown players get an empty 204
+    if response.status() == reqwest::StatusCode::NO_CONTENT {
+        return Ok(None);
+    }
+    let profile: SessionProfile = response.json().await?;
+    let Some(textures) = profile
+        .properties
+        .into_iter()
+        .find(|property| property.name == "textures")
+    else {
         return Ok(None);
+    };
+    let decoded = BASE64
+        .decode(textures.value)
+        .map_err(|e| AccountError::ProfileUnavailable(e.to_string()))?;
+    let textures: TexturesProperty = serde_json::from_slice(&decoded)?;
+    Ok(textures.textures.skin.map(|skin| skin.url))
+}
+
+/// Crops the face out of the skin at `texture`, puts the hat layer over it
+/// and saves it scaled to 64x64 at `dest`. HD skins, multiples of the 64x64
+/// or 64x32 layout, are cropped at their own scale. Returns `false` for a
+/// texture of any other size, which gets the default head instead.
+fn render_head(texture: &Path, dest: &Path) -> Result<bool, AccountError> {
+    let skin = image::open(texture)?.to_rgba8();
+    let scale = skin.width() / 64;
+    let legacy = skin.height() * 2 == skin.width();
+    if scale == 0 || skin.width() % 64 != 0 || !(legacy || skin.height() == skin.width()) {
+        println!(
+            "Unsupported skin size {}x{}, using the default head",
+            skin.width(),
+            skin.height()
+        );
+        return Ok(false);
+    }
+    let face = |x: u32| imageops::crop_imm(&skin, x * scale, 8 * scale, 8 * scale, 8 * scale);
+    let mut head = face(8).to_image();
+    let hat = face(40).to_image();
+    // Old 64x32 skins often fill the hat with an opaque colour; the game
+    // ignores such a hat, so do the same
+    let opaque_legacy_hat = legacy && hat.pixels().all(|pixel| pixel[3] == 255);
+    if !opaque_legacy_hat {
+        imageops::overlay(&mut head, &hat, 0, 0);
     }
+    imageops::resize(&head, 64, 64, imageops::FilterType::Nearest).save(dest)?;
+    Ok(true)
+}
 
-    let bytes = response.bytes().await?;
-    let dest = cache_dir.join(format!("{}.png", uuid));
-    fs::write(&dest, bytes)?;
-    Ok(Some(dest.to_string_lossy().to_string()))
+/// Downloads the full skin texture from the profile's `url`, for the 3D
+/// preview. Returns whether a copy is available.
+async fn cache_skin_texture(uuid: &str, url: &str) -> Result<bool, AccountError> {
+    let dest = skin_texture_path(uuid)?;
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let client = Client::builder().timeout(Duration::from_secs(15)).build()?;
+    Ok(downloader::fetch_cached(&client, url, &dest, Duration::ZERO).await?)
+}
+
+/// Where the skin texture of the Microsoft account `uuid` is cached. It is
+/// refreshed at every sign-in and skin refresh, so it may not exist yet.
+pub fn skin_texture_path(uuid: &str) -> Result<PathBuf, AccountError> {
+    Ok(skin_cache_dir()?.join(format!("{}-texture.png", uuid)))
 }
 
-fn skin_cache_dir() -> Result<PathBuf, AccountError> {
-    data_dir().map(|root| root.join("skins"))
+/// Where the skin picked for the offline account `account_id` is kept, if it
+/// has one. Unlike downloaded skins it can't be fetched again, so it lives in
+/// the data dir.
+pub fn offline_skin_path(account_id: &Uuid) -> Result<PathBuf, AccountError> {
+    Ok(data_dir()?
+        .join("offline-skins")
+        .join(format!("{}.png", account_id)))
+}
+
+/// Cached head renders and skin textures. They can always be re-downloaded,
+/// so they live in the cache dir rather than next to the account store.
+pub fn skin_cache_dir() -> Result<PathBuf, AccountError> {
+    let dirs =
+        ProjectDirs::from("com", "fastmc", "fastmc").ok_or(AccountError::ConfigDirMissing)?;
+    Ok(dirs.cache_dir().join("skins"))
 }
 
 fn accounts_file() -> Result<PathBuf, AccountError> {
@@ -597,6 +968,13 @@ fn unix_timestamp_after(duration: Duration) -> u64 {
         .as_secs()
 }
 
+/// Secrets handed out during this run. Saves a keyring round-trip per launch
+/// and, on Windows, is the only place the access token is kept.
+fn session_cache() -> &'static Mutex<HashMap<Uuid, MicrosoftSecrets>> {
+    static CACHE: OnceLock<Mutex<HashMap<Uuid, MicrosoftSecrets>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
 fn keyring_entry(account_id: &Uuid) -> Result<Entry, AccountError> {
     Ok(Entry::new(SERVICE_NAME, &format!("account-{account_id}"))?)
 }
@@ -617,14 +995,132 @@ fn store_microsoft_tokens(
     let entry = keyring_entry(&account_id)?;
     let payload = serde_json::to_string(&secrets)?;
     entry.set_password(&payload)?;
+
+    session_cache().lock().unwrap().insert(
+        account_id,
+        MicrosoftSecrets {
+            access_token: session.access_token.clone(),
+            ..secrets
+        },
+    );
     Ok(())
 }
 
 fn load_microsoft_tokens(account_id: &Uuid) -> Result<Option<MicrosoftSecrets>, AccountError> {
+    if let Some(secrets) = session_cache().lock().unwrap().get(account_id) {
+        return Ok(Some(secrets.clone()));
+    }
+
     let entry = keyring_entry(account_id)?;
-    match entry.get_password() {
-        Ok(raw) => Ok(Some(serde_json::from_str(&raw)?)),
-        Err(KeyringError::NoEntry) => Ok(None),
-        Err(err) => Err(AccountError::Keyring(err)),
+    let secrets: MicrosoftSecrets = match entry.get_password() {
+        Ok(raw) => serde_json::from_str(&raw)?,
+        Err(KeyringError::NoEntry) => return Ok(None),
+        Err(err) => return Err(AccountError::Keyring(err)),
+    };
+    session_cache()
+        .lock()
+        .unwrap()
+        .insert(*account_id, secrets.clone());
+    Ok(Some(secrets))
+}
+
+/// Outcome of a single keyring operation performed by [`keyring_health_check`].
+#[derive(Debug, Clone)]
+pub struct KeyringStep {
+    pub name: &'static str,
+    pub result: Result<(), String>,
+}
+
+/// Result of exercising the platform keyring with a throwaway entry.
+#[derive(Debug, Clone)]
+pub struct KeyringReport {
+    pub backend: &'static str,
+    pub steps: Vec<KeyringStep>,
+    pub hints: Vec<&'static str>,
+}
+
+impl KeyringReport {
+    pub fn is_healthy(&self) -> bool {
+        self.steps.iter().all(|step| step.result.is_ok())
+    }
+}
+
+/// Writes, reads back and deletes a dummy keyring entry so storage problems
+/// surface before a Microsoft login tries to persist its tokens.
+pub fn keyring_health_check() -> KeyringReport {
+    const PROBE: &str = "fastmc-keyring-probe";
+
+    let mut steps = Vec::new();
+    let entry = match Entry::new(SERVICE_NAME, "health-check") {
+        Ok(entry) => entry,
+        Err(err) => {
+            steps.push(KeyringStep {
+                name: "open",
+                result: Err(err.to_string()),
+            });
+            return keyring_report(steps);
+        }
+    };
+
+    let write = entry.set_password(PROBE).map_err(|e| e.to_string());
+    let wrote = write.is_ok();
+    steps.push(KeyringStep {
+        name: "write",
+        result: write,
+    });
+
+    if wrote {
+        let read = match entry.get_password() {
+            Ok(value) if value == PROBE => Ok(()),
+            Ok(_) => Err("stored value did not round-trip".to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+        steps.push(KeyringStep {
+            name: "read",
+            result: read,
+        });
+        steps.push(KeyringStep {
+            name: "delete",
+            result: entry.delete_password().map_err(|e| e.to_string()),
+        });
+    }
+
+    keyring_report(steps)
+}
+
+fn keyring_report(steps: Vec<KeyringStep>) -> KeyringReport {
+    let healthy = steps.iter().all(|step| step.result.is_ok());
+    KeyringReport {
+        backend: keyring_backend_name(),
+        hints: if healthy { Vec::new() } else { keyring_hints() },
+        steps,
+    }
+}
+
+fn keyring_backend_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "Windows Credential Manager"
+    } else if cfg!(target_os = "macos") {
+        "macOS Keychain"
+    } else if cfg!(target_os = "linux") {
+        "Secret Service (D-Bus)"
+    } else {
+        "platform keyring"
+    }
+}
+
+fn keyring_hints() -> Vec<&'static str> {
+    if cfg!(target_os = "linux") {
+        vec![
+            "Install a Secret Service provider such as gnome-keyring or KWallet.",
+            "Make sure the keyring daemon is running and unlocked in your session.",
+            "On minimal desktops, start gnome-keyring-daemon with the secrets component enabled.",
+        ]
+    } else if cfg!(target_os = "macos") {
+        vec!["Unlock the login keychain in Keychain Access and allow fastmc to use it."]
+    } else if cfg!(target_os = "windows") {
+        vec!["Check that the Credential Manager service is running."]
+    } else {
+        vec!["No supported keyring backend is available on this platform."]
     }
 }
@@ -0,0 +1,260 @@
+//! Accounts on third-party Yggdrasil servers (Ely.by, self-hosted
+//! Blessing Skin, ...), used in game through authlib-injector.
+
+use crate::{Account, AccountError, AccountKind, AccountStore, keyring_entry};
+use keyring::Error as KeyringError;
+use reqwest::header::HeaderName;
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Header through which a site points at its authlib-injector API, so users
+/// can enter the site's address instead of the API's.
+const API_LOCATION_HEADER: HeaderName = HeaderName::from_static("x-authlib-injector-api-location");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YggdrasilSecrets {
+    pub access_token: String,
+    /// Sent back with every refresh; the server ties the access token to it.
+    pub client_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthResponse {
+    access_token: String,
+    client_token: String,
+    selected_profile: Option<YggdrasilProfile>,
+    #[serde(default)]
+    available_profiles: Vec<YggdrasilProfile>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct YggdrasilProfile {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorResponse {
+    error_message: Option<String>,
+}
+
+fn http() -> Result<Client, AccountError> {
+    Ok(Client::builder().timeout(Duration::from_secs(15)).build()?)
+}
+
+/// The authlib-injector API root for what the user typed: a bare host gets
+/// `https://`, and the site's API location header is followed if it sends
+/// one. Returned without a trailing slash.
+pub async fn resolve_api_root(server: &str) -> Result<String, AccountError> {
+    let server = server.trim();
+    let url = if server.contains("://") {
+        server.to_string()
+    } else {
+        format!("https://{}", server)
+    };
+    let url = Url::parse(&url).map_err(|e| AccountError::InvalidServer(e.to_string()))?;
+
+    let response = http()?.get(url.clone()).send().await?;
+    let root = match response.headers().get(API_LOCATION_HEADER) {
+        Some(location) => location
+            .to_str()
+            .ok()
+            .and_then(|location| url.join(location).ok())
+            .unwrap_or(url),
+        None => url,
+    };
+    Ok(root.as_str().trim_end_matches('/').to_string())
+}
+
+/// Host of a server's API root, to tell accounts on different servers apart.
+pub fn server_host(api_root: &str) -> String {
+    Url::parse(api_root)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| api_root.to_string())
+}
+
+async fn post(
+    api_root: &str,
+    endpoint: &str,
+    body: serde_json::Value,
+) -> Result<reqwest::Response, AccountError> {
+    let response = http()?
+        .post(format!("{}/authserver/{}", api_root, endpoint))
+        .json(&body)
+        .send()
+        .await?;
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let message = response
+        .json::<ErrorResponse>()
+        .await
+        .ok()
+        .and_then(|error| error.error_message)
+        .unwrap_or_else(|| status.to_string());
+    Err(AccountError::ServerRejected(message))
+}
+
+impl AccountStore {
+    /// Signs in to the Yggdrasil server at `server` with a username (or
+    /// email) and password, adding the account or refreshing an existing one.
+    /// The password is only sent to the server, never stored.
+    pub async fn login_custom(
+        &mut self,
+        server: &str,
+        username: &str,
+        password: &str,
+    ) -> Result<&Account, AccountError> {
+        let api_root = resolve_api_root(server).await?;
+        let response: AuthResponse = post(
+            &api_root,
+            "authenticate",
+            json!({
+                "agent": { "name": "Minecraft", "version": 1 },
+                "username": username,
+                "password": password,
+                "clientToken": Uuid::new_v4().simple().to_string(),
+                "requestUser": false,
+            }),
+        )
+        .await?
+        .json()
+        .await?;
+
+        let profile = response
+            .selected_profile
+            .or_else(|| response.available_profiles.into_iter().next())
+            .ok_or_else(|| {
+                AccountError::ProfileUnavailable("no character on this account".to_string())
+            })?;
+        let secrets = YggdrasilSecrets {
+            access_token: response.access_token,
+            client_token: response.client_token,
+        };
+
+        let existing = self.accounts.iter().position(|account| {
+            matches!(
+                &account.kind,
+                AccountKind::Custom { server, uuid, .. } if *server == api_root && *uuid == profile.id
+            )
+        });
+        let kind = AccountKind::Custom {
+            server: api_root,
+            uuid: profile.id,
+            username: profile.name.clone(),
+        };
+        let index = match existing {
+            Some(index) => {
+                let account = &mut self.accounts[index];
+                account.display_name = profile.name;
+                account.kind = kind;
+                account.requires_login = false;
+                index
+            }
+            None => {
+                self.accounts.push(Account {
+                    id: Uuid::new_v4(),
+                    display_name: profile.name,
+                    kind,
+                    skin_path: None,
+                    requires_login: false,
+                });
+                self.accounts.len() - 1
+            }
+        };
+
+        let account_id = self.accounts[index].id;
+        store_custom_tokens(&account_id, &secrets)?;
+        self.active = Some(account_id);
+        self.save()?;
+        Ok(&self.accounts[index])
+    }
+
+    pub fn custom_tokens(
+        &self,
+        account_id: &Uuid,
+    ) -> Result<Option<YggdrasilSecrets>, AccountError> {
+        match keyring_entry(account_id)?.get_password() {
+            Ok(raw) => Ok(Some(serde_json::from_str(&raw)?)),
+            Err(KeyringError::NoEntry) => Ok(None),
+            Err(err) => Err(AccountError::Keyring(err)),
+        }
+    }
+
+    /// Access token to launch the custom-server account `account_id` with,
+    /// refreshed first if the server no longer accepts it. When that fails
+    /// too the account is marked as needing to sign in again.
+    pub async fn custom_launch_token(&mut self, account_id: &Uuid) -> Result<String, AccountError> {
+        let Some(Account {
+            kind: AccountKind::Custom { server, .. },
+            ..
+        }) = self
+            .accounts
+            .iter()
+            .find(|account| account.id == *account_id)
+        else {
+            return Err(AccountError::ProfileUnavailable(
+                "not a custom server account".to_string(),
+            ));
+        };
+        let server = server.clone();
+        let secrets = self
+            .custom_tokens(account_id)?
+            .ok_or_else(|| AccountError::ProfileUnavailable("no tokens found".to_string()))?;
+        let tokens = json!({
+            "accessToken": secrets.access_token,
+            "clientToken": secrets.client_token,
+        });
+
+        // `validate` answers 204 for a usable token and 403 otherwise
+        let valid = http()?
+            .post(format!("{}/authserver/validate", server))
+            .json(&tokens)
+            .send()
+            .await?
+            .status()
+            == StatusCode::NO_CONTENT;
+        if valid {
+            return Ok(secrets.access_token);
+        }
+
+        let refreshed = match post(&server, "refresh", tokens).await {
+            Ok(response) => response
+                .json::<AuthResponse>()
+                .await
+                .map_err(AccountError::from),
+            Err(e) => Err(e),
+        };
+        match refreshed {
+            Ok(response) => {
+                store_custom_tokens(
+                    account_id,
+                    &YggdrasilSecrets {
+                        access_token: response.access_token.clone(),
+                        client_token: response.client_token,
+                    },
+                )?;
+                Ok(response.access_token)
+            }
+            Err(e) => {
+                if let Some(account) = self.accounts.iter_mut().find(|a| a.id == *account_id) {
+                    account.requires_login = true;
+                }
+                self.save()?;
+                Err(e)
+            }
+        }
+    }
+}
+
+fn store_custom_tokens(account_id: &Uuid, secrets: &YggdrasilSecrets) -> Result<(), AccountError> {
+    keyring_entry(account_id)?.set_password(&serde_json::to_string(secrets)?)?;
+    Ok(())
+}
@@ -2,12 +2,34 @@ use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 use thiserror::Error;
 
 const CONFIG_VERSION: u32 = 3;
 
+/// Accessor for a config value that an environment variable can override.
+type ConfigField = fn(&mut FastmcConfig) -> &mut Option<String>;
+
+/// Environment variables that override a config value for the current run,
+/// for CI, Flatpak and scripted launches. They are applied on load and never
+/// written back; an empty variable counts as unset.
+const ENV_OVERRIDES: [(&str, ConfigField); 5] = [
+    ("FASTMC_JAVA_PATH", |config| &mut config.java.java_path),
+    ("FASTMC_INSTANCES_DIR", |config| {
+        &mut config.profiles.instances_dir
+    }),
+    ("FASTMC_DEFAULT_PROFILE", |config| {
+        &mut config.profiles.default_profile
+    }),
+    ("FASTMC_MS_CLIENT_ID", |config| {
+        &mut config.accounts.microsoft_client_id
+    }),
+    ("FASTMC_ACCOUNTS_PATH", |config| {
+        &mut config.accounts.store_path
+    }),
+];
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("config directory unavailable")]
@@ -18,6 +40,8 @@ pub enum ConfigError {
     Json(#[from] serde_json::Error),
     #[error("persist error: {0}")]
     Persist(#[from] tempfile::PersistError),
+    #[error("invalid settings: {0}")]
+    Invalid(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -38,6 +62,10 @@ pub struct JavaConfig {
     /// Whether to auto-discover Java installations.
     #[serde(default = "default_true")]
     pub auto_discover: bool,
+    /// Whether versions that name a Mojang Java runtime launch on it, installed
+    /// on demand, when no Java path is set.
+    #[serde(default = "default_true")]
+    pub mojang_runtimes: bool,
     /// Requested minimum RAM (in megabytes) for the JVM.
     #[serde(default = "default_min_memory_mb")]
     pub min_memory_mb: u32,
@@ -57,6 +85,7 @@ impl Default for JavaConfig {
         Self {
             java_path: None,
             auto_discover: true,
+            mojang_runtimes: true,
             min_memory_mb: default_min_memory_mb(),
             max_memory_mb: default_max_memory_mb(),
             extra_jvm_args: Vec::new(),
@@ -70,6 +99,9 @@ pub struct AccountsConfig {
     /// Optional Microsoft client ID for device-code auth.
     #[serde(default)]
     pub microsoft_client_id: Option<String>,
+    /// How Microsoft sign-in is completed.
+    #[serde(default)]
+    pub login_flow: LoginFlow,
     /// Permit offline account creation.
     #[serde(default = "default_true")]
     pub allow_offline: bool,
@@ -82,12 +114,155 @@ impl Default for AccountsConfig {
     fn default() -> Self {
         Self {
             microsoft_client_id: None,
+            login_flow: LoginFlow::default(),
             allow_offline: true,
             store_path: None,
         }
     }
 }
 
+/// How Microsoft sign-in is completed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginFlow {
+    /// Enter a code shown by the launcher on microsoft.com/link.
+    #[default]
+    DeviceCode,
+    /// Sign in in the system browser, which redirects back to the launcher.
+    /// The client id needs `http://127.0.0.1` as a redirect URI.
+    Browser,
+}
+
+pub const ALL_LOGIN_FLOWS: [LoginFlow; 2] = [LoginFlow::DeviceCode, LoginFlow::Browser];
+
+impl std::fmt::Display for LoginFlow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LoginFlow::DeviceCode => "Device code",
+            LoginFlow::Browser => "Browser",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadsConfig {
+    /// How many times a failed download is retried before giving up.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on each further attempt.
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// Upper bound for the delay between two attempts.
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// How many files are fetched in parallel.
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+    /// URL prefixes to rewrite before downloading and fetching version
+    /// lists, e.g. pointing `https://libraries.minecraft.net/` at a regional
+    /// or in-house mirror where Mojang's servers are blocked.
+    #[serde(default)]
+    pub mirrors: Vec<MirrorRule>,
+    /// Connect over IPv4 only. Works around ISPs whose IPv6 route to Mojang's
+    /// servers is broken.
+    #[serde(default)]
+    pub prefer_ipv4: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorRule {
+    /// Prefix of the original URL, including the scheme, or a bare host such
+    /// as `libraries.minecraft.net` to match it under any scheme.
+    pub from: String,
+    /// Replacement for `from`; the rest of the URL is kept as-is. Without a
+    /// scheme the original one is kept.
+    pub to: String,
+}
+
+impl Default for DownloadsConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_max_retries(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+            max_concurrent: default_max_concurrent(),
+            mirrors: Vec::new(),
+            prefer_ipv4: false,
+        }
+    }
+}
+
+/// What closing the launcher does while a game is running.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseBehavior {
+    /// Quit the launcher and leave the game running on its own.
+    KeepRunning,
+    /// Minimize the launcher window instead of closing it.
+    Minimize,
+    /// Ask every time.
+    #[default]
+    Prompt,
+}
+
+pub const ALL_CLOSE_BEHAVIORS: [CloseBehavior; 3] = [
+    CloseBehavior::Prompt,
+    CloseBehavior::KeepRunning,
+    CloseBehavior::Minimize,
+];
+
+impl std::fmt::Display for CloseBehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CloseBehavior::KeepRunning => "Quit and keep the game running",
+            CloseBehavior::Minimize => "Minimize the launcher",
+            CloseBehavior::Prompt => "Ask every time",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LauncherConfig {
+    #[serde(default)]
+    pub close_behavior: CloseBehavior,
+    /// Keep stats, the mod cache and maintenance history in SQLite instead of
+    /// JSON files.
+    #[serde(default)]
+    pub use_database: bool,
+    /// Version the launcher last ran as; release notes after it are shown
+    /// once as "What's new".
+    #[serde(default)]
+    pub last_seen_version: Option<String>,
+}
+
+/// Background upkeep run at launcher start or once a day, each job toggleable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceConfig {
+    #[serde(default = "default_true")]
+    pub refresh_manifest: bool,
+    #[serde(default = "default_true")]
+    pub prune_logs: bool,
+    #[serde(default = "default_true")]
+    pub gc_assets: bool,
+    #[serde(default = "default_true")]
+    pub refresh_avatars: bool,
+    /// Game logs, crash reports and heap dumps older than this are deleted.
+    #[serde(default = "default_log_retention_days")]
+    pub log_retention_days: u32,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            refresh_manifest: true,
+            prune_logs: true,
+            gc_assets: true,
+            refresh_avatars: true,
+            log_retention_days: default_log_retention_days(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FastmcConfig {
     #[serde(default = "default_version")]
@@ -98,6 +273,12 @@ pub struct FastmcConfig {
     pub java: JavaConfig,
     #[serde(default)]
     pub accounts: AccountsConfig,
+    #[serde(default)]
+    pub downloads: DownloadsConfig,
+    #[serde(default)]
+    pub launcher: LauncherConfig,
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
 }
 
 impl Default for FastmcConfig {
@@ -107,12 +288,31 @@ impl Default for FastmcConfig {
             profiles: ProfilesConfig::default(),
             java: JavaConfig::default(),
             accounts: AccountsConfig::default(),
+            downloads: DownloadsConfig::default(),
+            launcher: LauncherConfig::default(),
+            maintenance: MaintenanceConfig::default(),
         }
     }
 }
 
 impl FastmcConfig {
+    /// Loads the config file, with the `FASTMC_*` variables of
+    /// [`ENV_OVERRIDES`] applied over it.
     pub fn load() -> Result<Self, ConfigError> {
+        let mut config = Self::load_file()?;
+        config.apply_overrides(|var| std::env::var(var).ok());
+        Ok(config)
+    }
+
+    fn apply_overrides(&mut self, lookup: impl Fn(&str) -> Option<String>) {
+        for (var, field) in ENV_OVERRIDES {
+            if let Some(value) = non_empty(lookup(var)) {
+                *field(self) = Some(value);
+            }
+        }
+    }
+
+    fn load_file() -> Result<Self, ConfigError> {
         let path = config_file()?;
         if !path.exists() {
             return Ok(Self::default());
@@ -124,8 +324,102 @@ impl FastmcConfig {
         Ok(config)
     }
 
+    /// Saves the config, leaving out values overridden from the environment.
     pub fn save(&self) -> Result<(), ConfigError> {
-        let path = config_file()?;
+        self.stored().write(&config_file()?)
+    }
+
+    /// Writes the settings to `path` for [`FastmcConfig::import`] on another
+    /// install, as [`FastmcConfig::save`] would store them.
+    pub fn export(&self, path: &Path) -> Result<(), ConfigError> {
+        self.stored().write(path)
+    }
+
+    /// Reads settings written by [`FastmcConfig::export`], checking them
+    /// before anything is applied. The caller decides what to save.
+    pub fn import(path: &Path) -> Result<Self, ConfigError> {
+        let content = fs::read_to_string(path)?;
+        let mut config: FastmcConfig = serde_json::from_str(&content)?;
+        if config.version > CONFIG_VERSION {
+            return Err(ConfigError::Invalid(
+                "exported by a newer fastMC".to_string(),
+            ));
+        }
+        migrate(&mut config);
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Puts every setting back to its default. What the launcher records for
+    /// itself, the last seen version and detected Java installations, is kept.
+    pub fn reset(&mut self) {
+        let defaults = Self {
+            launcher: LauncherConfig {
+                last_seen_version: self.launcher.last_seen_version.take(),
+                ..LauncherConfig::default()
+            },
+            java: JavaConfig {
+                detected_installations: std::mem::take(&mut self.java.detected_installations),
+                ..JavaConfig::default()
+            },
+            ..Self::default()
+        };
+        *self = defaults;
+    }
+
+    /// Takes the settings that only make sense on this machine from
+    /// `current`: the instances folder, the account store, the Java path and
+    /// detected installations, and the last seen version. Imported and reset
+    /// configs keep them, since another install's paths rarely exist here.
+    pub fn keep_machine_specific(&mut self, current: &FastmcConfig) {
+        self.profiles.instances_dir = current.profiles.instances_dir.clone();
+        self.accounts.store_path = current.accounts.store_path.clone();
+        self.java.java_path = current.java.java_path.clone();
+        self.java.detected_installations = current.java.detected_installations.clone();
+        self.launcher.last_seen_version = current.launcher.last_seen_version.clone();
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |reason: &str| Err(ConfigError::Invalid(reason.to_string()));
+        if self.java.min_memory_mb == 0 || self.java.min_memory_mb > self.java.max_memory_mb {
+            return invalid("minimum memory must be between 1 MB and the maximum memory");
+        }
+        if self.downloads.max_concurrent == 0 {
+            return invalid("at least one download must be allowed at a time");
+        }
+        if self.downloads.initial_backoff_ms > self.downloads.max_backoff_ms {
+            return invalid("the first retry delay is longer than the longest one");
+        }
+        if self
+            .downloads
+            .mirrors
+            .iter()
+            .any(|mirror| mirror.from.trim().is_empty() || mirror.to.trim().is_empty())
+        {
+            return invalid("a download mirror has an empty address");
+        }
+        Ok(())
+    }
+
+    /// The config as stored: values overridden from the environment keep
+    /// what the file had, so an override never outlives its variable.
+    fn stored(&self) -> Self {
+        let mut stored = self.clone();
+        if ENV_OVERRIDES
+            .iter()
+            .any(|(var, _)| env_override(var).is_some())
+        {
+            let mut on_disk = Self::load_file().unwrap_or_default();
+            for (var, field) in ENV_OVERRIDES {
+                if env_override(var).is_some() {
+                    *field(&mut stored) = field(&mut on_disk).take();
+                }
+            }
+        }
+        stored
+    }
+
+    fn write(&self, path: &Path) -> Result<(), ConfigError> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -144,6 +438,14 @@ fn config_file() -> Result<PathBuf, ConfigError> {
     Ok(dirs.config_dir().join("config.json"))
 }
 
+fn env_override(var: &str) -> Option<String> {
+    non_empty(std::env::var(var).ok())
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.is_empty())
+}
+
 fn migrate(config: &mut FastmcConfig) {
     if config.version < CONFIG_VERSION {
         config.version = CONFIG_VERSION;
@@ -162,6 +464,26 @@ fn default_max_memory_mb() -> u32 {
     4096
 }
 
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    500
+}
+
+fn default_max_backoff_ms() -> u64 {
+    8000
+}
+
+fn default_max_concurrent() -> usize {
+    8
+}
+
+fn default_log_retention_days() -> u32 {
+    30
+}
+
 fn default_true() -> bool {
     true
 }
@@ -178,3 +500,57 @@ pub struct JavaInstallationRecord {
     #[serde(default)]
     pub source: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_overrides_replace_values_and_skip_empty_ones() {
+        let mut config = FastmcConfig::default();
+        config.java.java_path = Some("/usr/bin/java".to_string());
+        config.profiles.default_profile = Some("Survival".to_string());
+
+        config.apply_overrides(|var| match var {
+            "FASTMC_JAVA_PATH" => Some("/opt/jdk/bin/java".to_string()),
+            "FASTMC_DEFAULT_PROFILE" => Some(String::new()),
+            "FASTMC_INSTANCES_DIR" => Some("/srv/instances".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(config.java.java_path.as_deref(), Some("/opt/jdk/bin/java"));
+        assert_eq!(config.profiles.default_profile.as_deref(), Some("Survival"));
+        assert_eq!(
+            config.profiles.instances_dir.as_deref(),
+            Some("/srv/instances")
+        );
+        assert_eq!(config.accounts.microsoft_client_id, None);
+    }
+
+    #[test]
+    fn imported_configs_keep_machine_specific_settings() {
+        let mut current = FastmcConfig::default();
+        current.profiles.instances_dir = Some("/srv/instances".to_string());
+        current.accounts.store_path = Some("/srv/accounts.json".to_string());
+        current.java.java_path = Some("/opt/jdk/bin/java".to_string());
+
+        let mut imported = FastmcConfig::default();
+        imported.java.java_path = Some("C:\\Java\\bin\\javaw.exe".to_string());
+        imported.java.max_memory_mb = 8192;
+        imported.keep_machine_specific(&current);
+
+        assert_eq!(
+            imported.profiles.instances_dir.as_deref(),
+            Some("/srv/instances")
+        );
+        assert_eq!(
+            imported.accounts.store_path.as_deref(),
+            Some("/srv/accounts.json")
+        );
+        assert_eq!(
+            imported.java.java_path.as_deref(),
+            Some("/opt/jdk/bin/java")
+        );
+        assert_eq!(imported.java.max_memory_mb, 8192);
+    }
+}
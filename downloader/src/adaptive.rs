@@ -0,0 +1,74 @@
+use std::time::Instant;
+
+/// Requests finished between two adjustments.
+pub(crate) const WINDOW: usize = 8;
+
+/// Never go below this many parallel requests, however flaky the link.
+const MIN_CONCURRENCY: usize = 2;
+
+/// Retries plus failures per finished request above which parallelism is
+/// halved.
+const MAX_ERROR_RATE: f64 = 0.25;
+
+/// Additive-increase/multiplicative-decrease limit on parallel downloads,
+/// driven by the error rate and throughput of the last [`WINDOW`] requests.
+pub(crate) struct AdaptiveConcurrency {
+    max: usize,
+    current: usize,
+    window_start: Instant,
+    window_done: usize,
+    window_errors: u32,
+    window_bytes: u64,
+    last_throughput: f64,
+    bytes_per_sec: u64,
+}
+
+impl AdaptiveConcurrency {
+    pub(crate) fn new(max: usize) -> Self {
+        let max = max.max(1);
+        Self {
+            max,
+            current: max.min(4),
+            window_start: Instant::now(),
+            window_done: 0,
+            window_errors: 0,
+            window_bytes: 0,
+            last_throughput: 0.0,
+            bytes_per_sec: 0,
+        }
+    }
+
+    pub(crate) fn current(&self) -> usize {
+        self.current
+    }
+
+    pub(crate) fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    pub(crate) fn record(&mut self, bytes: u64, retries: u32, failed: bool) {
+        self.window_done += 1;
+        self.window_bytes += bytes;
+        self.window_errors += retries + u32::from(failed);
+        if self.window_done < WINDOW {
+            return;
+        }
+
+        let elapsed = self.window_start.elapsed().as_secs_f64().max(0.001);
+        let throughput = self.window_bytes as f64 / elapsed;
+        let error_rate = f64::from(self.window_errors) / self.window_done as f64;
+
+        if error_rate > MAX_ERROR_RATE {
+            self.current = (self.current / 2).max(MIN_CONCURRENCY.min(self.max));
+        } else if throughput >= self.last_throughput * 0.9 {
+            self.current = (self.current + 1).min(self.max);
+        }
+
+        self.last_throughput = throughput;
+        self.bytes_per_sec = throughput as u64;
+        self.window_start = Instant::now();
+        self.window_done = 0;
+        self.window_errors = 0;
+        self.window_bytes = 0;
+    }
+}
@@ -0,0 +1,101 @@
+use crate::mirrors::mirrored;
+use reqwest::Url;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::{TcpStream, lookup_host};
+
+/// Well-known Minecraft mirror, used to tell "Mojang is unreachable" apart
+/// from "the internet is unreachable".
+const KNOWN_MIRROR: &str = "bmclapi2.bangbang93.com:443";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What [`diagnose`] found out about the route to a host.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnosis {
+    pub host: String,
+    /// Whether the host name resolved at all.
+    pub resolved: bool,
+    /// Whether a TCP connection over IPv4 succeeded; `None` when the host has
+    /// no IPv4 address.
+    pub ipv4: Option<bool>,
+    /// Same as `ipv4`, for IPv6.
+    pub ipv6: Option<bool>,
+    /// Whether a well-known mirror was reachable.
+    pub known_mirror: bool,
+}
+
+impl Diagnosis {
+    /// Actionable suggestions for the user, most likely cause first.
+    pub fn hints(&self) -> Vec<String> {
+        let mut hints = Vec::new();
+        if !self.resolved {
+            hints.push(format!(
+                "Could not resolve {}. Check your DNS settings or try a public resolver such as 1.1.1.1.",
+                self.host
+            ));
+        } else if self.ipv6 == Some(false) && self.ipv4 == Some(true) {
+            hints.push(format!(
+                "{} is reachable over IPv4 but not IPv6. Set `\"prefer_ipv4\": true` in the `\"downloads\"` object of config.json.",
+                self.host
+            ));
+        } else if self.ipv4 != Some(true) && self.ipv6 != Some(true) && self.known_mirror {
+            hints.push(format!(
+                "{} is unreachable but other servers are not. A firewall, proxy or VPN may be blocking it; a download mirror can be added to `\"mirrors\"` in the `\"downloads\"` object of config.json.",
+                self.host
+            ));
+        }
+        if !self.known_mirror && self.ipv4 != Some(true) && self.ipv6 != Some(true) {
+            hints.push("No server could be reached. Check your internet connection.".to_string());
+        }
+        hints
+    }
+}
+
+/// Probes the host of `url` (after mirror rewriting): DNS resolution, a TCP
+/// connection over each IP family, and a well-known mirror for comparison.
+pub async fn diagnose(url: &str) -> Diagnosis {
+    let url = mirrored(url);
+    let Some((host, port)) = Url::parse(&url)
+        .ok()
+        .and_then(|url| Some((url.host_str()?.to_string(), url.port_or_known_default()?)))
+    else {
+        return Diagnosis::default();
+    };
+
+    let addrs: Vec<SocketAddr> = lookup_host((host.as_str(), port))
+        .await
+        .map(Iterator::collect)
+        .unwrap_or_default();
+    let ipv4 = match addrs.iter().find(|addr| addr.is_ipv4()) {
+        Some(addr) => Some(reachable(*addr).await),
+        None => None,
+    };
+    let ipv6 = match addrs.iter().find(|addr| addr.is_ipv6()) {
+        Some(addr) => Some(reachable(*addr).await),
+        None => None,
+    };
+
+    let known_mirror = match lookup_host(KNOWN_MIRROR).await {
+        Ok(mut addrs) => match addrs.next() {
+            Some(addr) => reachable(addr).await,
+            None => false,
+        },
+        Err(_) => false,
+    };
+
+    Diagnosis {
+        host,
+        resolved: !addrs.is_empty(),
+        ipv4,
+        ipv6,
+        known_mirror,
+    }
+}
+
+async fn reachable(addr: SocketAddr) -> bool {
+    matches!(
+        tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(addr)).await,
+        Ok(Ok(_))
+    )
+}
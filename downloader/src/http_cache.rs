@@ -0,0 +1,133 @@
+use reqwest::header::{ETAG, HeaderName, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::fs;
+
+use crate::{DownloadError, mirrored};
+
+/// Validators remembered for a cached response, stored next to the body.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheMeta {
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    last_modified: Option<String>,
+    /// Unix time of the last successful fetch or revalidation.
+    #[serde(default)]
+    fetched_at: u64,
+}
+
+/// Fetches `url` into `dest`, revalidating an existing copy with
+/// `If-None-Match`/`If-Modified-Since` so unchanged resources cost a 304
+/// instead of a full download. A copy validated less than `max_age` ago is
+/// used without touching the network at all.
+///
+/// Returns `true` when `dest` holds a usable body afterwards. A stale copy is
+/// kept (and reported as usable) if the server errors or is unreachable.
+pub async fn fetch_cached(
+    client: &Client,
+    url: &str,
+    dest: &Path,
+    max_age: Duration,
+) -> Result<bool, DownloadError> {
+    let meta_path = meta_path(dest);
+    let cached = dest.exists();
+    let mut meta = if cached {
+        load_meta(&meta_path).await
+    } else {
+        CacheMeta::default()
+    };
+
+    if cached && now().saturating_sub(meta.fetched_at) < max_age.as_secs() {
+        return Ok(true);
+    }
+
+    let url = mirrored(url);
+    let mut request = client.get(&url);
+    if let Some(etag) = &meta.etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &meta.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(_) if cached => return Ok(true),
+        Err(source) => {
+            return Err(DownloadError::Http {
+                url: url.clone(),
+                source,
+            });
+        }
+    };
+
+    if response.status() == StatusCode::NOT_MODIFIED && cached {
+        meta.fetched_at = now();
+        save_meta(&meta_path, &meta).await?;
+        return Ok(true);
+    }
+    // An error status we can paper over with the stale copy.
+    if !response.status().is_success() {
+        return Ok(cached);
+    }
+
+    let header = |name: HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let meta = CacheMeta {
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
+        fetched_at: now(),
+    };
+
+    let bytes = match response.bytes().await {
+        Ok(bytes) => bytes,
+        Err(_) if cached => return Ok(true),
+        Err(source) => {
+            return Err(DownloadError::Http {
+                url: url.clone(),
+                source,
+            });
+        }
+    };
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(dest, bytes).await?;
+    save_meta(&meta_path, &meta).await?;
+    Ok(true)
+}
+
+fn meta_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".meta.json");
+    dest.with_file_name(name)
+}
+
+async fn load_meta(path: &Path) -> CacheMeta {
+    fs::read_to_string(path)
+        .await
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+async fn save_meta(path: &Path, meta: &CacheMeta) -> Result<(), DownloadError> {
+    let json = serde_json::to_string(meta).map_err(std::io::Error::from)?;
+    fs::write(path, json).await?;
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
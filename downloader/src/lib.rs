@@ -0,0 +1,587 @@
+mod adaptive;
+mod diagnostics;
+mod http_cache;
+mod mirrors;
+
+pub use diagnostics::{Diagnosis, diagnose};
+pub use http_cache::fetch_cached;
+pub use mirrors::{mirrored, set_mirrors};
+
+use adaptive::AdaptiveConcurrency;
+use futures::StreamExt;
+use futures::stream::FuturesUnordered;
+use reqwest::{Client, ClientBuilder, StatusCode};
+use sha1::{Digest, Sha1};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+pub use tokio_util::sync::CancellationToken;
+
+/// How long to wait for a server to accept the connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+/// How long a transfer may stall between two reads before it's retried.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Error)]
+pub enum DownloadError {
+    #[error("download cancelled")]
+    Cancelled,
+    #[error("failed to GET {url}: {source}")]
+    Http {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("download of {url} failed: {status}")]
+    Status { url: String, status: StatusCode },
+    #[error("checksum mismatch for {url}: expected {expected}, got {actual}")]
+    Checksum {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl DownloadError {
+    /// Timeouts, connection resets, 5xx/429 and checksum mismatches are worth
+    /// another attempt; everything else fails immediately.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            DownloadError::Http { source, .. } => {
+                source.is_timeout()
+                    || source.is_connect()
+                    || source.is_request()
+                    || source.is_body()
+            }
+            DownloadError::Status { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            DownloadError::Checksum { .. } => true,
+            DownloadError::Cancelled | DownloadError::Io(_) => false,
+        }
+    }
+
+    /// Whether the server could not be reached at all, as opposed to
+    /// answering with an error. Worth running [`diagnose`] for.
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self, DownloadError::Http { source, .. } if source.is_connect() || source.is_timeout())
+    }
+
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            DownloadError::Http { url, .. }
+            | DownloadError::Status { url, .. }
+            | DownloadError::Checksum { url, .. } => Some(url),
+            DownloadError::Cancelled | DownloadError::Io(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// How many times a failed download is retried before giving up.
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on each further attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff for the given retry (1-based), capped at
+    /// `max_backoff`, with up to 50% random jitter so parallel downloads don't
+    /// hammer the server in lockstep.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let initial = self.initial_backoff.as_millis() as u64;
+        let exp = initial.saturating_mul(1u64 << retry.saturating_sub(1).min(16));
+        let base = exp.min(self.max_backoff.as_millis() as u64);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos() as u64)
+            .unwrap_or_default();
+        let jitter = if base > 1 { nanos % (base / 2 + 1) } else { 0 };
+        Duration::from_millis(base + jitter)
+    }
+}
+
+/// A single file to fetch.
+#[derive(Debug, Clone)]
+pub struct DownloadRequest {
+    pub url: String,
+    pub dest: PathBuf,
+    /// Expected SHA1 of the body. Verified before anything is written, and
+    /// used to skip files that are already present and intact.
+    pub sha1: Option<String>,
+}
+
+impl DownloadRequest {
+    pub fn new(url: impl Into<String>, dest: impl Into<PathBuf>) -> Self {
+        Self {
+            url: url.into(),
+            dest: dest.into(),
+            sha1: None,
+        }
+    }
+
+    pub fn with_sha1(mut self, sha1: Option<String>) -> Self {
+        self.sha1 = sha1;
+        self
+    }
+
+    fn display_name(&self) -> String {
+        self.dest
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.url.clone())
+    }
+}
+
+/// Snapshot handed to progress callbacks after each finished request.
+#[derive(Debug, Clone, Default)]
+pub struct DownloadProgress {
+    pub completed: usize,
+    pub total: usize,
+    /// Bytes received over the network so far in this batch.
+    pub bytes: u64,
+    /// File name of the request that just finished.
+    pub current: String,
+    /// Throughput measured over the last few finished requests.
+    pub bytes_per_sec: u64,
+    /// Requests currently allowed in flight.
+    pub concurrency: usize,
+}
+
+#[derive(Debug)]
+pub struct DownloadFailure {
+    pub request: DownloadRequest,
+    pub error: DownloadError,
+}
+
+/// Outcome of [`Downloader::download_all`].
+#[derive(Debug, Default)]
+pub struct DownloadSummary {
+    pub bytes: u64,
+    pub failures: Vec<DownloadFailure>,
+}
+
+impl DownloadSummary {
+    /// Turns the first failure (if any) into an error, for batches where every
+    /// file is required.
+    pub fn ensure_complete(self) -> Result<u64, DownloadError> {
+        match self.failures.into_iter().next() {
+            Some(failure) => Err(failure.error),
+            None => Ok(self.bytes),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Downloader {
+    client: Client,
+    policy: RetryPolicy,
+    concurrency: usize,
+}
+
+impl Default for Downloader {
+    fn default() -> Self {
+        Self::new(RetryPolicy::default())
+    }
+}
+
+impl Downloader {
+    /// Panics like [`Client::new`] when the TLS backend can't be initialized.
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self {
+            client: client_builder()
+                .build()
+                .expect("failed to initialize the HTTP client"),
+            policy,
+            concurrency: 8,
+        }
+    }
+
+    /// Maximum number of requests [`download_all`](Self::download_all) keeps
+    /// in flight at once. The actual number adapts to the connection below
+    /// this cap.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Connects over IPv4 only, for networks whose IPv6 route to the download
+    /// servers is broken.
+    pub fn with_ipv4_only(mut self, ipv4_only: bool) -> Result<Self, reqwest::Error> {
+        if ipv4_only {
+            self.client = client_builder()
+                .local_address(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+                .build()?;
+        }
+        Ok(self)
+    }
+
+    /// The HTTP client downloads go through, for one-off requests that should
+    /// share its timeouts and IPv4 setting.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Fetches `request` unless its destination already matches the expected
+    /// SHA1 (or simply exists, when no SHA1 is known). Returns the number of
+    /// bytes downloaded, 0 when the file was skipped.
+    pub async fn ensure(
+        &self,
+        request: &DownloadRequest,
+        cancel: &CancellationToken,
+    ) -> Result<u64, DownloadError> {
+        self.ensure_tracked(request, cancel).await.0
+    }
+
+    /// Fetches `request`, retrying transient failures with exponential backoff.
+    /// The body is hashed as it streams to a temporary file, which only
+    /// replaces the destination once it matches, so a corrupt file never
+    /// lands there. Returns the number of bytes written.
+    pub async fn download(
+        &self,
+        request: &DownloadRequest,
+        cancel: &CancellationToken,
+    ) -> Result<u64, DownloadError> {
+        self.download_tracked(request, cancel).await.0
+    }
+
+    /// [`ensure`](Self::ensure), also returning how many retries it took.
+    async fn ensure_tracked(
+        &self,
+        request: &DownloadRequest,
+        cancel: &CancellationToken,
+    ) -> (Result<u64, DownloadError>, u32) {
+        let up_to_date = match &request.sha1 {
+            Some(sha1) => file_matches_sha1(&request.dest, sha1).await,
+            None => request.dest.exists(),
+        };
+        if up_to_date {
+            return (Ok(0), 0);
+        }
+        self.download_tracked(request, cancel).await
+    }
+
+    async fn download_tracked(
+        &self,
+        request: &DownloadRequest,
+        cancel: &CancellationToken,
+    ) -> (Result<u64, DownloadError>, u32) {
+        let attempts = self.policy.max_retries + 1;
+        let mut attempt = 1;
+
+        loop {
+            let result = tokio::select! {
+                _ = cancel.cancelled() => return (Err(DownloadError::Cancelled), attempt - 1),
+                result = self.try_download(request) => result,
+            };
+
+            let error = match result {
+                Ok(bytes) => return (Ok(bytes), attempt - 1),
+                Err(error) if error.is_transient() && attempt < attempts => error,
+                Err(error) => return (Err(error), attempt - 1),
+            };
+
+            let delay = self.policy.delay(attempt);
+            println!(
+                "{} (attempt {}/{}, retrying in {:?})",
+                error, attempt, attempts, delay
+            );
+            tokio::select! {
+                _ = cancel.cancelled() => return (Err(DownloadError::Cancelled), attempt),
+                _ = tokio::time::sleep(delay) => {}
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Runs [`ensure`](Self::ensure) for every request, calling `on_progress`
+    /// as each one finishes. Parallelism starts low and adapts to the
+    /// connection: it backs off when requests keep failing or retrying and
+    /// ramps up towards `concurrency` while throughput holds. Individual
+    /// failures are collected in the summary; only cancellation aborts the
+    /// batch.
+    pub async fn download_all<F>(
+        &self,
+        requests: Vec<DownloadRequest>,
+        cancel: &CancellationToken,
+        mut on_progress: F,
+    ) -> Result<DownloadSummary, DownloadError>
+    where
+        F: FnMut(&DownloadProgress) + Send,
+    {
+        let total = requests.len();
+        let mut progress = DownloadProgress {
+            total,
+            ..DownloadProgress::default()
+        };
+        let mut summary = DownloadSummary::default();
+        let mut limit = AdaptiveConcurrency::new(self.concurrency);
+
+        let mut pending = requests.into_iter();
+        let mut in_flight = FuturesUnordered::new();
+        loop {
+            while in_flight.len() < limit.current() {
+                let Some(request) = pending.next() else {
+                    break;
+                };
+                in_flight.push(async move {
+                    let (result, retries) = self.ensure_tracked(&request, cancel).await;
+                    (request, result, retries)
+                });
+            }
+            let Some((request, result, retries)) = in_flight.next().await else {
+                break;
+            };
+
+            progress.current = request.display_name();
+            match result {
+                Ok(bytes) => {
+                    summary.bytes += bytes;
+                    limit.record(bytes, retries, false);
+                }
+                Err(DownloadError::Cancelled) => return Err(DownloadError::Cancelled),
+                Err(error) => {
+                    println!("Failed to download {}: {}", request.url, error);
+                    summary.failures.push(DownloadFailure { request, error });
+                    limit.record(0, retries, true);
+                }
+            }
+
+            progress.completed += 1;
+            progress.bytes = summary.bytes;
+            progress.bytes_per_sec = limit.bytes_per_sec();
+            progress.concurrency = limit.current();
+            on_progress(&progress);
+        }
+
+        Ok(summary)
+    }
+
+    async fn try_download(&self, request: &DownloadRequest) -> Result<u64, DownloadError> {
+        let url = mirrored(&request.url);
+        println!("Downloading {} to {:?}", url, request.dest);
+        let http_error = |source| DownloadError::Http {
+            url: url.clone(),
+            source,
+        };
+
+        let mut response = self.client.get(&url).send().await.map_err(http_error)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(DownloadError::Status {
+                url: url.clone(),
+                status,
+            });
+        }
+
+        if let Some(parent) = request.dest.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        // Renaming is atomic, so an interrupted download never leaves a
+        // truncated file where a later run would take it for a complete one
+        let partial = partial_path(&request.dest);
+        let mut file = fs::File::create(&partial).await?;
+        let mut hasher = Sha1::new();
+        let mut len = 0;
+        let streamed = async {
+            while let Some(chunk) = response.chunk().await.map_err(http_error)? {
+                hasher.update(&chunk);
+                file.write_all(&chunk).await?;
+                len += chunk.len() as u64;
+            }
+            file.flush().await?;
+            Ok(())
+        }
+        .await;
+        drop(file);
+        if let Err(error) = streamed {
+            let _ = fs::remove_file(&partial).await;
+            return Err(error);
+        }
+
+        if let Some(expected) = &request.sha1 {
+            let actual = format!("{:x}", hasher.finalize());
+            if !actual.eq_ignore_ascii_case(expected) {
+                let _ = fs::remove_file(&partial).await;
+                return Err(DownloadError::Checksum {
+                    url: url.clone(),
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+
+        fs::rename(&partial, &request.dest).await?;
+        Ok(len)
+    }
+}
+
+/// Timeouts shared by every client: the defaults let a stalled connection
+/// hang a download forever.
+fn client_builder() -> ClientBuilder {
+    Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .read_timeout(READ_TIMEOUT)
+}
+
+/// Where a download to `dest` is written before it is moved into place.
+fn partial_path(dest: &Path) -> PathBuf {
+    let mut name = dest.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+/// Returns true when `path` exists and its SHA1 equals `expected`.
+pub async fn file_matches_sha1(path: &Path, expected: &str) -> bool {
+    match fs::read(path).await {
+        Ok(bytes) => sha1_hex(&bytes).eq_ignore_ascii_case(expected),
+        Err(_) => false,
+    }
+}
+
+pub fn sha1_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha1::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(1000),
+        };
+
+        let first = policy.delay(1).as_millis();
+        assert!((100..=150).contains(&first));
+
+        let third = policy.delay(3).as_millis();
+        assert!((400..=600).contains(&third));
+
+        let capped = policy.delay(10).as_millis();
+        assert!((1000..=1500).contains(&capped));
+    }
+
+    #[test]
+    fn summary_reports_first_failure() {
+        let summary = DownloadSummary {
+            bytes: 10,
+            failures: vec![DownloadFailure {
+                request: DownloadRequest::new("https://example.invalid/a", "a"),
+                error: DownloadError::Cancelled,
+            }],
+        };
+        assert!(matches!(
+            summary.ensure_complete(),
+            Err(DownloadError::Cancelled)
+        ));
+        assert_eq!(DownloadSummary::default().ensure_complete().unwrap(), 0);
+    }
+
+    #[test]
+    fn concurrency_backs_off_on_errors_and_ramps_up() {
+        let mut limit = AdaptiveConcurrency::new(8);
+        assert_eq!(limit.current(), 4);
+
+        for _ in 0..adaptive::WINDOW {
+            limit.record(0, 1, true);
+        }
+        assert_eq!(limit.current(), 2);
+        for _ in 0..adaptive::WINDOW {
+            limit.record(0, 1, false);
+        }
+        assert_eq!(limit.current(), 2);
+
+        for _ in 0..adaptive::WINDOW * 10 {
+            limit.record(1024, 0, false);
+        }
+        assert_eq!(limit.current(), 8);
+    }
+
+    #[test]
+    fn diagnosis_hints() {
+        let broken_ipv6 = Diagnosis {
+            host: "piston-meta.mojang.com".to_string(),
+            resolved: true,
+            ipv4: Some(true),
+            ipv6: Some(false),
+            known_mirror: true,
+        };
+        let hints = broken_ipv6.hints();
+        assert_eq!(hints.len(), 1);
+        assert!(hints[0].contains("prefer_ipv4"));
+
+        let offline = Diagnosis {
+            resolved: true,
+            ipv4: Some(false),
+            known_mirror: false,
+            ..broken_ipv6.clone()
+        };
+        assert!(offline.hints()[0].contains("internet connection"));
+
+        let healthy = Diagnosis {
+            ipv6: Some(true),
+            ..broken_ipv6
+        };
+        assert!(healthy.hints().is_empty());
+    }
+
+    #[test]
+    fn mirror_rewrites_matching_prefix_only() {
+        let rules = vec![(
+            "https://libraries.minecraft.net/".to_string(),
+            "https://bmclapi2.bangbang93.com/maven/".to_string(),
+        )];
+        assert_eq!(
+            mirrors::rewrite(&rules, "https://libraries.minecraft.net/com/a/a.jar"),
+            "https://bmclapi2.bangbang93.com/maven/com/a/a.jar"
+        );
+        assert_eq!(
+            mirrors::rewrite(&rules, "https://maven.fabricmc.net/a.jar"),
+            "https://maven.fabricmc.net/a.jar"
+        );
+    }
+
+    #[test]
+    fn mirror_host_rules_match_any_scheme() {
+        let rules = vec![(
+            "libraries.minecraft.net".to_string(),
+            "mirror.school.lan/libraries".to_string(),
+        )];
+        assert_eq!(
+            mirrors::rewrite(&rules, "https://libraries.minecraft.net/com/a/a.jar"),
+            "https://mirror.school.lan/libraries/com/a/a.jar"
+        );
+        assert_eq!(
+            mirrors::rewrite(&rules, "http://libraries.minecraft.net/com/a/a.jar"),
+            "http://mirror.school.lan/libraries/com/a/a.jar"
+        );
+        assert_eq!(
+            mirrors::rewrite(&rules, "https://libraries.minecraft.net.example/a.jar"),
+            "https://libraries.minecraft.net.example/a.jar"
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::RwLock;
+
+static MIRRORS: RwLock<Vec<(String, String)>> = RwLock::new(Vec::new());
+
+/// Replaces the process-wide mirror table with `(prefix, replacement)` pairs.
+pub fn set_mirrors(rules: Vec<(String, String)>) {
+    *MIRRORS.write().unwrap_or_else(|e| e.into_inner()) = rules;
+}
+
+/// Returns `url` with the first matching mirror prefix replaced, or `url`
+/// unchanged when no mirror applies.
+pub fn mirrored(url: &str) -> String {
+    rewrite(&MIRRORS.read().unwrap_or_else(|e| e.into_inner()), url)
+}
+
+/// A rule's `from` is either a URL prefix with its scheme
+/// (`https://libraries.minecraft.net/`) or a bare host and optional path
+/// (`libraries.minecraft.net`) matched under any scheme. A `to` without a
+/// scheme keeps the original URL's.
+pub(crate) fn rewrite(rules: &[(String, String)], url: &str) -> String {
+    let (scheme, without_scheme) = url.split_once("://").unwrap_or(("https", url));
+    rules
+        .iter()
+        .find_map(|(from, to)| {
+            let rest = if from.contains("://") {
+                url.strip_prefix(from.as_str())?
+            } else {
+                let rest = without_scheme.strip_prefix(from.as_str())?;
+                // `example.com` must not match `example.com.evil`
+                let boundary =
+                    from.ends_with('/') || rest.is_empty() || rest.starts_with(['/', ':', '?']);
+                boundary.then_some(rest)?
+            };
+            Some(if to.contains("://") {
+                format!("{}{}", to, rest)
+            } else {
+                format!("{}://{}{}", scheme, to, rest)
+            })
+        })
+        .unwrap_or_else(|| url.to_string())
+}
@@ -0,0 +1,230 @@
+use crate::{GameDirs, InstallProgress, explain};
+use downloader::{CancellationToken, DownloadError, DownloadFailure, DownloadRequest, Downloader};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs;
+use version_manager::AssetIndex;
+
+/// Extra rounds given to failed asset downloads once the rest of the batch is
+/// done, on top of the downloader's own per-file retries.
+const ASSET_RETRY_ROUNDS: u32 = 2;
+
+pub(crate) struct InstalledAssets {
+    /// Where the game reads its assets from, see [`crate::Installation`].
+    pub launch_dir: PathBuf,
+    pub missing: usize,
+}
+
+/// Downloads the asset index and its objects, then lays out the copies that
+/// versions before 1.7 read instead of the object store.
+pub(crate) async fn install_assets(
+    downloader: &Downloader,
+    asset_index: &AssetIndex,
+    dirs: &GameDirs,
+    cancel: &CancellationToken,
+    base: u64,
+    on_progress: &(impl Fn(InstallProgress) + Sync),
+) -> Result<InstalledAssets, String> {
+    let assets_dir = &dirs.assets;
+    let asset_index_path = assets_dir
+        .join("indexes")
+        .join(format!("{}.json", asset_index.id));
+    let index_request = DownloadRequest::new(&asset_index.url, &asset_index_path)
+        .with_sha1(asset_index.sha1.clone());
+    let downloaded = match downloader.ensure(&index_request, cancel).await {
+        Ok(bytes) => bytes,
+        Err(e) => return Err(explain(e).await),
+    };
+
+    println!("Verifying assets from index: {:?}", asset_index_path);
+    let index_content = fs::read_to_string(&asset_index_path)
+        .await
+        .map_err(|e| e.to_string())?;
+    let index_data: serde_json::Value =
+        serde_json::from_str(&index_content).map_err(|e| e.to_string())?;
+
+    let mut map_to_resources = false;
+    let mut is_virtual = false;
+
+    if let Some(val) = index_data.get("map_to_resources") {
+        map_to_resources = val.as_bool().unwrap_or(false);
+    }
+    if let Some(val) = index_data.get("virtual") {
+        is_virtual = val.as_bool().unwrap_or(false);
+    }
+
+    let resources_dir = dirs.game.join("resources");
+    let virtual_assets_dir = assets_dir.join("virtual").join("legacy");
+    // Versions before 1.6 read their sounds and textures from `resources`
+    let launch_dir = if map_to_resources {
+        resources_dir.clone()
+    } else if is_virtual {
+        virtual_assets_dir.clone()
+    } else {
+        assets_dir.clone()
+    };
+    let mut installed = InstalledAssets {
+        launch_dir,
+        missing: 0,
+    };
+
+    let Some(objects) = index_data["objects"].as_object() else {
+        return Ok(installed);
+    };
+    let objects_dir = assets_dir.join("objects");
+
+    if map_to_resources {
+        fs::create_dir_all(&resources_dir)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    if is_virtual {
+        fs::create_dir_all(&virtual_assets_dir)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let assets: Vec<(&String, &str)> = objects
+        .iter()
+        .filter_map(|(name, obj)| {
+            obj["hash"]
+                .as_str()
+                .filter(|hash| hash.len() >= 2)
+                .map(|hash| (name, hash))
+        })
+        .collect();
+    let asset_requests = assets
+        .iter()
+        .map(|(_, hash)| object_request(&objects_dir, hash))
+        .collect();
+
+    let base = base + downloaded;
+    let summary = downloader
+        .download_all(asset_requests, cancel, |p| {
+            // Thousands of assets: only report every few so the UI isn't flooded.
+            if p.completed % 25 == 0 || p.completed == p.total {
+                on_progress(InstallProgress::Item {
+                    file: p.current.clone(),
+                    done: p.completed,
+                    total: p.total,
+                });
+                on_progress(InstallProgress::Bytes(base + p.bytes));
+                on_progress(InstallProgress::Speed(p.bytes_per_sec));
+            }
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Missing assets only show up in game as silent sounds or missing
+    // textures, so the caller gets the count instead of a launchable install.
+    installed.missing = retry_failed_assets(downloader, summary.failures, cancel).await?;
+    if installed.missing > 0 {
+        return Ok(installed);
+    }
+
+    for (name, hash) in assets {
+        let object_path = objects_dir.join(&hash[..2]).join(hash);
+
+        // Copy to resources if legacy (map_to_resources)
+        if map_to_resources && object_path.exists() {
+            let res_path = resources_dir.join(name);
+            if !res_path.exists() {
+                if let Some(p) = res_path.parent() {
+                    fs::create_dir_all(p).await.map_err(|e| e.to_string())?;
+                }
+                fs::copy(&object_path, &res_path)
+                    .await
+                    .map_err(|e| format!("Failed to copy legacy resource {}: {}", name, e))?;
+            }
+        }
+
+        // Copy to virtual/legacy if virtual
+        if is_virtual && object_path.exists() {
+            let virt_path = virtual_assets_dir.join(name);
+            if !virt_path.exists() {
+                if let Some(p) = virt_path.parent() {
+                    fs::create_dir_all(p).await.map_err(|e| e.to_string())?;
+                }
+                fs::copy(&object_path, &virt_path)
+                    .await
+                    .map_err(|e| format!("Failed to copy virtual asset {}: {}", name, e))?;
+            }
+        }
+    }
+
+    // Fix for VanillaTweakInjector looking in assets/icons instead of resources/icons
+    if map_to_resources {
+        let src_icons = resources_dir.join("icons");
+        copy_icons(&src_icons, &assets_dir.join("icons")).await?;
+        // Also copy to virtual assets dir if active
+        if is_virtual {
+            copy_icons(&src_icons, &virtual_assets_dir.join("icons")).await?;
+        }
+    }
+
+    Ok(installed)
+}
+
+/// Request for the object with `hash` in the content-addressed store.
+pub(crate) fn object_request(objects_dir: &Path, hash: &str) -> DownloadRequest {
+    let prefix = &hash[..2];
+    DownloadRequest::new(
+        format!(
+            "https://resources.download.minecraft.net/{}/{}",
+            prefix, hash
+        ),
+        objects_dir.join(prefix).join(hash),
+    )
+    .with_sha1(Some(hash.to_string()))
+}
+
+async fn copy_icons(src: &Path, dst: &Path) -> Result<(), String> {
+    if !src.exists() || dst.exists() {
+        return Ok(());
+    }
+    fs::create_dir_all(dst).await.map_err(|e| e.to_string())?;
+    let mut entries = fs::read_dir(src).await.map_err(|e| e.to_string())?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.is_file() {
+            fs::copy(&path, dst.join(entry.file_name()))
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Retries failed asset downloads after a pause long enough to ride out a
+/// brief outage or rate limit. Returns how many assets are still missing.
+async fn retry_failed_assets(
+    downloader: &Downloader,
+    mut failures: Vec<DownloadFailure>,
+    cancel: &CancellationToken,
+) -> Result<usize, String> {
+    for round in 1..=ASSET_RETRY_ROUNDS {
+        if failures.is_empty() {
+            break;
+        }
+        let delay = Duration::from_secs(2 << round);
+        println!(
+            "Retrying {} failed assets in {:?} (round {}/{})",
+            failures.len(),
+            delay,
+            round,
+            ASSET_RETRY_ROUNDS
+        );
+        tokio::select! {
+            _ = cancel.cancelled() => return Err(DownloadError::Cancelled.to_string()),
+            _ = tokio::time::sleep(delay) => {}
+        }
+
+        let requests = failures.into_iter().map(|f| f.request).collect();
+        failures = downloader
+            .download_all(requests, cancel, |_| {})
+            .await
+            .map_err(|e| e.to_string())?
+            .failures;
+    }
+    Ok(failures.len())
+}
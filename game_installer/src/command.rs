@@ -0,0 +1,139 @@
+use crate::Installation;
+use crate::libraries::{library_names, lwjgl_supports_wayland};
+use launcher::{LaunchAuth, MemorySettings, QuickPlay, Resolution, VanillaLaunchConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Command;
+use version_manager::VersionCapabilities;
+
+/// Windowing system the game opens its window on under Linux.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DisplayServer {
+    /// Whatever GLFW picks for the session.
+    #[default]
+    Auto,
+    /// X11, through XWayland on Wayland sessions.
+    X11,
+    /// Native Wayland. Needs LWJGL 3.3 (Minecraft 1.19+).
+    Wayland,
+}
+
+impl fmt::Display for DisplayServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisplayServer::Auto => write!(f, "Auto"),
+            DisplayServer::X11 => write!(f, "X11 / XWayland"),
+            DisplayServer::Wayland => write!(f, "Wayland"),
+        }
+    }
+}
+
+/// How to run an [`Installation`]: everything the version JSON doesn't say.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    pub java_path: PathBuf,
+    pub memory: Option<MemorySettings>,
+    /// Appended after the version's own JVM arguments.
+    pub extra_jvm_args: Vec<String>,
+    /// Server address to join once the game is up.
+    pub join_server: Option<String>,
+    /// Folder name of a world to open, when `join_server` isn't set.
+    pub join_world: Option<String>,
+    /// Cuts the game off from the network, see
+    /// [`VanillaLaunchConfig::network_isolated`].
+    pub offline: bool,
+    pub display_server: DisplayServer,
+    /// Set last, so they win over the display server hints.
+    pub env_vars: BTreeMap<String, String>,
+}
+
+/// The command that starts `installation` as `auth`. Options the version
+/// can't take are dropped rather than passed along for the game to ignore or
+/// choke on.
+pub fn build_command(
+    installation: &Installation,
+    options: LaunchOptions,
+    auth: &LaunchAuth,
+) -> Command {
+    let version = &installation.version;
+    let dirs = &installation.dirs;
+
+    let capabilities = VersionCapabilities::from_release_time(&version.release_time);
+    let join_server = options
+        .join_server
+        .clone()
+        .filter(|_| capabilities.join_server);
+    if options.join_server.is_some() && join_server.is_none() {
+        println!("Warning: {} can't join a server on launch", dirs.version_id);
+    }
+    let join_world = options
+        .join_world
+        .clone()
+        .filter(|_| capabilities.quick_play);
+    if options.join_world.is_some() && join_world.is_none() {
+        println!(
+            "Warning: {} has no Quick Play to open a world",
+            dirs.version_id
+        );
+    }
+
+    let config = VanillaLaunchConfig {
+        java_path: options.java_path,
+        game_dir: dirs.game.clone(),
+        assets_dir: installation.assets_dir.clone(),
+        classpath: installation.classpath.clone(),
+        main_class: version.main_class.clone(),
+        version_name: dirs.version_id.clone(),
+        version_type: version.version_type.clone(),
+        asset_index: Some(version.asset_index.id.clone()),
+        resolution: capabilities.custom_resolution.then_some(Resolution {
+            width: 1280,
+            height: 720,
+        }),
+        memory: options.memory,
+        extra_jvm_args: options.extra_jvm_args,
+        extra_game_args: vec![],
+        natives_dir: Some(dirs.natives.clone()),
+        libraries_dir: Some(dirs.libraries.clone()),
+        network_isolated: options.offline,
+        arguments: version.arguments.clone(),
+        legacy_arguments: version.minecraft_arguments.clone(),
+        quick_play: join_server
+            .map(QuickPlay::Multiplayer)
+            .or_else(|| join_world.map(QuickPlay::Singleplayer)),
+    };
+
+    let mut cmd = config.build_command(auth);
+    let wayland_supported = lwjgl_supports_wayland(&library_names(version));
+    apply_display_server(&mut cmd, options.display_server, wayland_supported);
+    cmd.envs(&options.env_vars);
+    cmd
+}
+
+/// Points GLFW, and SDL used by some mods, at the instance's display server.
+/// Only Linux has a choice.
+fn apply_display_server(cmd: &mut Command, server: DisplayServer, wayland_supported: bool) {
+    if !cfg!(target_os = "linux") {
+        return;
+    }
+    match server {
+        DisplayServer::Auto => {}
+        DisplayServer::X11 => {
+            // Without a Wayland socket GLFW falls back to XWayland
+            cmd.env_remove("WAYLAND_DISPLAY");
+            cmd.env("GLFW_PLATFORM", "x11");
+            cmd.env("SDL_VIDEODRIVER", "x11");
+        }
+        DisplayServer::Wayland if wayland_supported => {
+            cmd.env("GLFW_PLATFORM", "wayland");
+            cmd.env("SDL_VIDEODRIVER", "wayland");
+        }
+        DisplayServer::Wayland => {
+            println!(
+                "Warning: this version's LWJGL predates Wayland support; using the default display server"
+            );
+        }
+    }
+}
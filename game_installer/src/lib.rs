@@ -0,0 +1,340 @@
+//! Installs a Minecraft version into a game directory and builds the command
+//! that starts it, for any front end: [`load_version`] reads or fetches the
+//! version JSON, [`install`] downloads what it lists, [`verify`] checks an
+//! install without downloading, and [`build_command`] assembles the launch.
+
+mod assets;
+mod command;
+mod libraries;
+mod manifest;
+mod natives;
+
+pub use command::{DisplayServer, LaunchOptions, build_command};
+pub use libraries::{host_arch, library_names, maven_to_path, uses_lwjgl2};
+pub use manifest::load_version;
+
+use downloader::{CancellationToken, DownloadError, DownloadRequest, DownloadSummary, Downloader};
+use std::path::PathBuf;
+use tokio::fs;
+use version_manager::VersionDetails;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchPhase {
+    Manifest,
+    /// Fetching a Mojang Java runtime, which the caller does before
+    /// [`install`] since it picks the architecture.
+    Runtime,
+    ClientJar,
+    Libraries,
+    Assets,
+    Launching,
+}
+
+impl std::fmt::Display for LaunchPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LaunchPhase::Manifest => write!(f, "Fetching version manifest"),
+            LaunchPhase::Runtime => write!(f, "Downloading Java runtime"),
+            LaunchPhase::ClientJar => write!(f, "Downloading client"),
+            LaunchPhase::Libraries => write!(f, "Downloading libraries"),
+            LaunchPhase::Assets => write!(f, "Verifying assets"),
+            LaunchPhase::Launching => write!(f, "Starting game"),
+        }
+    }
+}
+
+/// Events emitted by [`install`].
+#[derive(Debug, Clone)]
+pub enum InstallProgress {
+    Phase(LaunchPhase),
+    /// `done` of `total` items of the current phase are processed; `file` is
+    /// the most recent one.
+    Item {
+        file: String,
+        done: usize,
+        total: usize,
+    },
+    /// Bytes received over the network since the install started.
+    Bytes(u64),
+    /// Download speed currently measured by the downloader, in bytes/s.
+    Speed(u64),
+}
+
+/// Where one version of an instance lives. `game` is the `.minecraft`
+/// directory; shared files (versions, libraries, assets) are kept under it.
+#[derive(Debug, Clone)]
+pub struct GameDirs {
+    pub version_id: String,
+    pub game: PathBuf,
+    pub versions: PathBuf,
+    pub libraries: PathBuf,
+    pub assets: PathBuf,
+    pub natives: PathBuf,
+}
+
+impl GameDirs {
+    pub fn new(game_dir: PathBuf, version_id: &str) -> Self {
+        Self {
+            version_id: version_id.to_string(),
+            versions: game_dir.join("versions"),
+            libraries: game_dir.join("libraries"),
+            assets: game_dir.join("assets"),
+            natives: game_dir.join("natives").join(version_id),
+            game: game_dir,
+        }
+    }
+
+    pub async fn create(&self) -> Result<(), String> {
+        for dir in [&self.versions, &self.libraries, &self.assets, &self.natives] {
+            fs::create_dir_all(dir).await.map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// The client jar `version` runs, which a loader profile shares with the
+    /// version it inherits from.
+    fn client_jar(&self, version: &VersionDetails) -> PathBuf {
+        let id = version.jar.as_deref().unwrap_or(&self.version_id);
+        self.versions.join(id).join(format!("{}.jar", id))
+    }
+
+    fn asset_index(&self, version: &VersionDetails) -> PathBuf {
+        self.assets
+            .join("indexes")
+            .join(format!("{}.json", version.asset_index.id))
+    }
+}
+
+/// A version whose files are on disk, ready for [`build_command`].
+#[derive(Debug, Clone)]
+pub struct Installation {
+    pub version: VersionDetails,
+    pub dirs: GameDirs,
+    /// Libraries for the chosen architecture, then the client jar.
+    pub classpath: Vec<PathBuf>,
+    /// Where the game reads its assets from: `resources` before 1.6, the
+    /// virtual copy up to 1.7.2, the object store after.
+    pub assets_dir: PathBuf,
+    /// Assets that failed to download. The game starts without them but
+    /// plays silent sounds and missing textures.
+    pub missing_assets: usize,
+}
+
+/// Downloads the client, libraries and assets `version` needs for a runtime
+/// of `arch` and unpacks its natives.
+pub async fn install(
+    downloader: &Downloader,
+    version: VersionDetails,
+    dirs: &GameDirs,
+    arch: &str,
+    cancel: &CancellationToken,
+    on_progress: impl Fn(InstallProgress) + Sync,
+) -> Result<Installation, String> {
+    let mut downloaded: u64 = 0;
+
+    on_progress(InstallProgress::Phase(LaunchPhase::ClientJar));
+    let client_jar = dirs.client_jar(&version);
+    let client = &version.downloads.client;
+    let client_request =
+        DownloadRequest::new(&client.url, &client_jar).with_sha1(Some(client.sha1.clone()));
+    downloaded += match downloader.ensure(&client_request, cancel).await {
+        Ok(bytes) => bytes,
+        Err(e) => return Err(explain(e).await),
+    };
+    on_progress(InstallProgress::Bytes(downloaded));
+
+    on_progress(InstallProgress::Phase(LaunchPhase::Libraries));
+    if arch == "aarch64" && uses_lwjgl2(&library_names(&version)) {
+        println!(
+            "Warning: {} uses LWJGL 2, which has no ARM64 natives; install an x86_64 Java runtime to run it under emulation",
+            dirs.version_id
+        );
+    }
+    let mut libraries = libraries::resolve(&version, &dirs.libraries, arch);
+    libraries.classpath.push(client_jar);
+
+    let base = downloaded;
+    let summary = downloader
+        .download_all(libraries.requests, cancel, |p| {
+            on_progress(InstallProgress::Item {
+                file: p.current.clone(),
+                done: p.completed,
+                total: p.total,
+            });
+            on_progress(InstallProgress::Bytes(base + p.bytes));
+            on_progress(InstallProgress::Speed(p.bytes_per_sec));
+        })
+        .await
+        .and_then(DownloadSummary::ensure_complete);
+    downloaded += match summary {
+        Ok(bytes) => bytes,
+        Err(e) => return Err(explain(e).await),
+    };
+
+    // Unzipping is blocking work; its progress is relayed from here
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let jars = libraries.natives;
+    let natives_dir = dirs.natives.clone();
+    let extraction = tokio::task::spawn_blocking(move || {
+        natives::extract_natives(&jars, &natives_dir, |file, done, total| {
+            let _ = tx.send(InstallProgress::Item {
+                file: file.to_string(),
+                done,
+                total,
+            });
+        })
+    });
+    while let Some(event) = rx.recv().await {
+        on_progress(event);
+    }
+    extraction.await.map_err(|e| e.to_string())??;
+
+    on_progress(InstallProgress::Phase(LaunchPhase::Assets));
+    let assets = assets::install_assets(
+        downloader,
+        &version.asset_index,
+        dirs,
+        cancel,
+        downloaded,
+        &on_progress,
+    )
+    .await?;
+
+    Ok(Installation {
+        version,
+        dirs: dirs.clone(),
+        classpath: libraries.classpath,
+        assets_dir: assets.launch_dir,
+        missing_assets: assets.missing,
+    })
+}
+
+/// Checks the files [`install`] would fetch for `version` and `arch` without
+/// downloading anything, returning those that are missing or don't match
+/// their checksum. Asset objects are content-addressed and only checked for
+/// presence.
+pub async fn verify(
+    version: &VersionDetails,
+    dirs: &GameDirs,
+    arch: &str,
+) -> Result<Vec<PathBuf>, String> {
+    let client = &version.downloads.client;
+    let index_path = dirs.asset_index(version);
+    let mut requests = vec![
+        DownloadRequest::new(&client.url, dirs.client_jar(version))
+            .with_sha1(Some(client.sha1.clone())),
+        DownloadRequest::new(&version.asset_index.url, &index_path)
+            .with_sha1(version.asset_index.sha1.clone()),
+    ];
+    requests.extend(libraries::resolve(version, &dirs.libraries, arch).requests);
+
+    let mut broken = vec![];
+    for request in requests {
+        let intact = match &request.sha1 {
+            Some(sha1) => downloader::file_matches_sha1(&request.dest, sha1).await,
+            None => request.dest.exists(),
+        };
+        if !intact {
+            broken.push(request.dest);
+        }
+    }
+
+    // Objects can only be listed once the index is there
+    if let Ok(content) = fs::read_to_string(&index_path).await {
+        let index: serde_json::Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+        if let Some(objects) = index["objects"].as_object() {
+            let objects_dir = dirs.assets.join("objects");
+            broken.extend(
+                objects
+                    .values()
+                    .filter_map(|obj| obj["hash"].as_str())
+                    .filter(|hash| hash.len() >= 2)
+                    .map(|hash| assets::object_request(&objects_dir, hash).dest)
+                    .filter(|path| !path.exists()),
+            );
+        }
+    }
+    Ok(broken)
+}
+
+/// Stringifies a download error for the UI. When the server couldn't be
+/// reached at all, probes the connection and appends what might fix it.
+pub async fn explain(error: DownloadError) -> String {
+    let mut message = error.to_string();
+    if let Some(url) = error.url().filter(|_| error.is_unreachable()) {
+        let diagnosis = downloader::diagnose(url).await;
+        println!("Connection diagnosis: {:?}", diagnosis);
+        for hint in diagnosis.hints() {
+            message.push('\n');
+            message.push_str(&hint);
+        }
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libraries::{lwjgl_lacks_arm64, native_arch_applies, native_classifier};
+    use std::collections::HashSet;
+    use std::path::Path;
+    use version_manager::Library;
+
+    fn library(json: serde_json::Value) -> Library {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn maven_coordinates_map_to_repository_paths() {
+        assert_eq!(
+            maven_to_path("net.fabricmc:fabric-loader:0.15.0"),
+            Path::new("net/fabricmc/fabric-loader/0.15.0/fabric-loader-0.15.0.jar")
+        );
+    }
+
+    #[test]
+    fn lwjgl_before_3_3_1_lacks_arm64() {
+        assert!(lwjgl_lacks_arm64("3.2.2"));
+        assert!(lwjgl_lacks_arm64("3.3.0"));
+        assert!(!lwjgl_lacks_arm64("3.3.1"));
+        assert!(!lwjgl_lacks_arm64("2.9.4-nightly-20150209"));
+    }
+
+    #[test]
+    fn native_classifier_prefers_arch_specific_variant() {
+        let os = launcher::current_os();
+        let lib = library(serde_json::json!({
+            "name": "org.lwjgl:lwjgl:3.2.2",
+            "natives": { (os): format!("natives-{}", os) },
+            "downloads": {
+                "classifiers": {
+                    (format!("natives-{}-arm64", os)): {
+                        "url": "https://example.com/arm64.jar",
+                        "sha1": "0000000000000000000000000000000000000000"
+                    }
+                }
+            }
+        }));
+        assert_eq!(
+            native_classifier(&lib, "aarch64"),
+            Some(format!("natives-{}-arm64", os))
+        );
+        assert_eq!(
+            native_classifier(&lib, "x86_64"),
+            Some(format!("natives-{}", os))
+        );
+    }
+
+    #[test]
+    fn split_natives_keep_the_runtime_arch() {
+        let plain = library(serde_json::json!({ "name": "org.lwjgl:lwjgl:3.3.2:natives-linux" }));
+        let arm =
+            library(serde_json::json!({ "name": "org.lwjgl:lwjgl:3.3.2:natives-linux-arm64" }));
+        let names: HashSet<&str> = [plain.name.as_str(), arm.name.as_str()].into();
+
+        assert!(native_arch_applies(&plain, &names, "x86_64"));
+        assert!(!native_arch_applies(&arm, &names, "x86_64"));
+        assert!(!native_arch_applies(&plain, &names, "aarch64"));
+        assert!(native_arch_applies(&arm, &names, "aarch64"));
+    }
+}
@@ -0,0 +1,263 @@
+use downloader::DownloadRequest;
+use launcher::current_os;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+use version_manager::{Library, VersionDetails};
+
+/// What [`resolve`] makes of a version's libraries for one architecture.
+pub(crate) struct ResolvedLibraries {
+    pub requests: Vec<DownloadRequest>,
+    pub classpath: Vec<PathBuf>,
+    /// Natives jars to unpack, with their SHA1.
+    pub natives: Vec<(PathBuf, String)>,
+}
+
+/// Picks the libraries of `version` that apply on this OS and `arch`, and
+/// where each goes under `libraries_dir`.
+pub(crate) fn resolve(
+    version: &VersionDetails,
+    libraries_dir: &Path,
+    arch: &str,
+) -> ResolvedLibraries {
+    let names = library_names(version);
+    let mut resolved = ResolvedLibraries {
+        requests: vec![],
+        classpath: vec![],
+        natives: vec![],
+    };
+    for lib in &version.libraries {
+        if !lib.applies() || !native_arch_applies(lib, &names, arch) {
+            continue;
+        }
+        if let Some(files) = lwjgl_arm64_override(lib, arch) {
+            for (url, rel_path) in files {
+                let lib_path = libraries_dir.join(rel_path);
+                if !resolved.classpath.contains(&lib_path) {
+                    resolved.requests.push(DownloadRequest::new(url, &lib_path));
+                    resolved.classpath.push(lib_path);
+                }
+            }
+            continue;
+        }
+        let native_classifier = native_classifier(lib, arch);
+
+        // Standard library
+        if let Some(artifact) = &lib.downloads.artifact {
+            let rel_path = if let Some(p) = &artifact.path {
+                p.clone()
+            } else {
+                maven_to_path(&lib.name).to_string_lossy().to_string()
+            };
+
+            let lib_path = libraries_dir.join(&rel_path);
+            resolved.requests.push(
+                DownloadRequest::new(&artifact.url, &lib_path)
+                    .with_sha1(Some(artifact.sha1.clone())),
+            );
+            resolved.classpath.push(lib_path);
+        } else if lib.downloads.classifiers.is_none() {
+            let rel_path = maven_to_path(&lib.name);
+            let base_url = lib
+                .url
+                .as_deref()
+                .unwrap_or("https://libraries.minecraft.net/");
+            let lib_path = libraries_dir.join(&rel_path);
+            resolved.requests.push(
+                DownloadRequest::new(format!("{}{}", base_url, rel_path.display()), &lib_path)
+                    .with_sha1(lib.sha1.clone()),
+            );
+            resolved.classpath.push(lib_path);
+        }
+
+        // Natives
+        if let (Some(classifiers), Some(os_classifier)) =
+            (&lib.downloads.classifiers, native_classifier)
+            && let Some(file_info) = classifiers.get(&os_classifier)
+        {
+            let nat_path = libraries_dir.join(format!(
+                "{}-{}.jar",
+                lib.name.replace(':', "-"),
+                os_classifier
+            ));
+            resolved.requests.push(
+                DownloadRequest::new(&file_info.url, &nat_path)
+                    .with_sha1(Some(file_info.sha1.clone())),
+            );
+            resolved.natives.push((nat_path, file_info.sha1.clone()));
+        }
+    }
+    resolved
+}
+
+/// Maven coordinates of every library in `version`, whatever their rules.
+pub fn library_names(version: &VersionDetails) -> HashSet<&str> {
+    version
+        .libraries
+        .iter()
+        .map(|lib| lib.name.as_str())
+        .collect()
+}
+
+/// Whether the version runs on LWJGL 2, which has no ARM64 natives.
+pub fn uses_lwjgl2(names: &HashSet<&str>) -> bool {
+    names
+        .iter()
+        .any(|name| name.starts_with("org.lwjgl.lwjgl:"))
+}
+
+/// Classifier of `lib`'s natives for the running OS and `arch`, preferring
+/// an architecture-specific variant (`natives-macos-arm64`) when the library
+/// ships one.
+pub(crate) fn native_classifier(lib: &Library, arch: &str) -> Option<String> {
+    let base = lib
+        .natives
+        .get(current_os())?
+        .replace("${arch}", arch_bits(arch));
+    let specific = arch_suffix(arch).map(|suffix| {
+        [
+            format!("{}-{}", base, suffix),
+            format!(
+                "{}-{}",
+                base.replace("natives-osx", "natives-macos"),
+                suffix
+            ),
+        ]
+    });
+    specific
+        .into_iter()
+        .flatten()
+        .find(|classifier| lib.has_classifier(classifier))
+        .or(Some(base))
+}
+
+/// 1.19+ ships natives as separate libraries, one per OS/arch, all allowed by
+/// the same OS rule. Keep the variant for `arch`: an arch-suffixed one when it
+/// exists, otherwise the plain (x86_64) one.
+pub(crate) fn native_arch_applies(lib: &Library, names: &HashSet<&str>, arch: &str) -> bool {
+    let Some(classifier) = lib.classifier().filter(|c| c.starts_with("natives-")) else {
+        return true;
+    };
+    match classifier_arch(classifier) {
+        Some(classifier_arch) => Some(classifier_arch) == arch_suffix(arch),
+        None => arch_suffix(arch)
+            .is_none_or(|suffix| !names.contains(format!("{}-{}", lib.name, suffix).as_str())),
+    }
+}
+
+/// First LWJGL release with ARM64 natives.
+const LWJGL_ARM64_VERSION: &str = "3.3.1";
+
+/// Files replacing an LWJGL 3 library that predates ARM64 natives (Minecraft
+/// 1.13 to 1.18) for an ARM64 runtime, as `(url, path under libraries/)`: the
+/// same module from LWJGL 3.3.1 and its natives jar. Like the 1.19+ natives
+/// libraries, the natives jar goes on the classpath and LWJGL extracts it.
+fn lwjgl_arm64_override(lib: &Library, arch: &str) -> Option<Vec<(String, PathBuf)>> {
+    if arch != "aarch64" {
+        return None;
+    }
+    let ["org.lwjgl", artifact, version] = lib.name.split(':').collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    if !lwjgl_lacks_arm64(version) {
+        return None;
+    }
+
+    let dir = PathBuf::from("org/lwjgl")
+        .join(artifact)
+        .join(LWJGL_ARM64_VERSION);
+    let base_url = format!(
+        "https://repo1.maven.org/maven2/org/lwjgl/{}/{}",
+        artifact, LWJGL_ARM64_VERSION
+    );
+    let file = |suffix: &str| {
+        let name = format!("{}-{}{}.jar", artifact, LWJGL_ARM64_VERSION, suffix);
+        (format!("{}/{}", base_url, name), dir.join(name))
+    };
+
+    let mut files = vec![file("")];
+    if lib.natives.contains_key(current_os()) {
+        let os = match current_os() {
+            "osx" => "macos",
+            os => os,
+        };
+        files.push(file(&format!("-natives-{}-arm64", os)));
+    }
+    Some(files)
+}
+
+/// Whether an LWJGL 3 version is older than [`LWJGL_ARM64_VERSION`].
+pub(crate) fn lwjgl_lacks_arm64(version: &str) -> bool {
+    let mut parts = version
+        .split('.')
+        .map(|part| part.parse::<u32>().unwrap_or(0));
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(3), minor, patch) => (minor.unwrap_or(0), patch.unwrap_or(0)) < (3, 1),
+        _ => false,
+    }
+}
+
+/// LWJGL 3.3 and later bundle a GLFW that runs on Wayland natively.
+pub(crate) fn lwjgl_supports_wayland(names: &HashSet<&str>) -> bool {
+    names
+        .iter()
+        .filter_map(|name| name.strip_prefix("org.lwjgl:lwjgl:"))
+        .any(|version| {
+            let mut parts = version
+                .split('.')
+                .map(|part| part.parse::<u32>().unwrap_or(0));
+            matches!((parts.next(), parts.next()), (Some(3), Some(minor)) if minor >= 3)
+        })
+}
+
+/// Architecture suffix used in natives classifiers for `arch`; `None` on
+/// x86_64, whose natives carry no suffix.
+fn arch_suffix(arch: &str) -> Option<&'static str> {
+    match arch {
+        "aarch64" => Some("arm64"),
+        "x86" => Some("x86"),
+        _ => None,
+    }
+}
+
+fn classifier_arch(classifier: &str) -> Option<&'static str> {
+    ["arm64", "x86"]
+        .into_iter()
+        .find(|arch| classifier.ends_with(&format!("-{}", arch)))
+}
+
+/// Substitution for `${arch}` in native classifiers.
+fn arch_bits(arch: &str) -> &'static str {
+    if arch == "x86" { "32" } else { "64" }
+}
+
+/// CPU architecture of the machine. An x86_64 build of the launcher running
+/// under Rosetta 2 still reports `aarch64`.
+pub fn host_arch() -> &'static str {
+    static HOST: OnceLock<&'static str> = OnceLock::new();
+    HOST.get_or_init(|| {
+        let translated = cfg!(target_os = "macos")
+            && std::env::consts::ARCH == "x86_64"
+            && Command::new("sysctl")
+                .args(["-n", "sysctl.proc_translated"])
+                .output()
+                .is_ok_and(|output| output.stdout.trim_ascii() == b"1");
+        if translated {
+            "aarch64"
+        } else {
+            std::env::consts::ARCH
+        }
+    })
+}
+
+pub fn maven_to_path(maven_id: &str) -> PathBuf {
+    let parts: Vec<&str> = maven_id.split(':').collect();
+    let domain = parts[0].replace('.', "/");
+    let name = parts[1];
+    let version = parts[2];
+    PathBuf::from(format!(
+        "{}/{}/{}/{}-{}.jar",
+        domain, name, version, name, version
+    ))
+}
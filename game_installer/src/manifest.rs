@@ -0,0 +1,93 @@
+use crate::explain;
+use downloader::{CancellationToken, DownloadRequest, Downloader};
+use std::path::Path;
+use tokio::fs;
+use version_manager::VersionDetails;
+
+/// Reads `version_id` and its `inheritsFrom` chain from `versions_dir`,
+/// downloading any vanilla JSON of the chain that is missing or corrupt, and
+/// returns the merged version. Locally installed third-party JSONs (loader
+/// profiles from other launchers, custom builds) work the same way.
+pub async fn load_version(
+    downloader: &Downloader,
+    version_id: &str,
+    versions_dir: &Path,
+) -> Result<VersionDetails, String> {
+    let mut id = version_id.to_string();
+    for _ in 0..version_manager::inheritance::MAX_INHERITANCE_DEPTH {
+        let json_path = versions_dir.join(&id).join(format!("{}.json", id));
+        println!("Checking version manifest at {:?}", json_path);
+
+        let local = fs::read_to_string(&json_path)
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+        let version = match local {
+            Some(version) => version,
+            None => {
+                if json_path.exists() {
+                    println!("Local manifest corrupted. Re-downloading...");
+                }
+                fetch_manifest(downloader, &id, versions_dir, &json_path).await?
+            }
+        };
+
+        match version_manager::inheritance::parent_of(&version) {
+            Some(parent) => id = parent,
+            None => break,
+        }
+    }
+
+    let versions_dir = versions_dir.to_path_buf();
+    let version_id = version_id.to_string();
+    let merged = tokio::task::spawn_blocking(move || {
+        version_manager::inheritance::resolve(&versions_dir, &version_id)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    serde_json::from_value(merged).map_err(|e| format!("Invalid version JSON: {}", e))
+}
+
+async fn fetch_manifest(
+    downloader: &Downloader,
+    version_id: &str,
+    versions_dir: &Path,
+    json_path: &Path,
+) -> Result<serde_json::Value, String> {
+    let manifest = version_manager::vanilla::fetch_manifest().await?;
+    let version_entry = manifest
+        .versions
+        .into_iter()
+        .find(|v| v.id == version_id)
+        .ok_or(format!("Version {} not found", version_id))?;
+
+    println!("Found {} URL: {}", version_id, version_entry.url);
+
+    // Version JSONs are immutable, so one shared copy (checked against the
+    // manifest's SHA1) serves every instance and survives piston-meta outages.
+    let cache_path = version_manager::vanilla::meta_cache_dir()
+        .map(|dir| dir.join("versions").join(format!("{}.json", version_id)))
+        .unwrap_or_else(|| json_path.to_path_buf());
+    let request =
+        DownloadRequest::new(&version_entry.url, &cache_path).with_sha1(version_entry.sha1);
+    if let Err(e) = downloader.ensure(&request, &CancellationToken::new()).await {
+        return Err(format!(
+            "Failed to fetch {} version JSON: {}",
+            version_id,
+            explain(e).await
+        ));
+    }
+
+    let content = fs::read_to_string(&cache_path)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    fs::create_dir_all(versions_dir.join(version_id))
+        .await
+        .map_err(|e| e.to_string())?;
+    fs::write(json_path, &content)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
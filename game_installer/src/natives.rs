@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+use version_manager::extract::{Extraction, extract_all};
+
+/// Lists the jars last extracted into a natives dir, one `<sha1> <file>` per
+/// line, so unchanged natives aren't unzipped again on every launch.
+const NATIVES_MARKER: &str = ".extracted";
+
+/// Unpacks `jars` into `natives_dir`, calling `on_item(file, done, total)`
+/// as entries are written. Nothing is marked extracted on failure, so the
+/// next launch tries again.
+pub(crate) fn extract_natives(
+    jars: &[(PathBuf, String)],
+    natives_dir: &Path,
+    on_item: impl Fn(&str, usize, usize) + Sync,
+) -> Result<(), String> {
+    let marker_path = natives_dir.join(NATIVES_MARKER);
+    let marker: String = jars
+        .iter()
+        .map(|(path, sha1)| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            format!("{} {}\n", sha1, name)
+        })
+        .collect();
+    if std::fs::read_to_string(&marker_path).is_ok_and(|existing| existing == marker) {
+        return Ok(());
+    }
+
+    let mut jobs = Vec::new();
+    for (nat_path, _) in jars {
+        let read_error = |e: zip::result::ZipError| {
+            format!("Failed to read natives {}: {}", nat_path.display(), e)
+        };
+        let file = std::fs::File::open(nat_path)
+            .map_err(|e| format!("Failed to open natives {}: {}", nat_path.display(), e))?;
+        let mut archive = zip::ZipArchive::new(file).map_err(read_error)?;
+        for index in 0..archive.len() {
+            let entry = archive.by_index(index).map_err(read_error)?;
+            if entry.is_dir() || entry.name().contains("META-INF") {
+                continue;
+            }
+            // A name like `../../x` would otherwise be written outside the
+            // natives folder
+            let relative = entry.enclosed_name().ok_or_else(|| {
+                format!(
+                    "Natives {} contain an unsafe path: {}",
+                    nat_path.display(),
+                    entry.name()
+                )
+            })?;
+            jobs.push(Extraction {
+                archive: nat_path.clone(),
+                entry: entry.name().to_string(),
+                dest: natives_dir.join(relative),
+            });
+        }
+    }
+    extract_all(&jobs, on_item).map_err(|e| format!("Failed to extract natives: {}", e))?;
+
+    if let Err(e) = std::fs::write(&marker_path, marker) {
+        println!("Failed to write natives marker: {}", e);
+    }
+    Ok(())
+}
@@ -0,0 +1,103 @@
+//! What `java -version` reported for each binary, keyed by path and
+//! modification time, so a scan only runs the JVMs that changed since the
+//! last one.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::detection::{InstallSource, JavaInstallation, installation_id};
+
+const CACHE_FILE: &str = "java-inspections.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedInspection {
+    modified: SystemTime,
+    version: Option<String>,
+    vendor: Option<String>,
+    is_64_bit: Option<bool>,
+    arch: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct InspectionCache {
+    entries: HashMap<PathBuf, CachedInspection>,
+}
+
+/// Modification time of the binary at `path`, `None` when it is gone.
+pub(crate) fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn cache_path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("com", "fastmc", "fastmc")
+        .map(|d| d.cache_dir().join(CACHE_FILE))
+}
+
+impl InspectionCache {
+    /// The cache on disk; empty when there is none or it can't be read.
+    pub(crate) async fn load() -> Self {
+        let Some(path) = cache_path() else {
+            return Self::default();
+        };
+        let entries = match tokio::fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => HashMap::new(),
+        };
+        Self { entries }
+    }
+
+    /// The cached result for `path`, if the binary hasn't changed since.
+    pub(crate) fn get(
+        &self,
+        path: &Path,
+        modified: SystemTime,
+        source: InstallSource,
+    ) -> Option<JavaInstallation> {
+        let entry = self.entries.get(path)?;
+        (entry.modified == modified).then(|| JavaInstallation {
+            id: installation_id(path),
+            path: path.to_path_buf(),
+            version: entry.version.clone(),
+            vendor: entry.vendor.clone(),
+            source,
+            is_64_bit: entry.is_64_bit,
+            arch: entry.arch.clone(),
+        })
+    }
+
+    pub(crate) fn insert(&mut self, installation: &JavaInstallation, modified: SystemTime) {
+        self.entries.insert(
+            installation.path.clone(),
+            CachedInspection {
+                modified,
+                version: installation.version.clone(),
+                vendor: installation.vendor.clone(),
+                is_64_bit: installation.is_64_bit,
+                arch: installation.arch.clone(),
+            },
+        );
+    }
+
+    /// Drops entries whose binary changed or disappeared, then writes the
+    /// cache. Failing to write only costs the next scan some time.
+    pub(crate) async fn save(mut self) {
+        self.entries
+            .retain(|path, entry| modified(path) == Some(entry.modified));
+        let Some(path) = cache_path() else {
+            return;
+        };
+        let Ok(json) = serde_json::to_vec(&self.entries) else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            let _ = tokio::fs::create_dir_all(parent).await;
+        }
+        if let Err(e) = tokio::fs::write(&path, json).await {
+            println!("Failed to save the Java inspection cache: {}", e);
+        }
+    }
+}
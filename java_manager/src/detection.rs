@@ -2,11 +2,19 @@ use std::collections::HashSet;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::time::Duration;
 
+use futures::future::join_all;
 use thiserror::Error;
+use tokio::process::Command;
 use uuid::Uuid;
 
+use crate::cache::{self, InspectionCache};
+
+/// How long one `java -version` may take before the binary is given up on.
+/// A broken or hanging install must not hold up the rest of the scan.
+const INSPECT_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InstallSource {
     JavaHome,
@@ -22,6 +30,20 @@ pub struct JavaInstallation {
     pub version: Option<String>,
     pub vendor: Option<String>,
     pub source: InstallSource,
+    /// `None` when neither `sun.arch.data.model` nor the VM line of
+    /// `java -version` gave it away.
+    pub is_64_bit: Option<bool>,
+    /// CPU architecture the runtime is built for, as `x86_64`, `aarch64`, ...
+    /// An x86_64 runtime on an ARM64 Mac runs under Rosetta 2.
+    pub arch: Option<String>,
+}
+
+impl JavaInstallation {
+    /// Whether this runtime is an x86 build that `host_arch` (an ARM64
+    /// machine) can only run under emulation, which is much slower.
+    pub fn is_emulated_on(&self, host_arch: &str) -> bool {
+        host_arch == "aarch64" && matches!(self.arch.as_deref(), Some("x86_64" | "x86"))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -45,6 +67,8 @@ pub enum JavaError {
     BinaryMissing(String),
     #[error("failed to inspect java at {path:?}: {error}")]
     Inspect { path: PathBuf, error: String },
+    #[error("java at {0:?} took too long to report its version")]
+    Timeout(PathBuf),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -55,10 +79,17 @@ pub struct DetectionSummary {
     pub errors: Vec<String>,
 }
 
-pub fn detect_installations(config: &JavaDetectionConfig) -> DetectionSummary {
+/// Inspects every candidate binary at once, each under [`INSPECT_TIMEOUT`].
+/// Binaries unchanged since an earlier scan are answered from the inspection
+/// cache instead of being run again. Installations are reported in candidate
+/// order, so a user-provided path still comes first.
+pub async fn detect_installations(config: &JavaDetectionConfig) -> DetectionSummary {
     let mut summary = DetectionSummary::default();
     let candidates = candidate_binaries(config);
     let mut seen = HashSet::new();
+    let mut cache = InspectionCache::load().await;
+    let cached = &cache;
+    let mut inspections = Vec::new();
 
     for (candidate, source) in candidates {
         let normalized = normalize_java_path(&candidate);
@@ -76,16 +107,63 @@ pub fn detect_installations(config: &JavaDetectionConfig) -> DetectionSummary {
             continue;
         }
 
-        match inspect_binary(&normalized, source) {
-            Ok(installation) => summary.installations.push(installation),
+        inspections.push(async move {
+            let modified = cache::modified(&normalized);
+            let hit = modified.and_then(|m| cached.get(&normalized, m, source));
+            let result = match hit {
+                Some(installation) => Ok(installation),
+                None => inspect_binary(&normalized, source).await,
+            };
+            (result, modified)
+        });
+    }
+
+    for (result, modified) in join_all(inspections).await {
+        match result {
+            Ok(installation) => {
+                if let Some(modified) = modified {
+                    cache.insert(&installation, modified);
+                }
+                summary.installations.push(installation);
+            }
             Err(err) => summary.errors.push(err.to_string()),
         }
     }
+    cache.save().await;
 
     summary
 }
 
 impl DetectionSummary {
+    /// The detected installation at `path`, e.g. the one a selector picked.
+    pub fn installation(&self, path: &Path) -> Option<&JavaInstallation> {
+        self.installations.iter().find(|i| i.path == path)
+    }
+
+    /// The installations built for `arch`, plus any the user picked
+    /// explicitly; `None` when no detected runtime has that architecture.
+    pub fn for_arch(&self, arch: &str) -> Option<DetectionSummary> {
+        if !self
+            .installations
+            .iter()
+            .any(|i| i.arch.as_deref() == Some(arch))
+        {
+            return None;
+        }
+        Some(DetectionSummary {
+            installations: self
+                .installations
+                .iter()
+                .filter(|i| {
+                    i.arch.as_deref() == Some(arch)
+                        || matches!(i.source, InstallSource::UserProvided)
+                })
+                .cloned()
+                .collect(),
+            errors: self.errors.clone(),
+        })
+    }
+
     pub fn select_for_version(&self, target_version: &str) -> Result<PathBuf, String> {
         // 1. Precise Match Logic
         // Legacy (1.0 - 1.16.5) -> Java 8
@@ -225,6 +303,69 @@ impl DetectionSummary {
             .map(|i| i.path.clone())
             .unwrap_or_else(|| PathBuf::from("java")))
     }
+
+    /// Picks a runtime for the `javaVersion.majorVersion` of a version JSON,
+    /// falling back to [`select_for_version`](Self::select_for_version) when
+    /// the JSON doesn't declare one. Java 8 games need exactly Java 8
+    /// (LaunchWrapper and old Forge break on newer runtimes); later ones run
+    /// on the required major or anything newer, the oldest match winning.
+    pub fn select_for_requirement(
+        &self,
+        required_major: Option<u32>,
+        target_version: &str,
+    ) -> Result<PathBuf, String> {
+        let Some(required) = required_major else {
+            return self.select_for_version(target_version);
+        };
+        let installed_major = |i: &JavaInstallation| i.version.as_deref().and_then(major_version);
+        let compatible = |major: u32| {
+            if required <= 8 {
+                major == 8
+            } else {
+                major >= required
+            }
+        };
+
+        if let Some(user_install) = self
+            .installations
+            .iter()
+            .find(|i| matches!(i.source, InstallSource::UserProvided))
+        {
+            return match installed_major(user_install) {
+                Some(major) if !compatible(major) => Err(format!(
+                    "Selected Java version ({}) is incompatible with Minecraft {}. Required: Java {}",
+                    user_install.version.as_deref().unwrap_or_default(),
+                    target_version,
+                    required
+                )),
+                // Unknown version: trust the user, as select_for_version does.
+                _ => Ok(user_install.path.clone()),
+            };
+        }
+
+        self.installations
+            .iter()
+            .filter_map(|i| Some((installed_major(i)?, i)))
+            .filter(|(major, _)| compatible(*major))
+            .min_by_key(|(major, _)| *major)
+            .map(|(_, i)| i.path.clone())
+            .ok_or_else(|| {
+                format!(
+                    "Java {} is required for Minecraft {}. Please install it or configure a Java path in settings.",
+                    required, target_version
+                )
+            })
+    }
+}
+
+/// Major version of a `java -version` string: `"1.8.0_392"` -> 8,
+/// `"17.0.2"` -> 17.
+pub fn major_version(version: &str) -> Option<u32> {
+    let mut parts = version.split(|c: char| !c.is_ascii_digit());
+    match parts.next()?.parse().ok()? {
+        1 => parts.next()?.parse().ok(),
+        major => Some(major),
+    }
 }
 
 fn candidate_binaries(config: &JavaDetectionConfig) -> Vec<(PathBuf, InstallSource)> {
@@ -273,44 +414,129 @@ fn normalize_java_path(path: &Path) -> PathBuf {
     fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
 }
 
-fn inspect_binary(path: &Path, source: InstallSource) -> Result<JavaInstallation, JavaError> {
+/// Stable id of the installation at `path`.
+pub(crate) fn installation_id(path: &Path) -> Uuid {
+    Uuid::new_v5(&Uuid::NAMESPACE_OID, path.to_string_lossy().as_bytes())
+}
+
+async fn inspect_binary(path: &Path, source: InstallSource) -> Result<JavaInstallation, JavaError> {
+    let output = run_inspection(path).await?;
+    Ok(installation_from_output(path, source, &output))
+}
+
+/// Runs the binary at `path` the way detection does, but never from the
+/// cache, and fails when the JVM exits with an error or reports no version.
+/// Used to check that an installation actually starts.
+pub async fn test_java(path: &Path, source: InstallSource) -> Result<JavaInstallation, JavaError> {
+    let output = run_inspection(path).await?;
+    let fail = |error: String| JavaError::Inspect {
+        path: path.to_path_buf(),
+        error,
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or_default();
+        return Err(fail(format!(
+            "exited with {}: {}",
+            output.status,
+            reason.trim()
+        )));
+    }
+    let installation = installation_from_output(path, source, &output);
+    if installation.version.is_none() {
+        return Err(fail("it didn't report a Java version".to_string()));
+    }
+    Ok(installation)
+}
+
+async fn run_inspection(path: &Path) -> Result<std::process::Output, JavaError> {
     if !path.exists() {
         return Err(JavaError::BinaryMissing(path.display().to_string()));
     }
 
+    // The property dump names the architecture; it goes to stderr before the
+    // usual version lines
     let output = Command::new(path)
+        .arg("-XshowSettings:properties")
         .arg("-version")
-        .output()
+        .kill_on_drop(true)
+        .output();
+    tokio::time::timeout(INSPECT_TIMEOUT, output)
+        .await
+        .map_err(|_| JavaError::Timeout(path.to_path_buf()))?
         .map_err(|error| JavaError::Inspect {
             path: path.to_path_buf(),
             error: error.to_string(),
-        })?;
+        })
+}
 
+fn installation_from_output(
+    path: &Path,
+    source: InstallSource,
+    output: &std::process::Output,
+) -> JavaInstallation {
     let metadata = parse_java_metadata(&output.stderr, &output.stdout);
-    let id = Uuid::new_v5(&Uuid::NAMESPACE_OID, path.to_string_lossy().as_bytes());
-
-    Ok(JavaInstallation {
-        id,
+    JavaInstallation {
+        id: installation_id(path),
         path: path.to_path_buf(),
         version: metadata.version,
         vendor: metadata.vendor,
         source,
-    })
+        is_64_bit: metadata.is_64_bit,
+        arch: metadata.arch,
+    }
 }
 
 struct JavaMetadata {
     version: Option<String>,
     vendor: Option<String>,
+    is_64_bit: Option<bool>,
+    arch: Option<String>,
+}
+
+/// Lines of `java -version` itself, without the indented `key = value`
+/// lines of `-XshowSettings:properties`.
+fn version_lines<'a>(stderr: &'a [u8], stdout: &'a [u8]) -> impl Iterator<Item = &'a [u8]> {
+    stderr
+        .split(|b| *b == b'\n')
+        .chain(stdout.split(|b| *b == b'\n'))
+        .filter(|line| !line.first().is_some_and(u8::is_ascii_whitespace))
+}
+
+/// Value of `key` in the `-XshowSettings:properties` dump.
+fn property(stderr: &[u8], key: &str) -> Option<String> {
+    let stderr = String::from_utf8_lossy(stderr);
+    stderr.lines().find_map(|line| {
+        let (name, value) = line.split_once('=')?;
+        (name.trim() == key).then(|| value.trim().to_string())
+    })
+}
+
+/// `os.arch` from the property dump, with the aliases Java uses folded into
+/// the names Rust and Mojang's manifests use.
+fn parse_arch(stderr: &[u8]) -> Option<String> {
+    Some(normalize_arch(&property(stderr, "os.arch")?))
+}
+
+fn normalize_arch(arch: &str) -> String {
+    match arch {
+        "amd64" | "x86_64" => "x86_64",
+        "aarch64" | "arm64" => "aarch64",
+        "i386" | "i486" | "i586" | "i686" | "x86" => "x86",
+        other => other,
+    }
+    .to_string()
 }
 
 fn parse_java_metadata(stderr: &[u8], stdout: &[u8]) -> JavaMetadata {
     let mut version = None;
     let mut vendor = None;
 
-    for line in stderr
-        .split(|b| *b == b'\n')
-        .chain(stdout.split(|b| *b == b'\n'))
-    {
+    for line in version_lines(stderr, stdout) {
         let line = String::from_utf8_lossy(line);
         let lower = line.to_lowercase();
 
@@ -340,10 +566,7 @@ fn parse_java_metadata(stderr: &[u8], stdout: &[u8]) -> JavaMetadata {
 
     if version.is_none() {
         // Fallback: grab the first token that looks like a version (handles some older Java 8 outputs).
-        for line in stderr
-            .split(|b| *b == b'\n')
-            .chain(stdout.split(|b| *b == b'\n'))
-        {
+        for line in version_lines(stderr, stdout) {
             let line = String::from_utf8_lossy(line);
             for token in line.split_whitespace() {
                 if let Some(v) = strip_version_like(token) {
@@ -357,7 +580,30 @@ fn parse_java_metadata(stderr: &[u8], stdout: &[u8]) -> JavaMetadata {
         }
     }
 
-    JavaMetadata { version, vendor }
+    JavaMetadata {
+        version,
+        vendor,
+        is_64_bit: parse_is_64_bit(stderr, stdout),
+        arch: parse_arch(stderr),
+    }
+}
+
+/// Bitness from `sun.arch.data.model` when the JVM reports it, otherwise
+/// from the VM line of `java -version`: 64-bit builds report a "64-Bit Server
+/// VM", 32-bit ones a plain "Client VM" or "Server VM".
+fn parse_is_64_bit(stderr: &[u8], stdout: &[u8]) -> Option<bool> {
+    match property(stderr, "sun.arch.data.model").as_deref() {
+        Some("64") => return Some(true),
+        Some("32") => return Some(false),
+        _ => {}
+    }
+    let output = version_lines(stderr, stdout)
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+    let vm_line = output.lines().find(|line| line.contains(" vm"))?;
+    Some(vm_line.contains("64-bit"))
 }
 
 fn strip_version_like(token: &str) -> Option<String> {
@@ -458,5 +704,85 @@ fn platform_candidates() -> Vec<(PathBuf, InstallSource)> {
         ));
     }
 
+    #[cfg(unix)]
+    paths.extend(
+        developer_jdks()
+            .into_iter()
+            .map(|path| (path, InstallSource::SystemLocation)),
+    );
+
     paths
 }
+
+/// JDKs installed through SDKMAN!, asdf, jabba or Homebrew, which keep them
+/// out of the system locations above.
+#[cfg(unix)]
+fn developer_jdks() -> Vec<PathBuf> {
+    let home = directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    let in_home = |var: &str, default: &str| {
+        env::var_os(var)
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(default)))
+    };
+
+    // Each of these holds one folder per JDK version
+    let version_dirs = [
+        in_home("SDKMAN_DIR", ".sdkman").map(|dir| dir.join("candidates").join("java")),
+        in_home("ASDF_DATA_DIR", ".asdf").map(|dir| dir.join("installs").join("java")),
+        in_home("JABBA_HOME", ".jabba").map(|dir| dir.join("jdk")),
+    ];
+    let mut jdks: Vec<PathBuf> = version_dirs
+        .into_iter()
+        .flatten()
+        .flat_map(subdirectories)
+        .collect();
+
+    // Cellar/openjdk@17/17.0.9/...: one folder per formula, then per version
+    let cellars = [
+        env::var_os("HOMEBREW_CELLAR").map(PathBuf::from),
+        Some(PathBuf::from("/opt/homebrew/Cellar")),
+        Some(PathBuf::from("/usr/local/Cellar")),
+        Some(PathBuf::from("/home/linuxbrew/.linuxbrew/Cellar")),
+        home.as_ref()
+            .map(|home| home.join(".linuxbrew").join("Cellar")),
+    ];
+    for cellar in cellars.into_iter().flatten() {
+        for formula in subdirectories(cellar) {
+            let is_openjdk = formula
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("openjdk"));
+            if is_openjdk {
+                jdks.extend(subdirectories(formula));
+            }
+        }
+    }
+
+    jdks.into_iter().map(|jdk| jdk_binary(&jdk)).collect()
+}
+
+#[cfg(unix)]
+fn subdirectories(dir: PathBuf) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// `bin/java` of a JDK folder. On macOS the JDK may sit in a bundle under
+/// `Contents/Home`, which Homebrew nests in `libexec/openjdk.jdk`.
+#[cfg(unix)]
+fn jdk_binary(jdk: &Path) -> PathBuf {
+    let candidates = [
+        jdk.join("Contents").join("Home"),
+        jdk.join("libexec/openjdk.jdk/Contents/Home"),
+        jdk.to_path_buf(),
+    ];
+    candidates
+        .iter()
+        .map(|home| home.join("bin").join("java"))
+        .find(|java| java.exists())
+        .unwrap_or_else(|| jdk.join("bin").join("java"))
+}
@@ -1,8 +1,106 @@
+mod cache;
 pub mod detection;
+pub mod presets;
+pub mod runtime;
 pub mod settings;
 
 pub use detection::{
     DetectionSummary, InstallSource, JavaDetectionConfig, JavaError, JavaInstallation,
-    detect_installations,
+    detect_installations, major_version, test_java,
 };
-pub use settings::JavaLaunchSettings;
+pub use presets::{ALL_PRESETS, JvmPreset};
+pub use runtime::{
+    InstalledRuntime, MojangRuntime, RuntimeError, ensure_runtime, installed_runtimes,
+    remove_runtime, update_runtime,
+};
+pub use settings::{JavaLaunchSettings, MemoryCheck, Workload, recommend_memory, total_memory_mb};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use runtime::{RuntimeFile, RuntimeManifest, is_contained, platform_arch};
+
+    #[test]
+    fn runtime_platforms_map_back_to_their_arch() {
+        for arch in ["x86_64", "aarch64", "x86"] {
+            if let Some(platform) = runtime::platform(arch) {
+                assert_eq!(platform_arch(platform), arch);
+            }
+        }
+        if cfg!(target_os = "linux") {
+            assert_eq!(runtime::platform("x86_64"), Some("linux"));
+            assert_eq!(runtime::platform("aarch64"), None);
+        }
+    }
+
+    #[test]
+    fn platform_arch_defaults_to_x86_64() {
+        assert_eq!(platform_arch("windows-x86"), "x86");
+        assert_eq!(platform_arch("mac-os-arm64"), "aarch64");
+        assert_eq!(platform_arch("gamecore"), "x86_64");
+    }
+
+    #[test]
+    fn runtime_manifest_parses_every_entry_type() {
+        let manifest: RuntimeManifest = serde_json::from_value(serde_json::json!({
+            "files": {
+                "bin": { "type": "directory" },
+                "bin/java": {
+                    "type": "file",
+                    "executable": true,
+                    "downloads": {
+                        "raw": {
+                            "sha1": "0000000000000000000000000000000000000000",
+                            "url": "https://example.com/java"
+                        }
+                    }
+                },
+                "lib/jli.so": { "type": "link", "target": "../bin/jli.so" }
+            }
+        }))
+        .unwrap();
+
+        assert!(matches!(manifest.files["bin"], RuntimeFile::Directory));
+        match &manifest.files["bin/java"] {
+            RuntimeFile::File {
+                executable,
+                downloads,
+            } => {
+                assert!(executable);
+                assert_eq!(downloads.raw.url, "https://example.com/java");
+            }
+            other => panic!("expected a file, got {:?}", other),
+        }
+        assert!(
+            matches!(&manifest.files["lib/jli.so"], RuntimeFile::Link { target } if target == "../bin/jli.so")
+        );
+    }
+
+    #[test]
+    fn manifest_paths_must_stay_inside_the_runtime() {
+        assert!(is_contained("bin/java"));
+        assert!(!is_contained(""));
+        assert!(!is_contained("/etc/passwd"));
+        assert!(!is_contained("../outside"));
+        assert!(!is_contained("bin/../../outside"));
+    }
+
+    #[test]
+    fn memory_recommendation_scales_with_mods_and_fits_the_machine() {
+        let modded = |mod_count| Workload {
+            modded: true,
+            mod_count,
+        };
+        assert_eq!(
+            recommend_memory(Some(16384), Workload::default()),
+            (1024, 2048)
+        );
+        assert_eq!(recommend_memory(None, modded(50)), (2048, 4096));
+        assert_eq!(recommend_memory(None, modded(51)), (2048, 5120));
+        assert_eq!(recommend_memory(None, modded(1000)), (2048, 12288));
+        // Twice the system reserve is left to the rest of the machine
+        assert_eq!(recommend_memory(Some(8192), modded(120)), (2048, 6144));
+        assert_eq!(recommend_memory(Some(7000), modded(120)), (2048, 4608));
+        assert_eq!(recommend_memory(Some(2048), modded(0)), (512, 1024));
+    }
+}
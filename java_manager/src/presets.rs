@@ -0,0 +1,76 @@
+use std::fmt;
+
+/// Well-known garbage collector flag sets users can apply instead of typing
+/// JVM arguments by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JvmPreset {
+    /// Aikar's G1 tuning, originally for Paper servers but popular for heavy
+    /// modded clients too.
+    Aikar,
+    /// Plain G1 with a short pause target.
+    G1,
+    /// Generational ZGC. Requires Java 21+.
+    Zgc,
+}
+
+pub const ALL_PRESETS: [JvmPreset; 3] = [JvmPreset::Aikar, JvmPreset::G1, JvmPreset::Zgc];
+
+const AIKAR_ARGS: &[&str] = &[
+    "-XX:+UseG1GC",
+    "-XX:+ParallelRefProcEnabled",
+    "-XX:MaxGCPauseMillis=200",
+    "-XX:+UnlockExperimentalVMOptions",
+    "-XX:+DisableExplicitGC",
+    "-XX:+AlwaysPreTouch",
+    "-XX:G1NewSizePercent=30",
+    "-XX:G1MaxNewSizePercent=40",
+    "-XX:G1HeapRegionSize=8M",
+    "-XX:G1ReservePercent=20",
+    "-XX:G1HeapWastePercent=5",
+    "-XX:G1MixedGCCountTarget=4",
+    "-XX:InitiatingHeapOccupancyPercent=15",
+    "-XX:G1MixedGCLiveThresholdPercent=90",
+    "-XX:G1RSetUpdatingPauseTimePercent=5",
+    "-XX:SurvivorRatio=32",
+    "-XX:+PerfDisableSharedMem",
+    "-XX:MaxTenuringThreshold=1",
+    "-Dusing.aikars.flags=https://mcflags.emc.gs",
+    "-Daikars.new.flags=true",
+];
+
+const G1_ARGS: &[&str] = &[
+    "-XX:+UseG1GC",
+    "-XX:MaxGCPauseMillis=50",
+    "-XX:+ParallelRefProcEnabled",
+    "-XX:+DisableExplicitGC",
+];
+
+const ZGC_ARGS: &[&str] = &["-XX:+UseZGC", "-XX:+ZGenerational", "-XX:+AlwaysPreTouch"];
+
+impl JvmPreset {
+    pub fn args(&self) -> &'static [&'static str] {
+        match self {
+            JvmPreset::Aikar => AIKAR_ARGS,
+            JvmPreset::G1 => G1_ARGS,
+            JvmPreset::Zgc => ZGC_ARGS,
+        }
+    }
+
+    /// Whether `arg` belongs to any preset, i.e. would be replaced when
+    /// switching presets.
+    pub fn is_preset_arg(arg: &str) -> bool {
+        ALL_PRESETS
+            .iter()
+            .any(|preset| preset.args().contains(&arg))
+    }
+}
+
+impl fmt::Display for JvmPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JvmPreset::Aikar => write!(f, "Aikar's flags"),
+            JvmPreset::G1 => write!(f, "G1 (low pause)"),
+            JvmPreset::Zgc => write!(f, "ZGC (Java 21+)"),
+        }
+    }
+}
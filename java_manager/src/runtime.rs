@@ -0,0 +1,383 @@
+//! Mojang's own Java runtimes, as the official launcher installs them: a
+//! version JSON names a `javaVersion.component` (`jre-legacy`,
+//! `java-runtime-gamma`, ...), the runtime index maps it to a per-platform
+//! file manifest, and every file in it is downloaded and SHA1-checked.
+
+use downloader::{CancellationToken, DownloadError, DownloadProgress, DownloadRequest, Downloader};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+const RUNTIME_INDEX_URL: &str = "https://launchermeta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
+
+/// A cached runtime index younger than this is used without revalidating it.
+const INDEX_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Written into a runtime's folder once all of its files are in place; holds
+/// the SHA1 of the manifest they came from.
+const INSTALLED_MARKER: &str = ".fastmc-manifest";
+
+#[derive(Debug, Error)]
+pub enum RuntimeError {
+    #[error("Mojang doesn't ship {component} for {platform}")]
+    Unavailable { component: String, platform: String },
+    #[error("this platform has no Mojang Java runtimes")]
+    UnsupportedPlatform,
+    #[error("the Java runtime index is unavailable")]
+    IndexUnavailable,
+    #[error("invalid runtime manifest: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("runtime manifest points outside the runtime: {0}")]
+    UnsafePath(String),
+    #[error(transparent)]
+    Download(#[from] DownloadError),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// An installed Mojang runtime.
+#[derive(Debug, Clone)]
+pub struct MojangRuntime {
+    pub component: String,
+    /// As Mojang names it, e.g. `17.0.8`.
+    pub version: String,
+    pub java: PathBuf,
+    /// `x86_64`, `aarch64` or `x86`, the same names detection reports.
+    pub arch: &'static str,
+}
+
+/// A runtime found in [`runtimes_dir`], see [`installed_runtimes`].
+#[derive(Debug, Clone)]
+pub struct InstalledRuntime {
+    pub component: String,
+    /// Mojang's platform name, e.g. `mac-os-arm64`.
+    pub platform: String,
+    /// Empty when the install predates version tracking.
+    pub version: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexEntry {
+    manifest: FileRef,
+    version: IndexVersion,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexVersion {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct FileRef {
+    pub(crate) sha1: String,
+    pub(crate) url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RuntimeManifest {
+    pub(crate) files: HashMap<String, RuntimeFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub(crate) enum RuntimeFile {
+    Directory,
+    File {
+        #[serde(default)]
+        executable: bool,
+        downloads: FileDownloads,
+    },
+    Link {
+        target: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct FileDownloads {
+    pub(crate) raw: FileRef,
+}
+
+/// Where runtimes are installed, one folder per component and platform.
+pub fn runtimes_dir() -> Option<PathBuf> {
+    directories::ProjectDirs::from("com", "fastmc", "fastmc")
+        .map(|d| d.data_local_dir().join("runtimes"))
+}
+
+/// Mojang's name for the platform a runtime built for `arch` runs on, `None`
+/// where Mojang ships nothing.
+pub fn platform(arch: &str) -> Option<&'static str> {
+    match (std::env::consts::OS, arch) {
+        ("linux", "x86_64") => Some("linux"),
+        ("linux", "x86") => Some("linux-i386"),
+        ("macos", "x86_64") => Some("mac-os"),
+        ("macos", "aarch64") => Some("mac-os-arm64"),
+        ("windows", "x86_64") => Some("windows-x64"),
+        ("windows", "x86") => Some("windows-x86"),
+        ("windows", "aarch64") => Some("windows-arm64"),
+        _ => None,
+    }
+}
+
+pub(crate) fn platform_arch(platform: &str) -> &'static str {
+    match platform {
+        "linux-i386" | "windows-x86" => "x86",
+        "mac-os-arm64" | "windows-arm64" => "aarch64",
+        _ => "x86_64",
+    }
+}
+
+/// The java binary inside an installed runtime.
+fn java_binary(runtime_dir: &Path, platform: &str) -> PathBuf {
+    if platform.starts_with("mac-os") {
+        runtime_dir.join("jre.bundle/Contents/Home/bin/java")
+    } else if platform.starts_with("windows") {
+        runtime_dir.join("bin").join("java.exe")
+    } else {
+        runtime_dir.join("bin").join("java")
+    }
+}
+
+/// Installs `component` for `arch` unless it is already up to date, and
+/// returns it. Files are fetched through `downloader`, so they are verified
+/// against the manifest's SHA1s and retried like any other download;
+/// `on_progress` sees each one finish. When the index can't be reached, an
+/// earlier install is used as is.
+pub async fn ensure_runtime<F>(
+    downloader: &Downloader,
+    component: &str,
+    arch: &str,
+    cancel: &CancellationToken,
+    on_progress: F,
+) -> Result<MojangRuntime, RuntimeError>
+where
+    F: FnMut(&DownloadProgress) + Send,
+{
+    let platform = platform(arch).ok_or(RuntimeError::UnsupportedPlatform)?;
+    let root = runtimes_dir().ok_or(RuntimeError::UnsupportedPlatform)?;
+    let runtime_dir = root.join(component).join(platform);
+    let java = java_binary(&runtime_dir, platform);
+    let marker = runtime_dir.join(INSTALLED_MARKER);
+    let runtime = |version: String| MojangRuntime {
+        component: component.to_string(),
+        version,
+        java: java.clone(),
+        arch: platform_arch(platform),
+    };
+
+    let entry = match fetch_index_entry(downloader, &root, component, platform).await {
+        Ok(entry) => entry,
+        Err(e) if java.exists() && marker.exists() => {
+            println!("Using the installed {} runtime: {}", component, e);
+            let version = read_version(&runtime_dir).await.unwrap_or_default();
+            return Ok(runtime(version));
+        }
+        Err(e) => return Err(e),
+    };
+
+    let installed = tokio::fs::read_to_string(&marker).await.unwrap_or_default();
+    if installed.trim() == entry.manifest.sha1 && java.exists() {
+        return Ok(runtime(entry.version.name));
+    }
+
+    let manifest_path = root.join(component).join(format!("{}.json", platform));
+    let manifest_request = DownloadRequest::new(&entry.manifest.url, &manifest_path)
+        .with_sha1(Some(entry.manifest.sha1.clone()));
+    downloader.ensure(&manifest_request, cancel).await?;
+    let manifest: RuntimeManifest =
+        serde_json::from_slice(&tokio::fs::read(&manifest_path).await?)?;
+
+    let mut requests = Vec::new();
+    let mut executables = Vec::new();
+    let mut links = Vec::new();
+    for (name, file) in &manifest.files {
+        if !is_contained(name) {
+            return Err(RuntimeError::UnsafePath(name.clone()));
+        }
+        let path = runtime_dir.join(name);
+        match file {
+            RuntimeFile::Directory => tokio::fs::create_dir_all(&path).await?,
+            RuntimeFile::File {
+                executable,
+                downloads,
+            } => {
+                if *executable {
+                    executables.push(path.clone());
+                }
+                requests.push(
+                    DownloadRequest::new(&downloads.raw.url, path)
+                        .with_sha1(Some(downloads.raw.sha1.clone())),
+                );
+            }
+            RuntimeFile::Link { target } => {
+                // Targets are relative to the link and may climb out of its
+                // folder, but never out of the runtime
+                if Path::new(target).is_absolute() {
+                    return Err(RuntimeError::UnsafePath(target.clone()));
+                }
+                links.push((path, target.clone()));
+            }
+        }
+    }
+
+    downloader
+        .download_all(requests, cancel, on_progress)
+        .await?
+        .ensure_complete()?;
+    for path in &executables {
+        make_executable(path).await?;
+    }
+    for (path, target) in &links {
+        link(path, target).await?;
+    }
+
+    tokio::fs::write(runtime_dir.join(".version"), &entry.version.name).await?;
+    tokio::fs::write(&marker, &entry.manifest.sha1).await?;
+    Ok(runtime(entry.version.name))
+}
+
+/// Every completely installed runtime, sorted by component and platform.
+/// Walks each one to measure it, so call it off the UI thread.
+pub fn installed_runtimes() -> Vec<InstalledRuntime> {
+    let Some(root) = runtimes_dir() else {
+        return Vec::new();
+    };
+    let mut runtimes = Vec::new();
+    for component in std::fs::read_dir(&root).into_iter().flatten().flatten() {
+        if !component.path().is_dir() {
+            continue;
+        }
+        for platform in std::fs::read_dir(component.path())
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            let path = platform.path();
+            if !path.join(INSTALLED_MARKER).exists() {
+                continue;
+            }
+            let version = std::fs::read_to_string(path.join(".version")).unwrap_or_default();
+            runtimes.push(InstalledRuntime {
+                component: component.file_name().to_string_lossy().into_owned(),
+                platform: platform.file_name().to_string_lossy().into_owned(),
+                version: version.trim().to_string(),
+                size_bytes: dir_size(&path),
+                path,
+            });
+        }
+    }
+    runtimes.sort_by(|a, b| (&a.component, &a.platform).cmp(&(&b.component, &b.platform)));
+    runtimes
+}
+
+/// Deletes an installed runtime. The next launch that needs it installs it
+/// again.
+pub fn remove_runtime(runtime: &InstalledRuntime) -> std::io::Result<()> {
+    std::fs::remove_dir_all(&runtime.path)?;
+    let manifest = runtime.path.with_extension("json");
+    if manifest.exists() {
+        std::fs::remove_file(manifest)?;
+    }
+    Ok(())
+}
+
+/// Brings an installed runtime up to the version Mojang currently ships; a
+/// no-op when it already is.
+pub async fn update_runtime(
+    downloader: &Downloader,
+    runtime: &InstalledRuntime,
+    cancel: &CancellationToken,
+) -> Result<MojangRuntime, RuntimeError> {
+    let arch = platform_arch(&runtime.platform);
+    if platform(arch) != Some(runtime.platform.as_str()) {
+        return Err(RuntimeError::UnsupportedPlatform);
+    }
+    ensure_runtime(downloader, &runtime.component, arch, cancel, |_| {}).await
+}
+
+fn dir_size(path: &Path) -> u64 {
+    std::fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            Ok(t) if t.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Whether a manifest path stays inside the runtime folder: relative, and
+/// without `..` or a drive prefix.
+pub(crate) fn is_contained(name: &str) -> bool {
+    !name.is_empty()
+        && Path::new(name)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+async fn fetch_index_entry(
+    downloader: &Downloader,
+    root: &Path,
+    component: &str,
+    platform: &str,
+) -> Result<IndexEntry, RuntimeError> {
+    let path = root.join("all.json");
+    if !downloader::fetch_cached(downloader.client(), RUNTIME_INDEX_URL, &path, INDEX_MAX_AGE)
+        .await?
+    {
+        return Err(RuntimeError::IndexUnavailable);
+    }
+    let mut index: HashMap<String, HashMap<String, Vec<IndexEntry>>> =
+        serde_json::from_slice(&tokio::fs::read(&path).await?)?;
+    index
+        .remove(platform)
+        .and_then(|mut components| components.remove(component))
+        .and_then(|entries| entries.into_iter().next())
+        .ok_or_else(|| RuntimeError::Unavailable {
+            component: component.to_string(),
+            platform: platform.to_string(),
+        })
+}
+
+async fn read_version(runtime_dir: &Path) -> Option<String> {
+    let version = tokio::fs::read_to_string(runtime_dir.join(".version"))
+        .await
+        .ok()?;
+    Some(version.trim().to_string())
+}
+
+#[cfg(unix)]
+async fn make_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).await
+}
+
+#[cfg(not(unix))]
+async fn make_executable(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Recreates a link from the manifest. Links only exist in the macOS and
+/// Linux runtimes; on Windows there is nothing to do.
+#[cfg(unix)]
+async fn link(path: &Path, target: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    tokio::fs::symlink(target, path).await
+}
+
+#[cfg(not(unix))]
+async fn link(_path: &Path, _target: &str) -> std::io::Result<()> {
+    Ok(())
+}
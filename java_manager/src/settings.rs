@@ -3,11 +3,69 @@ use std::path::PathBuf;
 use config_manager::{JavaConfig, JavaInstallationRecord};
 
 use crate::detection::JavaDetectionConfig;
+use crate::presets::JvmPreset;
+
+/// Largest heap a 32-bit JVM can reliably reserve.
+pub const MAX_32_BIT_HEAP_MB: u32 = 1536;
+
+/// RAM left to the OS and the launcher below which a warning is shown.
+const SYSTEM_RESERVE_MB: u64 = 1024;
+
+/// Verdict on the requested heap, checked before every launch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryCheck {
+    Ok,
+    /// The game can start, but the system is likely to swap.
+    Warning(String),
+    /// The JVM would refuse to start or the system would run out of memory.
+    Denied(String),
+}
+
+/// Heap the recommendation never goes beyond; more mostly lengthens GC pauses.
+const MAX_RECOMMENDED_HEAP_MB: u32 = 12288;
+
+/// What an instance asks of the heap, see [`recommend_memory`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Workload {
+    /// Runs a mod loader rather than vanilla.
+    pub modded: bool,
+    pub mod_count: u32,
+}
+
+/// Suggested `(min, max)` heap in MB: 2 GB for vanilla, 4 GB for a loader
+/// with up to 50 mods and 1 GB more per further 50, up to 12 GB. The maximum
+/// always leaves twice [`SYSTEM_RESERVE_MB`] of `total_memory_mb` to the rest
+/// of the system, and the minimum is half of it, capped at 2 GB.
+pub fn recommend_memory(total_memory_mb: Option<u64>, workload: Workload) -> (u32, u32) {
+    let wanted = if workload.modded {
+        4096 + workload.mod_count.saturating_sub(1) / 50 * 1024
+    } else {
+        2048
+    };
+    let mut max = wanted.min(MAX_RECOMMENDED_HEAP_MB);
+    if let Some(total) = total_memory_mb {
+        let budget = total.saturating_sub(2 * SYSTEM_RESERVE_MB);
+        max = max.min(u32::try_from(budget).unwrap_or(u32::MAX));
+    }
+    // Multiples of 512 MB, and never below what the game needs to start
+    let max = (max / 512 * 512).max(1024);
+    let min = (max / 2).min(2048);
+    (min, max)
+}
+
+/// Physical RAM of this machine, if it can be read.
+pub fn total_memory_mb() -> Option<u64> {
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    let total = system.total_memory() / (1024 * 1024);
+    (total > 0).then_some(total)
+}
 
 #[derive(Debug, Clone)]
 pub struct JavaLaunchSettings {
     pub java_path: Option<PathBuf>,
     pub auto_discover: bool,
+    pub mojang_runtimes: bool,
     pub min_memory_mb: u32,
     pub max_memory_mb: u32,
     pub extra_jvm_args: Vec<String>,
@@ -15,22 +73,80 @@ pub struct JavaLaunchSettings {
 }
 
 impl JavaLaunchSettings {
-    pub fn memory_bounds(&self, total_memory_mb: Option<u64>) -> (u32, u32) {
-        let mut max = self.max_memory_mb.max(self.min_memory_mb);
-        if let Some(total) = total_memory_mb {
-            let available = total.min(u64::from(u32::MAX)) as u32;
-            max = max.min(available);
+    /// Checks the maximum heap against the machine's RAM and, when known,
+    /// the bitness of the Java that will run the game.
+    pub fn memory_check(
+        &self,
+        total_memory_mb: Option<u64>,
+        java_64_bit: Option<bool>,
+    ) -> MemoryCheck {
+        let max = self.max_memory_mb.max(self.min_memory_mb);
+        if java_64_bit == Some(false) && max > MAX_32_BIT_HEAP_MB {
+            return MemoryCheck::Denied(format!(
+                "32-bit Java can't use more than {} MB; lower the maximum memory ({} MB) or install a 64-bit Java",
+                MAX_32_BIT_HEAP_MB, max
+            ));
+        }
+        let Some(total) = total_memory_mb else {
+            return MemoryCheck::Ok;
+        };
+        if u64::from(max) > total {
+            MemoryCheck::Denied(format!(
+                "Maximum memory ({} MB) exceeds the system's physical RAM ({} MB)",
+                max, total
+            ))
+        } else if u64::from(max) + SYSTEM_RESERVE_MB > total {
+            MemoryCheck::Warning(format!(
+                "Maximum memory ({} MB) leaves less than {} MB of the system's {} MB for other programs",
+                max, SYSTEM_RESERVE_MB, total
+            ))
+        } else {
+            MemoryCheck::Ok
         }
+    }
 
+    /// `(min, max)` heap in MB, or the reason the launch must not go ahead.
+    pub fn memory_bounds(
+        &self,
+        total_memory_mb: Option<u64>,
+        java_64_bit: Option<bool>,
+    ) -> Result<(u32, u32), String> {
+        if let MemoryCheck::Denied(reason) = self.memory_check(total_memory_mb, java_64_bit) {
+            return Err(reason);
+        }
+        let max = self.max_memory_mb.max(self.min_memory_mb);
         let min = self.min_memory_mb.min(max);
-        (min, max)
+        Ok((min, max))
     }
 
-    pub fn jvm_args(&self, total_memory_mb: Option<u64>) -> Vec<String> {
-        let (min, max) = self.memory_bounds(total_memory_mb);
+    pub fn jvm_args(
+        &self,
+        total_memory_mb: Option<u64>,
+        java_64_bit: Option<bool>,
+    ) -> Result<Vec<String>, String> {
+        let (min, max) = self.memory_bounds(total_memory_mb, java_64_bit)?;
         let mut args = vec![format!("-Xms{}M", min), format!("-Xmx{}M", max)];
         args.extend(self.extra_jvm_args.clone());
-        args
+        Ok(args)
+    }
+
+    /// Replaces any preset flags in `extra_jvm_args` with `preset`'s, keeping
+    /// the user's other arguments.
+    pub fn apply_preset(&mut self, preset: JvmPreset) {
+        self.extra_jvm_args
+            .retain(|arg| !JvmPreset::is_preset_arg(arg));
+        self.extra_jvm_args
+            .extend(preset.args().iter().map(|arg| arg.to_string()));
+    }
+
+    /// The preset whose flags are all present in `extra_jvm_args`, if any.
+    pub fn active_preset(&self) -> Option<JvmPreset> {
+        crate::presets::ALL_PRESETS.into_iter().find(|preset| {
+            preset
+                .args()
+                .iter()
+                .all(|arg| self.extra_jvm_args.iter().any(|a| a == arg))
+        })
     }
 
     pub fn detection_config(&self) -> JavaDetectionConfig {
@@ -47,6 +163,7 @@ impl JavaLaunchSettings {
                 .as_ref()
                 .map(|p| p.to_string_lossy().into_owned()),
             auto_discover: self.auto_discover,
+            mojang_runtimes: self.mojang_runtimes,
             min_memory_mb: self.min_memory_mb,
             max_memory_mb: self.max_memory_mb,
             extra_jvm_args: self.extra_jvm_args.clone(),
@@ -60,6 +177,7 @@ impl From<&JavaConfig> for JavaLaunchSettings {
         JavaLaunchSettings {
             java_path: config.java_path.as_ref().map(PathBuf::from),
             auto_discover: config.auto_discover,
+            mojang_runtimes: config.mojang_runtimes,
             min_memory_mb: config.min_memory_mb,
             max_memory_mb: config.max_memory_mb,
             extra_jvm_args: config.extra_jvm_args.clone(),
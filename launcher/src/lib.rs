@@ -1,4 +1,13 @@
+mod arguments;
+
+pub use arguments::substitute;
+pub use version_manager::arguments::{
+    Argument, ArgumentValue, Features, OsRule, Rule, RuleAction, VersionArguments, current_os,
+    rules_allow,
+};
+
 use account_manager::MinecraftSession;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Command;
 
@@ -14,6 +23,15 @@ pub struct Resolution {
     pub height: u32,
 }
 
+/// Where the game goes straight after starting, skipping the title screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuickPlay {
+    /// Folder name of a world in `saves/`.
+    Singleplayer(String),
+    /// Server address, `host` or `host:port`.
+    Multiplayer(String),
+}
+
 #[derive(Debug, Clone)]
 pub enum LaunchAuth {
     Offline {
@@ -25,6 +43,12 @@ pub enum LaunchAuth {
         uuid: String,
         access_token: String,
     },
+    /// A third-party Yggdrasil server, reached through authlib-injector.
+    Yggdrasil {
+        username: String,
+        uuid: String,
+        access_token: String,
+    },
 }
 
 impl LaunchAuth {
@@ -32,6 +56,7 @@ impl LaunchAuth {
         match self {
             LaunchAuth::Offline { username, .. } => username,
             LaunchAuth::Microsoft { username, .. } => username,
+            LaunchAuth::Yggdrasil { username, .. } => username,
         }
     }
 
@@ -39,13 +64,15 @@ impl LaunchAuth {
         match self {
             LaunchAuth::Offline { uuid, .. } => uuid,
             LaunchAuth::Microsoft { uuid, .. } => uuid,
+            LaunchAuth::Yggdrasil { uuid, .. } => uuid,
         }
     }
 
     pub fn access_token(&self) -> &str {
         match self {
             LaunchAuth::Offline { .. } => "offline-token",
-            LaunchAuth::Microsoft { access_token, .. } => access_token,
+            LaunchAuth::Microsoft { access_token, .. }
+            | LaunchAuth::Yggdrasil { access_token, .. } => access_token,
         }
     }
 
@@ -53,6 +80,7 @@ impl LaunchAuth {
         match self {
             LaunchAuth::Offline { .. } => "offline",
             LaunchAuth::Microsoft { .. } => "msa",
+            LaunchAuth::Yggdrasil { .. } => "mojang",
         }
     }
 }
@@ -75,16 +103,165 @@ pub struct VanillaLaunchConfig {
     pub classpath: Vec<PathBuf>,
     pub main_class: String,
     pub version_name: String,
+    /// The version JSON's `type` (`release`, `snapshot`, `old_beta`, ...),
+    /// passed as `--versionType`. `release` when unknown.
+    pub version_type: Option<String>,
     pub asset_index: Option<String>,
     pub resolution: Option<Resolution>,
     pub memory: Option<MemorySettings>,
     pub extra_jvm_args: Vec<String>,
     pub extra_game_args: Vec<String>,
     pub natives_dir: Option<PathBuf>,
+    /// `${library_directory}`, used by the module path of modern Forge and
+    /// NeoForge profiles.
+    pub libraries_dir: Option<PathBuf>,
+    /// Block the game's outbound HTTP(S)/SOCKS traffic (singleplayer-only mode).
+    pub network_isolated: bool,
+    /// The version JSON's `arguments` block (1.13+). When present it replaces
+    /// the hardcoded legacy argument list. Next to `legacy_arguments`, as a
+    /// loader profile layered over an older version leaves it, its entries
+    /// are added to the legacy ones.
+    pub arguments: Option<VersionArguments>,
+    /// The version JSON's `minecraftArguments` template (before 1.13). Used
+    /// for the game arguments when there is no `arguments` block; without
+    /// either a built-in list is used.
+    pub legacy_arguments: Option<String>,
+    pub quick_play: Option<QuickPlay>,
 }
 
 impl VanillaLaunchConfig {
     pub fn build_command(&self, auth: &LaunchAuth) -> Command {
+        match (&self.arguments, &self.legacy_arguments) {
+            (Some(arguments), None) => self.build_modern_command(arguments, auth),
+            _ => self.build_legacy_command(auth),
+        }
+    }
+
+    /// Follows the version JSON's `arguments` block: rules are evaluated
+    /// against the current OS and launcher features, and `${variables}` are
+    /// filled in from this config.
+    fn build_modern_command(&self, arguments: &VersionArguments, auth: &LaunchAuth) -> Command {
+        let vars = self.argument_variables(auth);
+        let expand = |args: &[Argument]| self.expand(args, &vars);
+
+        let mut cmd = Command::new(&self.java_path);
+        cmd.current_dir(&self.game_dir);
+
+        if let Some(memory) = &self.memory {
+            cmd.arg(format!("-Xms{}M", memory.min_megabytes))
+                .arg(format!("-Xmx{}M", memory.max_megabytes));
+        }
+        if self.network_isolated {
+            cmd.args(NETWORK_ISOLATION_JVM_ARGS);
+        }
+        cmd.args(expand(&arguments.jvm));
+        cmd.args(&self.extra_jvm_args);
+
+        cmd.arg(&self.main_class);
+        cmd.args(expand(&arguments.game));
+        if !arguments.supports_quick_play() {
+            cmd.args(self.legacy_quick_play_args());
+        }
+        cmd.args(&self.extra_game_args);
+
+        cmd
+    }
+
+    /// Values of the `args` whose rules apply, with `${variables}` filled in.
+    fn expand(&self, args: &[Argument], vars: &HashMap<&'static str, String>) -> Vec<String> {
+        let features = Features {
+            has_custom_resolution: self.resolution.is_some(),
+            is_demo_user: false,
+            is_quick_play_singleplayer: matches!(self.quick_play, Some(QuickPlay::Singleplayer(_))),
+            is_quick_play_multiplayer: matches!(self.quick_play, Some(QuickPlay::Multiplayer(_))),
+        };
+        args.iter()
+            .flat_map(|arg| arg.values(&features))
+            .map(|value| substitute(value, vars))
+            .collect()
+    }
+
+    fn argument_variables(&self, auth: &LaunchAuth) -> HashMap<&'static str, String> {
+        let path = |p: &PathBuf| p.to_string_lossy().to_string();
+        let classpath = self
+            .classpath
+            .iter()
+            .map(|p| p.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(classpath_separator());
+        let (width, height) = self
+            .resolution
+            .as_ref()
+            .map(|r| (r.width.to_string(), r.height.to_string()))
+            .unwrap_or_default();
+        let (quick_play_world, quick_play_server) = match &self.quick_play {
+            Some(QuickPlay::Singleplayer(world)) => (world.clone(), String::new()),
+            Some(QuickPlay::Multiplayer(server)) => (String::new(), server.clone()),
+            None => Default::default(),
+        };
+
+        HashMap::from([
+            ("auth_player_name", auth.username().to_string()),
+            ("auth_uuid", auth.uuid().to_string()),
+            ("auth_access_token", auth.access_token().to_string()),
+            ("auth_xuid", String::new()),
+            ("clientid", String::new()),
+            ("user_type", auth.user_type().to_string()),
+            ("version_name", self.version_name.clone()),
+            ("version_type", self.version_type().to_string()),
+            ("game_directory", path(&self.game_dir)),
+            ("assets_root", path(&self.assets_dir)),
+            ("game_assets", path(&self.assets_dir)),
+            (
+                "assets_index_name",
+                self.asset_index.clone().unwrap_or_default(),
+            ),
+            (
+                "natives_directory",
+                self.natives_dir.as_ref().map(path).unwrap_or_default(),
+            ),
+            (
+                "library_directory",
+                self.libraries_dir.as_ref().map(path).unwrap_or_default(),
+            ),
+            ("classpath", classpath),
+            ("classpath_separator", classpath_separator().to_string()),
+            ("launcher_name", LAUNCHER_NAME.to_string()),
+            ("launcher_version", LAUNCHER_VERSION.to_string()),
+            ("resolution_width", width),
+            ("resolution_height", height),
+            ("quickPlaySingleplayer", quick_play_world),
+            ("quickPlayMultiplayer", quick_play_server),
+            // Legacy templates only
+            ("auth_session", legacy_session(auth)),
+            ("user_properties", "{}".to_string()),
+        ])
+    }
+
+    fn version_type(&self) -> &str {
+        self.version_type.as_deref().unwrap_or("release")
+    }
+
+    /// `--server`/`--port`, understood by versions before Quick Play. Joining
+    /// a world directly has no legacy equivalent.
+    fn legacy_quick_play_args(&self) -> Vec<String> {
+        let Some(QuickPlay::Multiplayer(address)) = &self.quick_play else {
+            return vec![];
+        };
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) if port.parse::<u16>().is_ok() => (host, port),
+            _ => (address.as_str(), "25565"),
+        };
+        vec![
+            "--server".to_string(),
+            host.to_string(),
+            "--port".to_string(),
+            port.to_string(),
+        ]
+    }
+
+    fn build_legacy_command(&self, auth: &LaunchAuth) -> Command {
+        let vars = self.argument_variables(auth);
         let mut cmd = Command::new(&self.java_path);
         cmd.current_dir(&self.game_dir);
 
@@ -107,9 +284,49 @@ impl VanillaLaunchConfig {
             cmd.arg("-cp").arg(classpath);
         }
 
+        if self.network_isolated {
+            cmd.args(NETWORK_ISOLATION_JVM_ARGS);
+        }
+
+        // Modern version JSONs pass these through their `jvm` arguments
+        cmd.arg(format!("-Dminecraft.launcher.brand={}", LAUNCHER_NAME))
+            .arg(format!("-Dminecraft.launcher.version={}", LAUNCHER_VERSION));
+
+        if let Some(arguments) = &self.arguments {
+            cmd.args(self.expand(&arguments.jvm, &vars));
+        }
         cmd.args(&self.extra_jvm_args);
         cmd.arg(&self.main_class);
 
+        match &self.legacy_arguments {
+            Some(template) => {
+                cmd.args(
+                    template
+                        .split_whitespace()
+                        .map(|arg| substitute(arg, &vars)),
+                );
+            }
+            None => self.builtin_legacy_game_args(&mut cmd, auth),
+        }
+        if let Some(arguments) = &self.arguments {
+            cmd.args(self.expand(&arguments.game, &vars));
+        }
+
+        if let Some(resolution) = &self.resolution {
+            cmd.arg("--width")
+                .arg(resolution.width.to_string())
+                .arg("--height")
+                .arg(resolution.height.to_string());
+        }
+
+        cmd.args(self.legacy_quick_play_args());
+        cmd.args(&self.extra_game_args);
+
+        cmd
+    }
+
+    /// Game arguments for versions whose JSON has no template.
+    fn builtin_legacy_game_args(&self, cmd: &mut Command, auth: &LaunchAuth) {
         cmd.arg("--username").arg(auth.username());
         cmd.arg("--version").arg(&self.version_name);
         cmd.arg("--gameDir").arg(&self.game_dir);
@@ -122,28 +339,36 @@ impl VanillaLaunchConfig {
         cmd.arg("--uuid").arg(auth.uuid());
         cmd.arg("--accessToken").arg(auth.access_token());
 
-        // Legacy support (1.6.4 and older)
-        // Format often expected: token:<access_token>:<uuid>
-        // Or just the token. Let's try Generic legacy format.
-        let session_str = format!("token:{}:{}", auth.access_token(), auth.uuid());
-        cmd.arg("--session").arg(session_str);
+        cmd.arg("--session").arg(legacy_session(auth));
         cmd.arg("--userType").arg(auth.user_type());
-        cmd.arg("--versionType").arg("release");
+        cmd.arg("--versionType").arg(self.version_type());
         cmd.arg("--userProperties").arg("{}");
+    }
+}
 
-        if let Some(resolution) = &self.resolution {
-            cmd.arg("--width")
-                .arg(resolution.width.to_string())
-                .arg("--height")
-                .arg(resolution.height.to_string());
-        }
-
-        cmd.args(&self.extra_game_args);
+/// Reported to the game as `${launcher_name}` and `minecraft.launcher.brand`.
+const LAUNCHER_NAME: &str = "fastmc";
+const LAUNCHER_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-        cmd
-    }
+/// Session argument of 1.6.4 and older: `token:<access_token>:<uuid>`.
+fn legacy_session(auth: &LaunchAuth) -> String {
+    format!("token:{}:{}", auth.access_token(), auth.uuid())
 }
 
+/// Points every Java proxy setting at the discard port on localhost so Realms,
+/// skin lookups and server join requests fail immediately instead of reaching
+/// the network. Netty sockets are not proxied, which is why isolated launches
+/// also use the offline session token: online-mode servers reject it.
+const NETWORK_ISOLATION_JVM_ARGS: [&str; 7] = [
+    "-Djava.net.preferIPv4Stack=true",
+    "-Dhttp.proxyHost=127.0.0.1",
+    "-Dhttp.proxyPort=9",
+    "-Dhttps.proxyHost=127.0.0.1",
+    "-Dhttps.proxyPort=9",
+    "-DsocksProxyHost=127.0.0.1",
+    "-DsocksProxyPort=9",
+];
+
 fn classpath_separator() -> &'static str {
     if cfg!(windows) { ";" } else { ":" }
 }
@@ -161,6 +386,7 @@ mod tests {
             classpath: vec![PathBuf::from("a.jar"), PathBuf::from("b.jar")],
             main_class: "net.minecraft.client.main.Main".to_string(),
             version_name: "1.20.4".to_string(),
+            version_type: None,
             asset_index: Some("1.20".to_string()),
             resolution: Some(Resolution {
                 width: 854,
@@ -173,6 +399,11 @@ mod tests {
             extra_jvm_args: vec!["-Dfile.encoding=UTF-8".to_string()],
             extra_game_args: vec!["--demo".to_string()],
             natives_dir: Some(PathBuf::from("/tmp/natives")),
+            libraries_dir: None,
+            network_isolated: false,
+            arguments: None,
+            legacy_arguments: None,
+            quick_play: None,
         };
 
         let auth = LaunchAuth::Offline {
@@ -194,5 +425,421 @@ mod tests {
         assert!(args.contains(&"--username".to_string()));
         assert!(args.contains(&"--uuid".to_string()));
         assert!(args.contains(&"--accessToken".to_string()));
+        assert!(!args.contains(&"-Dhttp.proxyHost=127.0.0.1".to_string()));
+    }
+
+    #[test]
+    fn network_isolation_adds_blocking_proxy_args() {
+        let cfg = VanillaLaunchConfig {
+            java_path: PathBuf::from("java"),
+            game_dir: PathBuf::from("/tmp/game"),
+            assets_dir: PathBuf::from("/tmp/assets"),
+            classpath: vec![],
+            main_class: "net.minecraft.client.main.Main".to_string(),
+            version_name: "1.20.4".to_string(),
+            version_type: None,
+            asset_index: None,
+            resolution: None,
+            memory: None,
+            extra_jvm_args: vec![],
+            extra_game_args: vec![],
+            natives_dir: None,
+            libraries_dir: None,
+            network_isolated: true,
+            arguments: None,
+            legacy_arguments: None,
+            quick_play: None,
+        };
+
+        let auth = LaunchAuth::Offline {
+            username: "Player".into(),
+            uuid: "offline-uuid".into(),
+        };
+
+        let args = cfg
+            .build_command(&auth)
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        let main_class_pos = args
+            .iter()
+            .position(|a| a == "net.minecraft.client.main.Main")
+            .unwrap();
+        let proxy_pos = args
+            .iter()
+            .position(|a| a == "-Dhttps.proxyHost=127.0.0.1")
+            .unwrap();
+        assert!(proxy_pos < main_class_pos);
+        assert!(args.contains(&"offline-token".to_string()));
+    }
+
+    #[test]
+    fn modern_arguments_follow_rules_and_substitute_variables() {
+        let arguments: VersionArguments = serde_json::from_value(serde_json::json!({
+            "game": [
+                "--username", "${auth_player_name}",
+                "--version", "${version_name}",
+                {
+                    "rules": [{ "action": "allow", "features": { "is_demo_user": true } }],
+                    "value": "--demo"
+                },
+                {
+                    "rules": [{ "action": "allow", "features": { "has_custom_resolution": true } }],
+                    "value": ["--width", "${resolution_width}", "--height", "${resolution_height}"]
+                }
+            ],
+            "jvm": [
+                {
+                    "rules": [{ "action": "allow", "os": { "name": "no-such-os" } }],
+                    "value": "-XstartOnFirstThread"
+                },
+                "-Djava.library.path=${natives_directory}",
+                "-cp", "${classpath}"
+            ]
+        }))
+        .unwrap();
+
+        let cfg = VanillaLaunchConfig {
+            java_path: PathBuf::from("java"),
+            game_dir: PathBuf::from("/tmp/game"),
+            assets_dir: PathBuf::from("/tmp/assets"),
+            classpath: vec![PathBuf::from("a.jar")],
+            main_class: "net.minecraft.client.main.Main".to_string(),
+            version_name: "1.20.4".to_string(),
+            version_type: None,
+            asset_index: None,
+            resolution: Some(Resolution {
+                width: 854,
+                height: 480,
+            }),
+            memory: None,
+            extra_jvm_args: vec![],
+            extra_game_args: vec![],
+            natives_dir: Some(PathBuf::from("/tmp/natives")),
+            libraries_dir: None,
+            network_isolated: false,
+            arguments: Some(arguments),
+            legacy_arguments: None,
+            quick_play: None,
+        };
+
+        let auth = LaunchAuth::Offline {
+            username: "Player".into(),
+            uuid: "offline-uuid".into(),
+        };
+
+        let args = cfg
+            .build_command(&auth)
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            args,
+            [
+                "-Djava.library.path=/tmp/natives",
+                "-cp",
+                "a.jar",
+                "net.minecraft.client.main.Main",
+                "--username",
+                "Player",
+                "--version",
+                "1.20.4",
+                "--width",
+                "854",
+                "--height",
+                "480",
+            ]
+        );
+    }
+
+    #[test]
+    fn quick_play_uses_feature_args_or_legacy_server_flags() {
+        let arguments: VersionArguments = serde_json::from_value(serde_json::json!({
+            "game": [
+                {
+                    "rules": [{ "action": "allow", "features": { "is_quick_play_multiplayer": true } }],
+                    "value": ["--quickPlayMultiplayer", "${quickPlayMultiplayer}"]
+                }
+            ]
+        }))
+        .unwrap();
+
+        let mut cfg = VanillaLaunchConfig {
+            java_path: PathBuf::from("java"),
+            game_dir: PathBuf::from("/tmp/game"),
+            assets_dir: PathBuf::from("/tmp/assets"),
+            classpath: vec![],
+            main_class: "net.minecraft.client.main.Main".to_string(),
+            version_name: "1.20.4".to_string(),
+            version_type: None,
+            asset_index: None,
+            resolution: None,
+            memory: None,
+            extra_jvm_args: vec![],
+            extra_game_args: vec![],
+            natives_dir: None,
+            libraries_dir: None,
+            network_isolated: false,
+            arguments: Some(arguments),
+            legacy_arguments: None,
+            quick_play: Some(QuickPlay::Multiplayer("smp.example.com:25570".to_string())),
+        };
+        let auth = LaunchAuth::Offline {
+            username: "Player".into(),
+            uuid: "offline-uuid".into(),
+        };
+        let args_of = |cfg: &VanillaLaunchConfig| {
+            cfg.build_command(&auth)
+                .get_args()
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            args_of(&cfg),
+            [
+                "net.minecraft.client.main.Main",
+                "--quickPlayMultiplayer",
+                "smp.example.com:25570"
+            ]
+        );
+
+        cfg.arguments = None;
+        let args = args_of(&cfg);
+        let server = args.iter().position(|a| a == "--server").unwrap();
+        assert_eq!(
+            args[server..server + 4],
+            ["--server", "smp.example.com", "--port", "25570"]
+        );
+    }
+
+    #[test]
+    fn legacy_template_takes_the_arguments_of_a_loader_profile() {
+        let cfg = fixture_config(
+            r#"{
+                "mainClass": "net.minecraft.launchwrapper.Launch",
+                "minecraftArguments": "--username ${auth_player_name}",
+                "arguments": {
+                    "jvm": ["-Dfml.ignoreInvalidMinecraftCertificates=true"],
+                    "game": ["--tweakClass", "net.minecraftforge.fml.common.launcher.FMLTweaker"]
+                }
+            }"#,
+            "1.12.2-forge-14.23.5.2859",
+        );
+        let args = cfg
+            .build_command(&offline())
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        let main_class = args
+            .iter()
+            .position(|a| a == "net.minecraft.launchwrapper.Launch")
+            .unwrap();
+        assert!(
+            args[..main_class]
+                .contains(&"-Dfml.ignoreInvalidMinecraftCertificates=true".to_string())
+        );
+        assert_eq!(
+            args[main_class + 1..],
+            [
+                "--username",
+                "Player",
+                "--tweakClass",
+                "net.minecraftforge.fml.common.launcher.FMLTweaker"
+            ]
+        );
+    }
+
+    #[test]
+    fn substitute_keeps_unknown_placeholders() {
+        let vars = HashMap::from([("known", "x".to_string())]);
+        assert_eq!(substitute("${known}-${unknown}", &vars), "x-${unknown}");
+        assert_eq!(substitute("${unterminated", &vars), "${unterminated");
+    }
+
+    /// Version JSON trimmed to what the launcher reads: `mainClass`, `type`
+    /// and either `arguments` or `minecraftArguments`.
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Fixture {
+        main_class: String,
+        #[serde(rename = "type")]
+        version_type: Option<String>,
+        arguments: Option<VersionArguments>,
+        minecraft_arguments: Option<String>,
+    }
+
+    fn fixture_config(fixture: &str, version_name: &str) -> VanillaLaunchConfig {
+        let fixture: Fixture = serde_json::from_str(fixture).unwrap();
+        VanillaLaunchConfig {
+            java_path: PathBuf::from("java"),
+            game_dir: PathBuf::from("/tmp/game"),
+            assets_dir: PathBuf::from("/tmp/assets"),
+            classpath: vec![
+                PathBuf::from("/tmp/libraries/a.jar"),
+                PathBuf::from("/tmp/versions/client.jar"),
+            ],
+            main_class: fixture.main_class,
+            version_name: version_name.to_string(),
+            version_type: fixture.version_type,
+            asset_index: None,
+            resolution: None,
+            memory: None,
+            extra_jvm_args: vec![],
+            extra_game_args: vec![],
+            natives_dir: Some(PathBuf::from("/tmp/natives")),
+            libraries_dir: None,
+            network_isolated: false,
+            arguments: fixture.arguments,
+            legacy_arguments: fixture.minecraft_arguments,
+            quick_play: None,
+        }
+    }
+
+    fn offline() -> LaunchAuth {
+        LaunchAuth::Offline {
+            username: "Player".into(),
+            uuid: "offline-uuid".into(),
+        }
+    }
+
+    fn microsoft() -> LaunchAuth {
+        LaunchAuth::Microsoft {
+            username: "Player".into(),
+            uuid: "ms-uuid".into(),
+            access_token: "ms-token".into(),
+        }
+    }
+
+    /// Golden files list one argument per line. Lines tagged `[osx] ` or
+    /// `[windows] ` only apply on that OS, `""` is an empty argument, and
+    /// `{sep}`/`{launcher_version}` stand for values that vary by build.
+    fn expected_args(golden: &str) -> Vec<String> {
+        golden
+            .lines()
+            .filter_map(|line| match line.strip_prefix('[') {
+                Some(tagged) => {
+                    let (os, arg) = tagged.split_once("] ").unwrap();
+                    (os == current_os()).then_some(arg)
+                }
+                None => Some(line),
+            })
+            .map(|arg| match arg {
+                "\"\"" => String::new(),
+                arg => arg
+                    .replace("{sep}", classpath_separator())
+                    .replace("{launcher_version}", env!("CARGO_PKG_VERSION")),
+            })
+            .collect()
+    }
+
+    fn assert_golden(cfg: &VanillaLaunchConfig, auth: &LaunchAuth, golden: &str) {
+        let args = cfg
+            .build_command(auth)
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(args, expected_args(golden));
+    }
+
+    #[test]
+    fn golden_modern_vanilla() {
+        let mut cfg = fixture_config(include_str!("../testdata/modern_vanilla.json"), "1.20.4");
+        cfg.asset_index = Some("12".to_string());
+        cfg.resolution = Some(Resolution {
+            width: 854,
+            height: 480,
+        });
+        cfg.memory = Some(MemorySettings {
+            min_megabytes: 512,
+            max_megabytes: 2048,
+        });
+        cfg.extra_jvm_args = vec!["-Dfile.encoding=UTF-8".to_string()];
+        assert_golden(
+            &cfg,
+            &offline(),
+            include_str!("../testdata/modern_vanilla.golden"),
+        );
+    }
+
+    #[test]
+    fn golden_modern_quick_play() {
+        let mut cfg = fixture_config(include_str!("../testdata/modern_vanilla.json"), "1.20.4");
+        cfg.asset_index = Some("12".to_string());
+        cfg.quick_play = Some(QuickPlay::Singleplayer("New World".to_string()));
+        assert_golden(
+            &cfg,
+            &microsoft(),
+            include_str!("../testdata/modern_quick_play.golden"),
+        );
+    }
+
+    #[test]
+    fn golden_fabric() {
+        let cfg = fixture_config(
+            include_str!("../testdata/fabric.json"),
+            "fabric-loader-0.15.11-1.20.4",
+        );
+        assert_golden(&cfg, &offline(), include_str!("../testdata/fabric.golden"));
+    }
+
+    #[test]
+    fn golden_forge() {
+        let mut cfg = fixture_config(
+            include_str!("../testdata/forge.json"),
+            "1.20.1-forge-47.2.0",
+        );
+        cfg.libraries_dir = Some(PathBuf::from("/tmp/libraries"));
+        assert_golden(&cfg, &offline(), include_str!("../testdata/forge.golden"));
+    }
+
+    #[test]
+    fn golden_legacy_forge() {
+        let mut cfg = fixture_config(
+            include_str!("../testdata/legacy_forge.json"),
+            "1.12.2-forge-14.23.5.2860",
+        );
+        cfg.asset_index = Some("1.12".to_string());
+        cfg.resolution = Some(Resolution {
+            width: 854,
+            height: 480,
+        });
+        cfg.memory = Some(MemorySettings {
+            min_megabytes: 512,
+            max_megabytes: 2048,
+        });
+        cfg.extra_jvm_args = vec!["-Dfile.encoding=UTF-8".to_string()];
+        cfg.quick_play = Some(QuickPlay::Multiplayer("mc.example.com".to_string()));
+        assert_golden(
+            &cfg,
+            &offline(),
+            include_str!("../testdata/legacy_forge.golden"),
+        );
+    }
+
+    #[test]
+    fn golden_legacy_1_5_2() {
+        let cfg = fixture_config(include_str!("../testdata/legacy_1.5.2.json"), "1.5.2");
+        assert_golden(
+            &cfg,
+            &microsoft(),
+            include_str!("../testdata/legacy_1.5.2.golden"),
+        );
+    }
+
+    #[test]
+    fn golden_legacy_without_template() {
+        let cfg = fixture_config(
+            r#"{ "mainClass": "net.minecraft.client.Minecraft", "type": "old_beta" }"#,
+            "b1.7.3",
+        );
+        assert_golden(
+            &cfg,
+            &offline(),
+            include_str!("../testdata/legacy_builtin.golden"),
+        );
     }
 }
@@ -1,12 +1,28 @@
 use crate::errors::AuthError;
 use crate::models::{DeviceCodeInfo, DeviceResponse, MicrosoftTokens};
-use oauth2::basic::BasicClient;
+use oauth2::basic::{BasicClient, BasicTokenResponse};
 use oauth2::devicecode::DeviceCodeErrorResponseType;
 use oauth2::reqwest::async_http_client;
+use oauth2::url::Url;
 use oauth2::{
-    AuthUrl, ClientId, DeviceAuthorizationUrl, RequestTokenError, Scope, TokenResponse, TokenUrl,
+    AuthUrl, AuthorizationCode, ClientId, CsrfToken, DeviceAuthorizationUrl, PkceCodeChallenge,
+    RedirectUrl, RequestTokenError, Scope, TokenResponse, TokenUrl,
 };
+use std::collections::HashMap;
 use std::time::{Duration, SystemTime};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+/// Where users revoke the launcher's access to their Microsoft account. The
+/// consumer endpoints have no token revocation API, so withdrawing the app's
+/// consent here is the only way to invalidate its refresh tokens everywhere.
+pub const CONSENT_MANAGEMENT_URL: &str = "https://account.live.com/consent/Manage";
+
+/// How long the browser flow waits for the user to finish signing in.
+const BROWSER_LOGIN_TIMEOUT: Duration = Duration::from_secs(300);
+
+const SCOPES: [&str; 3] = ["XboxLive.signin", "offline_access", "openid"];
 
 #[derive(Clone)]
 pub struct MicrosoftAuthenticator {
@@ -27,9 +43,7 @@ impl MicrosoftAuthenticator {
             .map_err(|err| AuthError::OAuth(format!("{:?}", err)))?;
 
         let response: DeviceResponse = request
-            .add_scope(Scope::new("XboxLive.signin".into()))
-            .add_scope(Scope::new("offline_access".into()))
-            .add_scope(Scope::new("openid".into()))
+            .add_scopes(SCOPES.map(|scope| Scope::new(scope.to_string())))
             .request_async(async_http_client)
             .await
             .map_err(|err| AuthError::OAuth(format!("{:?}", err)))?;
@@ -52,43 +66,78 @@ impl MicrosoftAuthenticator {
             raw: response,
         })
     }
+    /// Polls until the user finishes signing in, the code expires or
+    /// `cancel` fires, whichever comes first.
     pub async fn poll_device_code(
         &self,
         code: &DeviceCodeInfo,
+        cancel: &CancellationToken,
     ) -> Result<MicrosoftTokens, AuthError> {
-        let token = self
+        let poll = self
             .client
             .exchange_device_access_token(&code.raw)
             .request_async(
                 async_http_client,
                 tokio::time::sleep,
                 Some(Duration::from_secs(code.expires_in)),
-            )
+            );
+        let token = tokio::select! {
+            token = poll => token,
+            _ = cancel.cancelled() => return Err(AuthError::Cancelled),
+        };
+        let token = token.map_err(|err| match err {
+            RequestTokenError::ServerResponse(resp)
+                if resp.error() == &DeviceCodeErrorResponseType::ExpiredToken =>
+            {
+                AuthError::OAuth("device code expired".to_string())
+            }
+            other => AuthError::OAuth(other.to_string()),
+        })?;
+
+        tokens_from(&token)
+    }
+    /// Interactive login: opens the authorization page with `open_browser`
+    /// and waits for Microsoft to redirect back to a one-shot listener on
+    /// 127.0.0.1. PKCE ties the returned code to this attempt.
+    pub async fn login_with_browser(
+        &self,
+        open_browser: impl FnOnce(&str) -> Result<(), String>,
+    ) -> Result<MicrosoftTokens, AuthError> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let port = listener.local_addr()?.port();
+        // Any port is accepted for a registered loopback redirect. The
+        // literal address keeps browsers that resolve `localhost` to `::1`
+        // on the listener
+        let redirect = RedirectUrl::new(format!("http://127.0.0.1:{}", port))
+            .map_err(|err| AuthError::OAuth(err.to_string()))?;
+        let client = self.client.clone().set_redirect_uri(redirect);
+
+        let (challenge, verifier) = PkceCodeChallenge::new_random_sha256();
+        let (url, state) = client
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(SCOPES.map(|scope| Scope::new(scope.to_string())))
+            .add_extra_param("prompt", "select_account")
+            .set_pkce_challenge(challenge)
+            .url();
+        open_browser(url.as_str()).map_err(AuthError::Browser)?;
+
+        let code = tokio::time::timeout(
+            BROWSER_LOGIN_TIMEOUT,
+            wait_for_redirect(&listener, state.secret()),
+        )
+        .await
+        .map_err(|_| AuthError::OAuth("browser login timed out".to_string()))??;
+
+        let token = client
+            .exchange_code(AuthorizationCode::new(code))
+            .set_pkce_verifier(verifier)
+            .request_async(async_http_client)
             .await
-            .map_err(|err| match err {
-                RequestTokenError::ServerResponse(resp)
-                    if resp.error() == &DeviceCodeErrorResponseType::ExpiredToken =>
-                {
-                    AuthError::OAuth("device code expired".to_string())
-                }
-                other => AuthError::OAuth(other.to_string()),
-            })?;
-
-        let access_token = token.access_token().secret().to_owned();
-        let refresh_token = token
-            .refresh_token()
-            .map(|v| v.secret().to_owned())
-            .ok_or_else(|| AuthError::OAuth("missing refresh token".to_string()))?;
-        let expires_in = token
-            .expires_in()
-            .unwrap_or_else(|| Duration::from_secs(3600));
-
-        Ok(MicrosoftTokens {
-            access_token,
-            refresh_token,
-            expires_at: unix_timestamp_after(expires_in),
-        })
+            .map_err(|err| AuthError::OAuth(err.to_string()))?;
+
+        tokens_from(&token)
     }
+
     pub async fn refresh_access_token(
         &self,
         refresh_token: &str,
@@ -100,20 +149,7 @@ impl MicrosoftAuthenticator {
             .await
             .map_err(|err| AuthError::OAuth(err.to_string()))?;
 
-        let access_token = token.access_token().secret().to_owned();
-        let refresh_token = token
-            .refresh_token()
-            .map(|v| v.secret().to_owned())
-            .ok_or_else(|| AuthError::OAuth("missing refresh token".to_string()))?;
-        let expires_in = token
-            .expires_in()
-            .unwrap_or_else(|| Duration::from_secs(3600));
-
-        Ok(MicrosoftTokens {
-            access_token,
-            refresh_token,
-            expires_at: unix_timestamp_after(expires_in),
-        })
+        tokens_from(&token)
     }
 }
 
@@ -140,6 +176,90 @@ fn oauth_client(client_id: String) -> BasicClient {
     )
 }
 
+/// Accepts connections until one carries the authorization response, and
+/// answers it with a page that sends the user back to the launcher.
+async fn wait_for_redirect(listener: &TcpListener, state: &str) -> Result<String, AuthError> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let mut buf = vec![0; 8192];
+        let read = stream.read(&mut buf).await?;
+        let request = String::from_utf8_lossy(&buf[..read]);
+
+        // "GET /?code=...&state=... HTTP/1.1"
+        let Some(url) = request
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|target| Url::parse(&format!("http://127.0.0.1{}", target)).ok())
+        else {
+            continue;
+        };
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+
+        if !params.contains_key("error") && !params.contains_key("code") {
+            // The browser asking for a favicon
+            let _ = respond(&mut stream, "404 Not Found", "").await;
+            continue;
+        }
+        // A stale tab or another page on the machine; the real redirect may
+        // still come
+        if params.get("state").map(String::as_str) != Some(state) {
+            let page = "This sign-in is not the one fastMC is waiting for.";
+            let _ = respond(&mut stream, "400 Bad Request", page).await;
+            continue;
+        }
+
+        let result = match (params.get("code"), params.get("error")) {
+            (Some(code), None) => Ok(code.clone()),
+            (_, error) => Err(AuthError::OAuth(
+                params
+                    .get("error_description")
+                    .or(error)
+                    .cloned()
+                    .unwrap_or_default(),
+            )),
+        };
+
+        let page = match &result {
+            Ok(_) => "Signed in. You can close this tab and return to fastMC.",
+            Err(_) => "Sign-in failed. Return to fastMC for details.",
+        };
+        let _ = respond(&mut stream, "200 OK", page).await;
+        return result;
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, message: &str) -> std::io::Result<()> {
+    let body = format!(
+        "<!DOCTYPE html><html><body><p>{}</p></body></html>",
+        message
+    );
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await
+}
+
+fn tokens_from(token: &BasicTokenResponse) -> Result<MicrosoftTokens, AuthError> {
+    let access_token = token.access_token().secret().to_owned();
+    let refresh_token = token
+        .refresh_token()
+        .map(|v| v.secret().to_owned())
+        .ok_or_else(|| AuthError::OAuth("missing refresh token".to_string()))?;
+    let expires_in = token
+        .expires_in()
+        .unwrap_or_else(|| Duration::from_secs(3600));
+
+    Ok(MicrosoftTokens {
+        access_token,
+        refresh_token,
+        expires_at: unix_timestamp_after(expires_in),
+    })
+}
+
 fn unix_timestamp_after(duration: Duration) -> u64 {
     SystemTime::now()
         .checked_add(duration)
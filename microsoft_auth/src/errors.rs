@@ -4,4 +4,10 @@ use thiserror::Error;
 pub enum AuthError {
     #[error("oauth2 error: {0}")]
     OAuth(String),
+    #[error("login cancelled")]
+    Cancelled,
+    #[error("couldn't open the browser: {0}")]
+    Browser(String),
+    #[error("login redirect listener failed: {0}")]
+    Io(#[from] std::io::Error),
 }
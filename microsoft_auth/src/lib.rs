@@ -2,6 +2,7 @@ mod authenticator;
 mod errors;
 mod models;
 
-pub use authenticator::MicrosoftAuthenticator;
+pub use authenticator::{CONSENT_MANAGEMENT_URL, MicrosoftAuthenticator};
 pub use errors::AuthError;
 pub use models::{DeviceCodeInfo, MicrosoftTokens};
+pub use tokio_util::sync::CancellationToken;
@@ -0,0 +1,181 @@
+//! Per-instance activity log in `<instance>/activity.jsonl`: one JSON event
+//! per line, appended as things happen, so users can look up what changed
+//! before an instance broke. Recording never fails the action it describes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const ACTIVITY_FILE: &str = "activity.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Activity {
+    Created {
+        version: String,
+    },
+    LoaderInstalled {
+        loader: String,
+        version: String,
+    },
+    /// Mod jars that appeared or went away since the last check.
+    ModsChanged {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    VersionChanged {
+        from: String,
+        to: String,
+    },
+    Launched {
+        version: String,
+    },
+    /// How the game ended, e.g. `"exit code 0"`.
+    Exited {
+        outcome: String,
+    },
+}
+
+impl fmt::Display for Activity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Activity::Created { version } => write!(f, "Created on {}", version),
+            Activity::LoaderInstalled { loader, version } => {
+                write!(f, "Installed {} {}", loader, version)
+            }
+            Activity::ModsChanged { added, removed } => {
+                let mut parts = Vec::new();
+                if !added.is_empty() {
+                    parts.push(format!("added {}", added.join(", ")));
+                }
+                if !removed.is_empty() {
+                    parts.push(format!("removed {}", removed.join(", ")));
+                }
+                write!(f, "Mods {}", parts.join("; "))
+            }
+            Activity::VersionChanged { from, to } => write!(f, "Version {} → {}", from, to),
+            Activity::Launched { version } => write!(f, "Launched {}", version),
+            Activity::Exited { outcome } => write!(f, "Game exited ({})", outcome),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Unix timestamp, seconds.
+    pub at: u64,
+    #[serde(flatten)]
+    pub activity: Activity,
+}
+
+/// Appends `activity` to the instance's log.
+pub fn record(instance_dir: &Path, activity: Activity) {
+    let entry = Entry {
+        at: unix_now(),
+        activity,
+    };
+    let appended = serde_json::to_string(&entry)
+        .map_err(|e| e.to_string())
+        .and_then(|line| {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(instance_dir.join(ACTIVITY_FILE))
+                .map_err(|e| e.to_string())?;
+            file.write_all(format!("{}\n", line).as_bytes())
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = appended {
+        println!("Failed to record instance activity: {}", e);
+    }
+}
+
+/// Every entry of the instance's log, oldest first. Lines that don't parse
+/// are skipped.
+pub fn read(instance_dir: &Path) -> Vec<Entry> {
+    fs::read_to_string(instance_dir.join(ACTIVITY_FILE))
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Records the jars added to or removed from `.minecraft/mods` since the log
+/// last looked.
+pub fn record_mod_changes(instance_dir: &Path) {
+    let entries = read(instance_dir);
+    let mut known = BTreeSet::new();
+    for entry in &entries {
+        if let Activity::ModsChanged { added, removed } = &entry.activity {
+            known.extend(added.iter().cloned());
+            for name in removed {
+                known.remove(name);
+            }
+        }
+    }
+
+    let present: BTreeSet<String> = fs::read_dir(instance_dir.join(".minecraft").join("mods"))
+        .map(|dir| {
+            dir.flatten()
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| name.ends_with(".jar"))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let added: Vec<String> = present.difference(&known).cloned().collect();
+    let removed: Vec<String> = known.difference(&present).cloned().collect();
+    if !added.is_empty() || !removed.is_empty() {
+        record(instance_dir, Activity::ModsChanged { added, removed });
+    }
+}
+
+/// Records a launch of `version`, preceded by the mod and version changes
+/// since the previous one.
+pub fn record_launch(instance_dir: &Path, version: &str) {
+    record_mod_changes(instance_dir);
+    let entries = read(instance_dir);
+    if let Some(from) = last_version(&entries).filter(|from| *from != version) {
+        record(
+            instance_dir,
+            Activity::VersionChanged {
+                from: from.to_string(),
+                to: version.to_string(),
+            },
+        );
+    }
+    record(
+        instance_dir,
+        Activity::Launched {
+            version: version.to_string(),
+        },
+    );
+}
+
+/// Game version the log last saw the instance on.
+fn last_version(entries: &[Entry]) -> Option<&str> {
+    entries
+        .iter()
+        .rev()
+        .find_map(|entry| match &entry.activity {
+            Activity::Created { version } | Activity::Launched { version } => {
+                Some(version.as_str())
+            }
+            Activity::VersionChanged { to, .. } => Some(to.as_str()),
+            _ => None,
+        })
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
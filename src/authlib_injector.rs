@@ -0,0 +1,116 @@
+//! authlib-injector, the Java agent that points the game's login, session and
+//! skin lookups at a third-party Yggdrasil server instead of Mojang's.
+
+use crate::game;
+use downloader::{CancellationToken, DownloadRequest};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::{Component, Path, PathBuf};
+use tokio::fs;
+
+const LATEST_URL: &str = "https://authlib-injector.yushi.moe/artifact/latest.json";
+
+#[derive(Debug, Deserialize)]
+struct Artifact {
+    version: String,
+    download_url: String,
+    checksums: Checksums,
+}
+
+#[derive(Debug, Deserialize)]
+struct Checksums {
+    sha256: String,
+}
+
+/// Path of the authlib-injector jar under `libraries_dir`, downloading the
+/// latest build if it isn't there yet. When the release server can't be
+/// reached, the newest copy already downloaded is used.
+pub async fn ensure(libraries_dir: &Path, cancel: &CancellationToken) -> Result<PathBuf, String> {
+    let dir = libraries_dir.join("moe/yushi/authlib-injector");
+    let artifact = match fetch_latest().await {
+        Ok(artifact) => artifact,
+        Err(e) => {
+            return newest_downloaded(&dir)
+                .ok_or_else(|| format!("Failed to look up authlib-injector: {}", e));
+        }
+    };
+
+    // The version names a directory, so it mustn't be able to leave `dir`
+    if !is_plain_component(&artifact.version) {
+        return Err(format!(
+            "authlib-injector reported an invalid version: {:?}",
+            artifact.version
+        ));
+    }
+
+    let jar = dir
+        .join(&artifact.version)
+        .join(format!("authlib-injector-{}.jar", artifact.version));
+    let expected = &artifact.checksums.sha256;
+    if !matches_sha256(&jar, expected).await {
+        game::downloader()
+            .download(&DownloadRequest::new(&artifact.download_url, &jar), cancel)
+            .await
+            .map_err(|e| format!("Failed to download authlib-injector: {}", e))?;
+        if !matches_sha256(&jar, expected).await {
+            let _ = fs::remove_file(&jar).await;
+            return Err(format!(
+                "Downloaded authlib-injector {} doesn't match its SHA-256",
+                artifact.version
+            ));
+        }
+    }
+    Ok(jar)
+}
+
+/// JVM arguments that load the agent at `jar` against the server whose
+/// authlib-injector API is at `api_root`.
+pub fn jvm_args(jar: &Path, api_root: &str) -> Vec<String> {
+    vec![format!("-javaagent:{}={}", jar.display(), api_root)]
+}
+
+async fn fetch_latest() -> Result<Artifact, String> {
+    let response = game::downloader()
+        .client()
+        .get(downloader::mirrored(LATEST_URL))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?;
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Whether `jar` exists and hashes to `expected`, which the release server
+/// publishes as SHA-256 only.
+async fn matches_sha256(jar: &Path, expected: &str) -> bool {
+    match fs::read(jar).await {
+        Ok(bytes) => format!("{:x}", Sha256::digest(&bytes)).eq_ignore_ascii_case(expected),
+        Err(_) => false,
+    }
+}
+
+/// Whether `name` is a single file name, with no separators or `..`.
+fn is_plain_component(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    ) && !name.contains(['/', '\\'])
+}
+
+/// The most recently downloaded jar in `dir`, one version per subdirectory.
+fn newest_downloaded(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let version = entry.file_name().to_string_lossy().into_owned();
+            let jar = entry
+                .path()
+                .join(format!("authlib-injector-{}.jar", version));
+            let modified = jar.metadata().and_then(|m| m.modified()).ok()?;
+            Some((modified, jar))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, jar)| jar)
+}
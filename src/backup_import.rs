@@ -0,0 +1,244 @@
+//! Imports a zipped `.minecraft` folder, the backup most players already
+//! have, as a new instance. The game version and loader are read from the
+//! backup's `versions/` profiles, with its mods as a fallback for the loader.
+
+use crate::activity::{self, Activity};
+use crate::instance_manager::{ALL_LOADERS, InstanceManager, InstanceMetadata, ModLoader};
+use crate::mod_metadata;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Entries only found at the top of a `.minecraft` folder, used to tell where
+/// it starts inside the archive.
+const MARKERS: [&str; 5] = [
+    "versions",
+    "mods",
+    "saves",
+    "options.txt",
+    "launcher_profiles.json",
+];
+
+/// Progress of [`import`].
+#[derive(Debug, Clone)]
+pub enum ImportEvent {
+    /// `done` of `total` archive entries are extracted.
+    Extracting {
+        done: usize,
+        total: usize,
+    },
+    Finished(Result<Box<InstanceMetadata>, String>),
+}
+
+/// What the backup's version profiles say it runs.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DetectedVersion {
+    pub game_version: String,
+    pub loader: ModLoader,
+    pub loader_version: Option<String>,
+}
+
+/// Extracts the backup at `archive` into a new instance named `name`,
+/// calling `progress` after each entry. Blocking; call it off the UI thread.
+pub fn import(
+    manager: &InstanceManager,
+    name: String,
+    archive: &Path,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<InstanceMetadata, String> {
+    let file = fs::File::open(archive).map_err(|e| format!("Failed to open the backup: {}", e))?;
+    let mut zip =
+        zip::ZipArchive::new(file).map_err(|e| format!("Not a readable zip file: {}", e))?;
+    let root = find_root(zip.file_names())
+        .ok_or("The archive doesn't contain a .minecraft folder (no versions, mods or saves)")?;
+
+    manager.init().map_err(|e| e.to_string())?;
+    let id = Uuid::new_v4().to_string();
+    let game_dir = manager.game_dir(&id);
+    let result = extract(&mut zip, &root, &game_dir, &mut progress)
+        .map_err(|e| format!("Failed to extract the backup: {}", e))
+        .and_then(|()| {
+            detect_version(&game_dir.join("versions")).ok_or_else(|| {
+                "Couldn't tell the Minecraft version: the backup has no installed versions"
+                    .to_string()
+            })
+        });
+    let detected = match result {
+        Ok(detected) => detected,
+        Err(e) => {
+            let _ = manager.delete_instance(&id);
+            return Err(e);
+        }
+    };
+
+    let (loader, loader_version) = if detected.loader == ModLoader::Vanilla {
+        // Mods without a loader profile: suggest the loader they're built for
+        (mods_loader(&game_dir.join("mods")), None)
+    } else {
+        (detected.loader, detected.loader_version)
+    };
+    let mut metadata = InstanceMetadata::new(id, name, detected.game_version);
+    metadata.loader = loader;
+    metadata.loader_version = loader_version;
+    manager
+        .save_instance(&metadata)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    let instance_dir = game_dir.parent().unwrap_or(&game_dir);
+    activity::record(
+        instance_dir,
+        Activity::Created {
+            version: metadata.game_version.clone(),
+        },
+    );
+    activity::record_mod_changes(instance_dir);
+    Ok(metadata)
+}
+
+/// Path inside the archive where the `.minecraft` contents start, e.g. `""`,
+/// `".minecraft/"` or `"Backup 2024/.minecraft/"`. The shallowest match wins,
+/// so a modpack's nested `mods` folder isn't mistaken for the root.
+pub(crate) fn find_root<'a>(names: impl Iterator<Item = &'a str>) -> Option<String> {
+    names
+        .filter_map(|name| {
+            let parts: Vec<&str> = name.split('/').collect();
+            let index = parts.iter().position(|part| MARKERS.contains(part))?;
+            Some(
+                parts[..index]
+                    .iter()
+                    .map(|part| format!("{}/", part))
+                    .collect(),
+            )
+        })
+        .min_by_key(|root: &String| root.matches('/').count())
+}
+
+fn extract<R: io::Read + io::Seek>(
+    zip: &mut zip::ZipArchive<R>,
+    root: &str,
+    game_dir: &Path,
+    progress: &mut impl FnMut(usize, usize),
+) -> io::Result<()> {
+    fs::create_dir_all(game_dir)?;
+    let total = zip.len();
+    for index in 0..total {
+        let mut entry = zip.by_index(index)?;
+        // `enclosed_name` rejects absolute paths and `..`
+        let target = entry
+            .enclosed_name()
+            .and_then(|path| path.strip_prefix(root).ok())
+            .filter(|path| !path.as_os_str().is_empty())
+            .map(|path| game_dir.join(path));
+        if let Some(target) = target {
+            if entry.is_dir() {
+                fs::create_dir_all(&target)?;
+            } else {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                io::copy(&mut entry, &mut fs::File::create(&target)?)?;
+            }
+        }
+        progress(index + 1, total);
+    }
+    Ok(())
+}
+
+/// Picks the version to run from the profiles in `versions/`. Loader profiles
+/// win over the vanilla version they inherit from; among several, the most
+/// recently released one.
+pub(crate) fn detect_version(versions_dir: &Path) -> Option<DetectedVersion> {
+    let mut profiles: Vec<(bool, String, DetectedVersion)> = fs::read_dir(versions_dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let json = fs::read_to_string(entry.path().join(format!("{}.json", id))).ok()?;
+            let value: serde_json::Value = serde_json::from_str(&json).ok()?;
+            let released = value["releaseTime"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            let detected = match value["inheritsFrom"].as_str() {
+                Some(parent) => parse_loader_id(&id, parent),
+                None => DetectedVersion {
+                    game_version: id,
+                    loader: ModLoader::Vanilla,
+                    loader_version: None,
+                },
+            };
+            Some((detected.loader != ModLoader::Vanilla, released, detected))
+        })
+        .collect();
+    // ISO 8601 release times sort chronologically as strings
+    profiles.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+    profiles.pop().map(|(_, _, detected)| detected)
+}
+
+/// Reads the loader from a profile id as the official installers name them:
+/// `fabric-loader-0.15.11-1.20.4`, `quilt-loader-0.26.0-1.20.4`,
+/// `1.20.1-forge-47.2.0` (`1.12.2-forge1.12.2-14.23.5.2859` before 1.13) and
+/// `neoforge-20.4.80`.
+pub(crate) fn parse_loader_id(id: &str, game_version: &str) -> DetectedVersion {
+    let lower = id.to_lowercase();
+    let (loader, loader_version) = if let Some(rest) = lower.strip_prefix("fabric-loader-") {
+        (
+            ModLoader::Fabric,
+            Some(strip_game_version(rest, game_version)),
+        )
+    } else if let Some(rest) = lower.strip_prefix("quilt-loader-") {
+        (
+            ModLoader::Quilt,
+            Some(strip_game_version(rest, game_version)),
+        )
+    } else if let Some((_, version)) = lower.split_once("neoforge-") {
+        (ModLoader::NeoForge, Some(version.to_string()))
+    } else if let Some((_, rest)) = lower.split_once("forge") {
+        let rest = rest.trim_start_matches('-');
+        let version = rest
+            .strip_prefix(game_version)
+            .map_or(rest, |v| v.trim_start_matches('-'));
+        (ModLoader::Forge, Some(version.to_string()))
+    } else {
+        // OptiFine and other custom profiles run as their base version
+        (ModLoader::Vanilla, None)
+    };
+    DetectedVersion {
+        game_version: game_version.to_string(),
+        loader,
+        loader_version,
+    }
+}
+
+/// `0.15.11-1.20.4` -> `0.15.11`
+fn strip_game_version(rest: &str, game_version: &str) -> String {
+    rest.strip_suffix(game_version)
+        .and_then(|v| v.strip_suffix('-'))
+        .unwrap_or(rest)
+        .to_string()
+}
+
+/// The loader most of the mods in `mods_dir` are built for; Vanilla when
+/// there are none.
+fn mods_loader(mods_dir: &Path) -> ModLoader {
+    let jars: Vec<PathBuf> = fs::read_dir(mods_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "jar"))
+                .collect()
+        })
+        .unwrap_or_default();
+    let declared: Vec<ModLoader> = jars
+        .iter()
+        .flat_map(|jar| mod_metadata::declared_loaders(jar))
+        .collect();
+    ALL_LOADERS
+        .into_iter()
+        .map(|loader| (declared.iter().filter(|d| **d == loader).count(), loader))
+        .filter(|(count, _)| *count > 0)
+        .max_by_key(|(count, _)| *count)
+        .map_or(ModLoader::Vanilla, |(_, loader)| loader)
+}
@@ -0,0 +1,96 @@
+//! Release notes bundled from `CHANGELOG.md`, shown as "What's new" after an
+//! update and in full from Settings.
+
+use config_manager::FastmcConfig;
+use iced::widget::{column, text};
+use iced::{Color, Element};
+
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+/// One `## [version] - date` section of the changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    pub version: &'static str,
+    pub date: Option<&'static str>,
+    pub notes: Vec<Note>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Note {
+    /// `### Added`, `### Fixed`, ...
+    Heading(&'static str),
+    Item(&'static str),
+}
+
+/// Every release in the changelog, newest first.
+pub fn releases() -> Vec<Release> {
+    parse(CHANGELOG)
+}
+
+fn parse(changelog: &'static str) -> Vec<Release> {
+    let mut releases: Vec<Release> = Vec::new();
+    for line in changelog.lines().map(str::trim) {
+        if let Some(title) = line.strip_prefix("## ") {
+            let (version, date) = match title.split_once(" - ") {
+                Some((version, date)) => (version, Some(date.trim())),
+                None => (title, None),
+            };
+            releases.push(Release {
+                version: version.trim().trim_start_matches('[').trim_end_matches(']'),
+                date,
+                notes: Vec::new(),
+            });
+        } else if let Some(release) = releases.last_mut() {
+            if let Some(heading) = line.strip_prefix("### ") {
+                release.notes.push(Note::Heading(heading));
+            } else if let Some(item) = line.strip_prefix("- ") {
+                release.notes.push(Note::Item(item));
+            }
+        }
+    }
+    releases
+}
+
+/// Releases newer than the last version the launcher ran as, recording this
+/// version as seen. Nothing is shown on a fresh install.
+pub fn take_unseen() -> Vec<Release> {
+    let mut config = FastmcConfig::load().unwrap_or_default();
+    let current = env!("CARGO_PKG_VERSION");
+    let unseen = match config.launcher.last_seen_version.as_deref() {
+        Some(seen) if seen == current => return Vec::new(),
+        Some(seen) => releases()
+            .into_iter()
+            .take_while(|release| release.version != seen)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    config.launcher.last_seen_version = Some(current.to_string());
+    if let Err(e) = config.save() {
+        println!("Failed to save last seen version: {}", e);
+    }
+    unseen
+}
+
+/// The notes of `releases`, one block per release.
+pub fn view<'a, M: 'a>(releases: &[Release]) -> Element<'a, M> {
+    let text_primary = Color::from_rgb(0.88, 0.89, 0.91);
+    let text_muted = Color::from_rgb(0.63, 0.64, 0.67);
+
+    let mut content = column![].spacing(14);
+    for release in releases {
+        let title = match release.date {
+            Some(date) => format!("fastMC {} ({})", release.version, date),
+            None => format!("fastMC {}", release.version),
+        };
+        let mut block = column![text(title).size(16).color(text_primary)].spacing(4);
+        for note in &release.notes {
+            block = block.push(match note {
+                Note::Heading(heading) => text(*heading).size(13).color(text_primary),
+                Note::Item(item) => text(format!("• {}", item)).size(12).color(text_muted),
+            });
+        }
+        content = content.push(block);
+    }
+    content.into()
+}
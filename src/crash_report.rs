@@ -0,0 +1,151 @@
+//! Finds and summarizes the report left behind when the game crashes: a
+//! Minecraft crash report in `crash-reports/`, or an `hs_err_pid*.log` when
+//! the JVM itself died.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Stack trace lines shown in the crash dialog.
+const EXCERPT_LINES: usize = 12;
+
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub path: PathBuf,
+    /// What went wrong in one line, e.g. the exception and its message.
+    pub headline: String,
+    /// The part of the report worth reading first.
+    pub excerpt: String,
+    /// Mods the game blamed for the crash, when it could tell.
+    pub suspected_mods: Vec<String>,
+    /// Mods loaded when the game crashed.
+    pub mods: Vec<String>,
+}
+
+/// Newest crash report written after `since`, i.e. by the session that just
+/// ended.
+pub fn find(game_dir: &Path, since: SystemTime) -> Option<CrashReport> {
+    let crash_reports = fs::read_dir(game_dir.join("crash-reports"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "txt"));
+    let jvm_logs = fs::read_dir(game_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| is_jvm_log(&entry.path()));
+
+    let path = crash_reports
+        .chain(jvm_logs)
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .filter(|(modified, _)| *modified >= since)
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)?;
+    let content = fs::read_to_string(&path).ok()?;
+    Some(if is_jvm_log(&path) {
+        parse_jvm_log(path, &content)
+    } else {
+        parse_crash_report(path, &content)
+    })
+}
+
+fn is_jvm_log(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with("hs_err_pid"))
+}
+
+fn parse_crash_report(path: PathBuf, content: &str) -> CrashReport {
+    let lines: Vec<&str> = content.lines().collect();
+    let description = lines
+        .iter()
+        .find_map(|line| line.strip_prefix("Description: "))
+        .unwrap_or("Unexpected error");
+
+    // The exception follows the description after a blank line
+    let trace_start = lines
+        .iter()
+        .position(|line| line.starts_with("Description: "))
+        .and_then(|at| {
+            (at + 1..lines.len()).find(|&i| !lines[i].trim().is_empty() && lines[i - 1].is_empty())
+        });
+    let (headline, excerpt) = match trace_start {
+        Some(start) => {
+            let excerpt = lines[start..]
+                .iter()
+                .take_while(|line| !line.trim().is_empty())
+                .take(EXCERPT_LINES)
+                .copied()
+                .collect::<Vec<_>>()
+                .join("\n");
+            (format!("{}: {}", description, lines[start].trim()), excerpt)
+        }
+        None => (description.to_string(), String::new()),
+    };
+
+    let suspected_mods = lines
+        .iter()
+        .find_map(|line| {
+            let line = line.trim();
+            line.strip_prefix("Suspected Mods: ")
+                .or_else(|| line.strip_prefix("Suspected Mod: "))
+        })
+        .filter(|mods| !mods.starts_with("NONE") && !mods.starts_with("None"))
+        .map(|mods| mods.split(',').map(|m| m.trim().to_string()).collect())
+        .unwrap_or_default();
+
+    CrashReport {
+        path,
+        headline,
+        excerpt,
+        suspected_mods,
+        mods: mod_list(&lines),
+    }
+}
+
+/// Entries of the "Fabric Mods:" or "Mod List:" section of the system
+/// details, which are indented one level deeper than the section name.
+fn mod_list(lines: &[&str]) -> Vec<String> {
+    let Some(start) = lines.iter().position(|line| {
+        let line = line.trim();
+        line == "Fabric Mods:" || line == "Mod List:" || line == "Quilt Mods:"
+    }) else {
+        return Vec::new();
+    };
+    fn indent(line: &str) -> usize {
+        line.len() - line.trim_start().len()
+    }
+    let section_indent = indent(lines[start]);
+    lines[start + 1..]
+        .iter()
+        .take_while(|line| !line.trim().is_empty() && indent(line) > section_indent)
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
+fn parse_jvm_log(path: PathBuf, content: &str) -> CrashReport {
+    // The header is a block of `#` comments describing the fatal error
+    let header: Vec<&str> = content
+        .lines()
+        .take_while(|line| line.starts_with('#') || line.trim().is_empty())
+        .map(|line| line.trim_start_matches('#').trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let headline = header
+        .iter()
+        .find(|line| line.starts_with("SIG") || line.starts_with("EXCEPTION_"))
+        .map(|line| format!("The Java runtime crashed: {}", line))
+        .unwrap_or_else(|| "The Java runtime crashed".to_string());
+
+    CrashReport {
+        path,
+        headline,
+        excerpt: header
+            .into_iter()
+            .take(EXCERPT_LINES)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        suspected_mods: Vec::new(),
+        mods: Vec::new(),
+    }
+}
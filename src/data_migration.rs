@@ -0,0 +1,240 @@
+use crate::instance_manager::{self, InstanceManager};
+use crate::loader_installer;
+use account_manager::AccountStore;
+use directories::ProjectDirs;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use version_manager::LoaderProfile;
+
+/// Layout version written by this build. Bump it together with a new entry in
+/// [`MIGRATIONS`] whenever data moves between directories.
+pub const LAYOUT_VERSION: u32 = 2;
+
+const MARKER_FILE: &str = "layout_version";
+
+/// Directories a migration may read from or write to.
+pub struct DataDirs {
+    pub data: PathBuf,
+    pub data_local: PathBuf,
+}
+
+impl DataDirs {
+    pub fn new() -> Option<Self> {
+        let dirs = ProjectDirs::from("com", "fastmc", "fastmc")?;
+        Some(Self {
+            data: dirs.data_dir().to_path_buf(),
+            data_local: dirs.data_local_dir().to_path_buf(),
+        })
+    }
+
+    fn marker(&self) -> PathBuf {
+        self.data_local.join(MARKER_FILE)
+    }
+
+    fn backups(&self) -> PathBuf {
+        self.data_local.join("backups")
+    }
+
+    /// Where content moved by the migration to `version` is backed up.
+    fn backup_dir(&self, version: u32) -> PathBuf {
+        self.backups().join(format!("layout-v{}", version))
+    }
+}
+
+struct Migration {
+    /// Layout version reached once this step has run.
+    to: u32,
+    description: &'static str,
+    run: fn(&DataDirs, &Path) -> Result<(), String>,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        to: 1,
+        description: "Moving skin cache to the cache directory",
+        run: move_skin_cache,
+    },
+    Migration {
+        to: 2,
+        description: "Turning loader profiles into version JSONs",
+        run: convert_loader_profiles,
+    },
+];
+
+#[derive(Debug, Clone)]
+pub struct MigrationProgress {
+    pub step: usize,
+    pub total: usize,
+    pub description: String,
+}
+
+/// Layout version found on disk. A missing marker on a machine that already
+/// has data means the pre-versioning layout (0); on a fresh install there is
+/// nothing to migrate.
+fn current_version(dirs: &DataDirs) -> u32 {
+    match fs::read_to_string(dirs.marker()) {
+        Ok(raw) => raw.trim().parse().unwrap_or(0),
+        Err(_) if dirs.data.exists() || dirs.data_local.exists() => 0,
+        Err(_) => LAYOUT_VERSION,
+    }
+}
+
+/// Whether any migration has to run before the launcher starts.
+pub fn is_pending() -> bool {
+    DataDirs::new().is_some_and(|dirs| current_version(&dirs) < LAYOUT_VERSION)
+}
+
+/// Runs every pending migration in order, recording the reached version after
+/// each step so an interrupted upgrade resumes where it stopped.
+pub fn run(mut on_progress: impl FnMut(MigrationProgress)) -> Result<(), String> {
+    let dirs = DataDirs::new().ok_or("Data directory unavailable")?;
+    let from = current_version(&dirs);
+    let pending: Vec<&Migration> = MIGRATIONS.iter().filter(|m| m.to > from).collect();
+
+    for (index, migration) in pending.iter().enumerate() {
+        on_progress(MigrationProgress {
+            step: index + 1,
+            total: pending.len(),
+            description: migration.description.to_string(),
+        });
+        println!(
+            "Migrating data layout to v{}: {}",
+            migration.to, migration.description
+        );
+
+        let backup = dirs.backup_dir(migration.to);
+        (migration.run)(&dirs, &backup)?;
+        write_marker(&dirs, migration.to)?;
+    }
+
+    write_marker(&dirs, LAYOUT_VERSION)?;
+    prune_backups(&dirs, from);
+    Ok(())
+}
+
+/// Removes the backups of upgrades before the one that just completed, which
+/// the launcher has run fine since.
+fn prune_backups(dirs: &DataDirs, before: u32) {
+    let Ok(entries) = fs::read_dir(dirs.backups()) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let version = name
+            .to_str()
+            .and_then(|name| name.strip_prefix("layout-v"))
+            .and_then(|version| version.parse::<u32>().ok());
+        if version.is_some_and(|version| version <= before)
+            && let Err(e) = fs::remove_dir_all(entry.path())
+        {
+            println!(
+                "Failed to remove old backup {}: {}",
+                entry.path().display(),
+                e
+            );
+        }
+    }
+}
+
+fn write_marker(dirs: &DataDirs, version: u32) -> Result<(), String> {
+    fs::create_dir_all(&dirs.data_local).map_err(|e| e.to_string())?;
+    fs::write(dirs.marker(), version.to_string())
+        .map_err(|e| format!("Failed to write layout marker: {}", e))
+}
+
+/// Skins used to live in the roaming data dir, which got synced and backed up
+/// on Windows even though they can always be re-downloaded.
+fn move_skin_cache(dirs: &DataDirs, backup: &Path) -> Result<(), String> {
+    let old_dir = dirs.data.join("skins");
+    let new_dir = account_manager::skin_cache_dir().map_err(|e| e.to_string())?;
+    if !old_dir.exists() || old_dir == new_dir {
+        return Ok(());
+    }
+
+    move_dir(&old_dir, &new_dir, &backup.join("skins"))
+        .map_err(|e| format!("Failed to move skin cache: {}", e))?;
+
+    let mut store = AccountStore::load().map_err(|e| e.to_string())?;
+    let old_prefix = old_dir.to_string_lossy().to_string();
+    let new_prefix = new_dir.to_string_lossy().to_string();
+    for account in &mut store.accounts {
+        if let Some(path) = &mut account.skin_path
+            && let Some(rest) = path.strip_prefix(&old_prefix)
+        {
+            *path = format!("{}{}", new_prefix, rest);
+        }
+    }
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Loaders used to be described by a `loader_profile.json` next to each
+/// instance's `.minecraft`, which only fastmc understood. They now get a
+/// version JSON inheriting from the game version, like the official
+/// launcher writes.
+fn convert_loader_profiles(_dirs: &DataDirs, backup: &Path) -> Result<(), String> {
+    let manager = InstanceManager::new();
+    for metadata in manager.list_instances() {
+        let game_dir = manager.game_dir(&metadata.id);
+        let instance_dir = instance_manager::instances_dir().join(&metadata.id);
+        let path = instance_dir.join("loader_profile.json");
+        if !path.exists() {
+            continue;
+        }
+
+        let failed = |e: String| format!("Failed to convert {}: {}", path.display(), e);
+        let content = fs::read_to_string(&path).map_err(|e| failed(e.to_string()))?;
+        // A profile nothing can be made of is left for a reinstall to replace
+        match serde_json::from_str::<LoaderProfile>(&content) {
+            Ok(profile) if metadata.loader_installed && metadata.loader_version.is_some() => {
+                let id = metadata.version_id();
+                let json = loader_installer::version_json(&id, &metadata.game_version, &profile);
+                let dir = game_dir.join("versions").join(&id);
+                fs::create_dir_all(&dir).map_err(|e| failed(e.to_string()))?;
+                let json =
+                    serde_json::to_string_pretty(&json).map_err(|e| failed(e.to_string()))?;
+                fs::write(dir.join(format!("{}.json", id)), json)
+                    .map_err(|e| failed(e.to_string()))?;
+            }
+            _ => println!("Dropping unusable loader profile {}", path.display()),
+        }
+
+        let saved = backup.join(&metadata.id);
+        fs::create_dir_all(&saved).map_err(|e| failed(e.to_string()))?;
+        fs::copy(&path, saved.join("loader_profile.json")).map_err(|e| failed(e.to_string()))?;
+        fs::remove_file(&path).map_err(|e| failed(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Copies `from` into `backup`, then moves it to `to`. Falls back to copy and
+/// delete when a rename isn't possible (e.g. across filesystems).
+fn move_dir(from: &Path, to: &Path, backup: &Path) -> io::Result<()> {
+    copy_dir(from, backup)?;
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if to.exists() {
+        copy_dir(from, to)?;
+        return fs::remove_dir_all(from);
+    }
+    if fs::rename(from, to).is_err() {
+        copy_dir(from, to)?;
+        fs::remove_dir_all(from)?;
+    }
+    Ok(())
+}
+
+pub fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! Optional SQLite store for data the launcher derives itself: instance
+//! stats, the mod metadata cache and maintenance history. `instance.json`
+//! stays the source of truth for instances, and everything in here can be
+//! rebuilt. While the option is off the JSON files are used as before; the
+//! first open imports them.
+
+use config_manager::FastmcConfig;
+use directories::ProjectDirs;
+use rusqlite::Connection;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DB_FILE: &str = "fastmc.db";
+const SCHEMA_VERSION: i32 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS instance_stats (
+    instance_id TEXT PRIMARY KEY,
+    mod_count INTEGER NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    computed_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mod_info (
+    sha1 TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    version TEXT,
+    icon TEXT,
+    url TEXT
+);
+CREATE TABLE IF NOT EXISTS mod_files (
+    path TEXT PRIMARY KEY,
+    size INTEGER NOT NULL,
+    modified INTEGER NOT NULL,
+    sha1 TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS task_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    job TEXT NOT NULL,
+    ran_at INTEGER NOT NULL,
+    ok INTEGER NOT NULL,
+    summary TEXT
+);
+CREATE INDEX IF NOT EXISTS task_history_job ON task_history (job, ran_at);
+";
+
+/// Whether the user chose the database over the JSON files.
+pub fn enabled() -> bool {
+    FastmcConfig::load().is_ok_and(|config| config.launcher.use_database)
+}
+
+fn db_file() -> Result<PathBuf, String> {
+    let dirs = ProjectDirs::from("com", "fastmc", "fastmc").ok_or("Data directory unavailable")?;
+    Ok(dirs.data_local_dir().join(DB_FILE))
+}
+
+/// Opens the database, creating it and importing the JSON data on first use.
+/// Connections are cheap and not shared between threads; open one per use.
+pub fn open() -> Result<Connection, String> {
+    let path = db_file()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let conn = Connection::open(&path).map_err(|e| e.to_string())?;
+    // Screens and background jobs write concurrently
+    conn.busy_timeout(Duration::from_secs(5))
+        .map_err(|e| e.to_string())?;
+    conn.pragma_update(None, "journal_mode", "WAL")
+        .map_err(|e| e.to_string())?;
+    migrate(&conn)?;
+    Ok(conn)
+}
+
+fn migrate(conn: &Connection) -> Result<(), String> {
+    let version: i32 = conn
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if version >= SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    tx.execute_batch(SCHEMA).map_err(|e| e.to_string())?;
+    if version == 0 {
+        crate::instance_manager::InstanceManager::new().import_stats(&tx)?;
+        crate::mod_metadata::import_cache(&tx)?;
+        crate::maintenance::import_state(&tx)?;
+    }
+    tx.pragma_update(None, "user_version", SCHEMA_VERSION)
+        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())
+}
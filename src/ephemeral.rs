@@ -0,0 +1,51 @@
+//! Throwaway copies of an instance for ephemeral runs: the game plays in a
+//! temporary directory that is deleted once it exits, so nothing it changes
+//! reaches the instance.
+
+use crate::data_migration::copy_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Temporary copy of an instance directory, deleted on drop.
+pub struct EphemeralCopy {
+    root: PathBuf,
+}
+
+impl EphemeralCopy {
+    /// Copies `instance_dir` into the system temp directory. Downloaded
+    /// libraries and assets are copied too, since a link would let the game
+    /// write through to the instance. Slow for big modpacks; call it off the
+    /// UI thread.
+    pub fn create(instance_dir: &Path, id: &str) -> Result<Self, String> {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        let copy = Self {
+            root: std::env::temp_dir()
+                .join("fastmc-ephemeral")
+                .join(format!("{}-{}", id, stamp)),
+        };
+        copy_dir(&instance_dir.join(".minecraft"), &copy.game_dir())
+            .map_err(|e| format!("Failed to copy the instance for an ephemeral run: {}", e))?;
+        Ok(copy)
+    }
+
+    /// The copy's `.minecraft`.
+    pub fn game_dir(&self) -> PathBuf {
+        self.root.join(".minecraft")
+    }
+}
+
+impl Drop for EphemeralCopy {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.root) {
+            println!(
+                "Failed to remove ephemeral copy {}: {}",
+                self.root.display(),
+                e
+            );
+        }
+    }
+}
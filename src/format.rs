@@ -0,0 +1,87 @@
+//! Human-readable formatting shared by the screens. Strings are English-only
+//! until the launcher grows a locale setting.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// `1536` -> `"1.5 KB"`.
+pub fn size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Unix timestamp (seconds) relative to now, e.g. `"2 days ago"`. Zero means
+/// the event never happened.
+pub fn relative_time(unix_secs: u64) -> String {
+    if unix_secs == 0 {
+        return "Never".to_string();
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let elapsed = now.saturating_sub(unix_secs);
+
+    const STEPS: [(u64, &str); 5] = [
+        (60, "minute"),
+        (60 * 60, "hour"),
+        (60 * 60 * 24, "day"),
+        (60 * 60 * 24 * 30, "month"),
+        (60 * 60 * 24 * 365, "year"),
+    ];
+    match STEPS.iter().rev().find(|(secs, _)| elapsed >= *secs) {
+        Some((secs, unit)) => {
+            let count = elapsed / secs;
+            let plural = if count == 1 { "" } else { "s" };
+            format!("{} {}{} ago", count, unit, plural)
+        }
+        None => "Just now".to_string(),
+    }
+}
+
+/// `3725s` -> `"1h 02m"`, `200s` -> `"3m 20s"`.
+pub fn duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Unix timestamp as a UTC date and time usable in file names, e.g.
+/// `"2024-05-01_13-45-09"`.
+pub fn file_timestamp(unix_secs: u64) -> String {
+    let (days, secs) = (unix_secs / 86_400, unix_secs % 86_400);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}_{:02}-{:02}-{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
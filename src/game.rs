@@ -1,516 +1,549 @@
-use account_manager::Account;
-use launcher::{LaunchAuth, MemorySettings, Resolution, VanillaLaunchConfig};
-use serde::Deserialize;
+use crate::authlib_injector;
+use crate::instance_manager::{InstanceManager, InstanceMetadata, LaunchArch};
+use crate::log4j;
+use account_manager::{Account, AccountKind};
+use config_manager::{DownloadsConfig, FastmcConfig};
+use downloader::{CancellationToken, DownloadError, Downloader, RetryPolicy};
+use game_installer::{GameDirs, InstallProgress, LaunchOptions};
+use java_manager::{DetectionSummary, JavaLaunchSettings, MemoryCheck, RuntimeError};
+use launcher::{LaunchAuth, MemorySettings};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::RwLock;
+use std::time::{Duration, Instant, SystemTime};
 use tokio::fs;
+use version_manager::JavaVersion;
 
-#[allow(dead_code)]
+pub use game_installer::{LaunchPhase, host_arch, maven_to_path};
+
+/// Events emitted by [`prepare_and_launch`] while it gets an instance ready,
+/// and by the launch task once the game process is up.
+#[derive(Debug, Clone)]
 pub enum LaunchProgress {
-    Downloading(String, f32), // File, percentage
-    Extracting,
-    Launching,
+    Phase(LaunchPhase),
+    /// `done` of `total` items of the current phase are processed; `file` is
+    /// the most recent one.
+    Item {
+        file: String,
+        done: usize,
+        total: usize,
+    },
+    /// Bytes received over the network since the launch started.
+    Bytes(u64),
+    /// Download speed currently measured by the downloader, in bytes/s.
+    Speed(u64),
+    /// The game got past JVM startup and is opening its window, see
+    /// [`is_started_line`].
+    Running,
+    /// The game exited abnormally and left a report behind.
+    Crashed(Box<crate::crash_report::CrashReport>),
+    /// The game crashed and is being launched again, see
+    /// `InstanceMetadata::restart_on_crash`.
+    Restarting {
+        attempt: u32,
+        max: u32,
+    },
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Deserialize)]
-struct VersionManifest {
-    versions: Vec<VersionEntry>,
+impl From<InstallProgress> for LaunchProgress {
+    fn from(event: InstallProgress) -> Self {
+        match event {
+            InstallProgress::Phase(phase) => LaunchProgress::Phase(phase),
+            InstallProgress::Item { file, done, total } => {
+                LaunchProgress::Item { file, done, total }
+            }
+            InstallProgress::Bytes(bytes) => LaunchProgress::Bytes(bytes),
+            InstallProgress::Speed(bytes_per_sec) => LaunchProgress::Speed(bytes_per_sec),
+        }
+    }
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Deserialize)]
-struct VersionEntry {
-    id: String,
-    url: String,
+pub type ProgressSender = tokio::sync::mpsc::UnboundedSender<LaunchProgress>;
+
+/// How a game process ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameExit {
+    /// Exit code; `None` when the process was killed by a signal.
+    pub code: Option<i32>,
+    /// The user stopped the game.
+    pub stopped: bool,
+    /// The game got as far as opening its window.
+    pub started: bool,
 }
 
-#[derive(Debug, Deserialize)]
-struct VersionData {
-    libraries: Vec<Library>,
-    #[serde(rename = "mainClass")]
-    main_class: String,
-    downloads: VersionDownloads,
-    #[serde(rename = "assetIndex")]
-    asset_index: AssetIndexRef,
+impl GameExit {
+    pub fn crashed(&self) -> bool {
+        !self.stopped && self.code != Some(0)
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct AssetIndexRef {
-    id: String,
-    url: String,
+impl std::fmt::Display for GameExit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.code {
+            _ if self.stopped => write!(f, "stopped"),
+            Some(code) => write!(f, "exit code {}", code),
+            None => write!(f, "killed by a signal"),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct VersionDownloads {
-    client: DownloadFile,
+/// Folds [`LaunchProgress`] events into what a progress bar needs.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchStatus {
+    pub phase: Option<LaunchPhase>,
+    pub done: usize,
+    pub total: usize,
+    pub bytes: u64,
+    pub bytes_per_sec: u64,
+    /// When the game reported it started; `None` while still preparing.
+    pub running_since: Option<Instant>,
+    /// Restarts after crashes so far, and the most allowed.
+    pub restarts: Option<(u32, u32)>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
-struct DownloadFile {
-    url: String,
-    #[allow(dead_code)]
-    sha1: String,
-    #[allow(dead_code)]
-    size: u64,
-    path: Option<String>,
+impl LaunchStatus {
+    pub fn apply(&mut self, event: LaunchProgress) {
+        match event {
+            LaunchProgress::Phase(phase) => {
+                self.phase = Some(phase);
+                self.done = 0;
+                self.total = 0;
+                self.bytes_per_sec = 0;
+            }
+            LaunchProgress::Item { done, total, .. } => {
+                self.done = done;
+                self.total = total;
+            }
+            LaunchProgress::Bytes(bytes) => self.bytes = bytes,
+            LaunchProgress::Speed(bytes_per_sec) => self.bytes_per_sec = bytes_per_sec,
+            LaunchProgress::Running => self.running_since = Some(Instant::now()),
+            LaunchProgress::Crashed(_) => self.running_since = None,
+            LaunchProgress::Restarting { attempt, max } => {
+                *self = LaunchStatus {
+                    restarts: Some((attempt, max)),
+                    ..LaunchStatus::default()
+                };
+            }
+        }
+    }
+
+    /// Progress through the current phase, 0.0..=1.0.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.done as f32 / self.total as f32
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let restarts = match self.restarts {
+            Some((attempt, max)) => format!(" • restart {}/{}", attempt, max),
+            None => String::new(),
+        };
+        if let Some(since) = self.running_since {
+            return format!(
+                "Running • {}{}",
+                crate::format::duration(since.elapsed()),
+                restarts
+            );
+        }
+        let mut label = match self.phase {
+            Some(phase) => phase.to_string(),
+            None => "Preparing".to_string(),
+        };
+        if self.total > 0 {
+            label.push_str(&format!(" ({}/{})", self.done, self.total));
+        }
+        if self.bytes > 0 {
+            label.push_str(&format!(" • {}", crate::format::size(self.bytes)));
+        }
+        if self.bytes_per_sec > 0 {
+            label.push_str(&format!(" • {}/s", crate::format::size(self.bytes_per_sec)));
+        }
+        label.push_str(&restarts);
+        label
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct Library {
-    downloads: LibraryDownloads,
-    #[allow(dead_code)]
-    name: String,
+/// Runtimes to pick the game's Java from: on ARM64, those of the
+/// architecture the instance asks for. `Auto` falls back to x86_64 for
+/// versions on LWJGL 2, which has no ARM64 natives.
+fn runtimes_for_arch(
+    java: &DetectionSummary,
+    preference: LaunchArch,
+    uses_lwjgl2: bool,
+) -> Result<DetectionSummary, String> {
+    if host_arch() != "aarch64" {
+        return Ok(java.clone());
+    }
+    match preference {
+        LaunchArch::X86_64 => java.for_arch("x86_64").ok_or_else(|| {
+            "This instance is set to run on x86_64, but no x86_64 Java runtime is installed."
+                .to_string()
+        }),
+        LaunchArch::Auto if uses_lwjgl2 => {
+            Ok(java.for_arch("x86_64").unwrap_or_else(|| java.clone()))
+        }
+        LaunchArch::Auto | LaunchArch::Native => {
+            Ok(java.for_arch("aarch64").unwrap_or_else(|| java.clone()))
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct LibraryDownloads {
-    artifact: Option<DownloadFile>,
-    classifiers: Option<serde_json::Value>,
+/// Architecture of the Mojang runtime to install, following the same
+/// preference [`runtimes_for_arch`] applies to detected ones.
+fn runtime_arch(preference: LaunchArch, uses_lwjgl2: bool) -> &'static str {
+    let host = host_arch();
+    if host != "aarch64" {
+        return host;
+    }
+    match preference {
+        LaunchArch::X86_64 => "x86_64",
+        LaunchArch::Auto if uses_lwjgl2 => "x86_64",
+        LaunchArch::Auto | LaunchArch::Native => host,
+    }
 }
 
+/// `skin_server` is the API root of the [`crate::skin_server`] serving an
+/// offline account's skin, if one is running.
+#[allow(clippy::too_many_arguments)]
 pub async fn prepare_and_launch(
     account: &Account,
     access_token: &str,
-    java_path: PathBuf,
+    skin_server: Option<&str>,
+    java: &DetectionSummary,
+    java_settings: &JavaLaunchSettings,
     game_dir: PathBuf,
-    version_id: &str,
+    instance: &InstanceMetadata,
+    progress: &ProgressSender,
+    cancel: &CancellationToken,
 ) -> Result<Command, String> {
-    // 1. Setup directories
-    let versions_dir = game_dir.join("versions");
-    let libraries_dir = game_dir.join("libraries");
-    let assets_dir = game_dir.join("assets");
-    let natives_dir = game_dir.join("natives").join(version_id);
-
-    fs::create_dir_all(&versions_dir)
-        .await
-        .map_err(|e| e.to_string())?;
-    fs::create_dir_all(&libraries_dir)
-        .await
-        .map_err(|e| e.to_string())?;
-    fs::create_dir_all(&assets_dir)
-        .await
-        .map_err(|e| e.to_string())?;
-    fs::create_dir_all(&natives_dir)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    // 2. Fetch Manifest
-    let version_json_path = versions_dir
-        .join(version_id)
-        .join(format!("{}.json", version_id));
-
-    println!("Checking version manifest at {:?}", version_json_path);
-
-    // We can't use a closure easily with async recursion/await inside without BoxFuture.
-    // So we'll just inline the logic or use a loop.
-    let version_data: VersionData = if version_json_path.exists() {
-        let content = fs::read_to_string(&version_json_path)
-            .await
-            .map_err(|e| e.to_string())?;
-        match serde_json::from_str::<VersionData>(&content) {
-            Ok(data) => data,
-            Err(_) => {
-                println!("Local manifest corrupted. Re-downloading...");
-                fetch_manifest(version_id, &versions_dir, &version_json_path).await?
-            }
-        }
-    } else {
-        fetch_manifest(version_id, &versions_dir, &version_json_path).await?
+    let version_id = &instance.version_id();
+    // The UI may have gone away; a closed channel must not abort the launch.
+    let report = |event| {
+        let _ = progress.send(event);
     };
-
-    // 3. Download Client JAR
-    let client_jar = versions_dir
-        .join(version_id)
-        .join(format!("{}.jar", version_id));
-    if !client_jar.exists() {
-        download_file(&version_data.downloads.client.url, &client_jar).await?;
-    }
-
-    // 4. Download Libraries (Including Natives)
-    let mut classpath = vec![];
-    for lib in version_data.libraries {
-        // Standard library
-        if let Some(artifact) = lib.downloads.artifact {
-            let rel_path = if let Some(p) = artifact.path {
-                p
-            } else {
-                maven_to_path(&lib.name).to_string_lossy().to_string()
-            };
-
-            let lib_path = libraries_dir.join(&rel_path);
-
-            if !lib_path.exists() {
-                if let Some(parent) = lib_path.parent() {
-                    fs::create_dir_all(parent)
-                        .await
-                        .map_err(|e| e.to_string())?;
+    report(LaunchProgress::Phase(LaunchPhase::Manifest));
+
+    let dirs = GameDirs::new(game_dir.clone(), version_id);
+    dirs.create().await?;
+    let version = game_installer::load_version(&downloader(), version_id, &dirs.versions).await?;
+    let uses_lwjgl2 = game_installer::uses_lwjgl2(&game_installer::library_names(&version));
+
+    let mut runtime_bytes: u64 = 0;
+    let mojang_runtime = match version.java_version.as_ref() {
+        Some(JavaVersion {
+            component: Some(component),
+            ..
+        }) if java_settings.mojang_runtimes && java_settings.java_path.is_none() => {
+            let arch = runtime_arch(instance.launch_arch, uses_lwjgl2);
+            report(LaunchProgress::Phase(LaunchPhase::Runtime));
+            let result =
+                java_manager::ensure_runtime(&downloader(), component, arch, cancel, |p| {
+                    report(LaunchProgress::Item {
+                        file: p.current.clone(),
+                        done: p.completed,
+                        total: p.total,
+                    });
+                    report(LaunchProgress::Bytes(p.bytes));
+                    report(LaunchProgress::Speed(p.bytes_per_sec));
+                    runtime_bytes = p.bytes;
+                })
+                .await;
+            match result {
+                Ok(runtime) => Some(runtime),
+                Err(RuntimeError::Download(DownloadError::Cancelled)) => {
+                    return Err(DownloadError::Cancelled.to_string());
                 }
-                download_file(&artifact.url, &lib_path).await?;
-            }
-            classpath.push(lib_path);
-        }
-
-        // Natives
-        if let Some(classifiers) = lib.downloads.classifiers {
-            let os_classifier = if cfg!(target_os = "windows") {
-                "natives-windows"
-            } else if cfg!(target_os = "macos") {
-                "natives-macos"
-            } else if cfg!(target_os = "linux") {
-                "natives-linux"
-            } else {
-                "natives-unknown"
-            };
-
-            if let Some(native_obj) = classifiers.get(os_classifier) {
-                if let Ok(file_info) = serde_json::from_value::<DownloadFile>(native_obj.clone()) {
-                    let nat_path = libraries_dir.join(format!(
-                        "{}-{}.jar",
-                        lib.name.replace(':', "-"),
-                        os_classifier
-                    ));
-
-                    if !nat_path.exists() {
-                        download_file(&file_info.url, &nat_path).await?;
-                    }
-
-                    // Extract (Synchronous - handled in blocking task)
-                    let nat_path_clone = nat_path.clone();
-                    let natives_dir_clone = natives_dir.clone();
-
-                    tokio::task::spawn_blocking(move || {
-                        if let Ok(file) = std::fs::File::open(&nat_path_clone) {
-                            if let Ok(mut archive) = zip::ZipArchive::new(file) {
-                                for i in 0..archive.len() {
-                                    if let Ok(mut file) = archive.by_index(i) {
-                                        if file.name().contains("META-INF") {
-                                            continue;
-                                        }
-                                        let outpath = natives_dir_clone.join(file.name());
-                                        if let Some(p) = outpath.parent() {
-                                            std::fs::create_dir_all(p).ok();
-                                        }
-                                        if let Ok(mut outfile) = std::fs::File::create(&outpath) {
-                                            std::io::copy(&mut file, &mut outfile).ok();
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    })
-                    .await
-                    .map_err(|e| e.to_string())?;
+                // Detected runtimes still work, so this only costs the exact match
+                Err(e) => {
+                    println!("Falling back to a detected Java runtime: {}", e);
+                    None
                 }
             }
         }
-    }
-    classpath.push(client_jar);
-
-    // 5. Assets Index and Objects
-    let asset_index_path = assets_dir
-        .join("indexes")
-        .join(format!("{}.json", version_data.asset_index.id));
-    if !asset_index_path.exists() {
-        if let Some(parent) = asset_index_path.parent() {
-            fs::create_dir_all(parent)
-                .await
-                .map_err(|e| e.to_string())?;
+        _ => None,
+    };
+    let (java_path, arch, java_64_bit) = match mojang_runtime {
+        Some(runtime) => {
+            println!("Using Mojang's {} ({})", runtime.component, runtime.version);
+            (
+                runtime.java,
+                runtime.arch.to_string(),
+                Some(runtime.arch != "x86"),
+            )
         }
-        download_file(&version_data.asset_index.url, &asset_index_path).await?;
-    }
-
-    println!("Verifying assets from index: {:?}", asset_index_path);
-    let index_content = fs::read_to_string(&asset_index_path)
-        .await
-        .map_err(|e| e.to_string())?;
-    let index_data: serde_json::Value =
-        serde_json::from_str(&index_content).map_err(|e| e.to_string())?;
-
-    let mut map_to_resources = false;
-    let mut is_virtual = false;
+        None => {
+            let java = &runtimes_for_arch(java, instance.launch_arch, uses_lwjgl2)?;
+            let java_path = java.select_for_requirement(
+                version.java_version.as_ref().map(|j| j.major_version),
+                &instance.game_version,
+            )?;
+            let installation = java.installation(&java_path);
+            // Natives have to match the runtime, which may be emulated
+            let arch = installation
+                .and_then(|i| i.arch.clone())
+                .unwrap_or_else(|| host_arch().to_string());
+            let java_64_bit = installation.and_then(|i| i.is_64_bit);
+            (java_path, arch, java_64_bit)
+        }
+    };
+    println!("Selected Java path: {:?} ({})", java_path, arch);
 
-    if let Some(val) = index_data.get("map_to_resources") {
-        map_to_resources = val.as_bool().unwrap_or(false);
+    // Refuse a heap the machine or the JVM can't provide before downloading anything
+    let total_memory_mb = java_manager::total_memory_mb();
+    if let MemoryCheck::Warning(reason) = java_settings.memory_check(total_memory_mb, java_64_bit) {
+        println!("Warning: {}", reason);
     }
-    if let Some(val) = index_data.get("virtual") {
-        is_virtual = val.as_bool().unwrap_or(false);
+    let (min_memory_mb, max_memory_mb) =
+        java_settings.memory_bounds(total_memory_mb, java_64_bit)?;
+
+    let installation =
+        game_installer::install(&downloader(), version, &dirs, &arch, cancel, |event| {
+            report(match event {
+                InstallProgress::Bytes(bytes) => LaunchProgress::Bytes(runtime_bytes + bytes),
+                event => event.into(),
+            })
+        })
+        .await?;
+
+    // Missing assets only show up in game as silent sounds or missing
+    // textures, so rather than launching anyway the instance is flagged.
+    record_missing_assets(instance, installation.missing_assets);
+    if installation.missing_assets > 0 {
+        return Err(format!(
+            "{} game assets could not be downloaded. The instance is marked incomplete; launch it again to repair.",
+            installation.missing_assets
+        ));
     }
 
-    if let Some(objects) = index_data["objects"].as_object() {
-        let objects_dir = assets_dir.join("objects");
-        let resources_dir = game_dir.join("resources");
-        let virtual_assets_dir = assets_dir.join("virtual").join("legacy");
-
-        if map_to_resources {
-            fs::create_dir_all(&resources_dir)
-                .await
-                .map_err(|e| e.to_string())?;
-        }
-        if is_virtual {
-            fs::create_dir_all(&virtual_assets_dir)
-                .await
-                .map_err(|e| e.to_string())?;
+    report(LaunchProgress::Phase(LaunchPhase::Launching));
+    let mut extra_jvm_args = vec![];
+    if instance.heap_dump_on_oom {
+        let dump_dir = heap_dump_dir(&game_dir);
+        fs::create_dir_all(&dump_dir)
+            .await
+            .map_err(|e| e.to_string())?;
+        extra_jvm_args.push("-XX:+HeapDumpOnOutOfMemoryError".to_string());
+        extra_jvm_args.push(format!("-XX:HeapDumpPath={}", dump_dir.display()));
+    }
+    let library_names = game_installer::library_names(&installation.version);
+    extra_jvm_args.extend(
+        log4j::mitigation_args(
+            &downloader(),
+            installation
+                .version
+                .logging
+                .as_ref()
+                .and_then(|logging| logging.client.as_ref()),
+            &library_names,
+            &dirs.assets,
+            cancel,
+        )
+        .await?,
+    );
+    if let AccountKind::Custom { server, .. } = &account.kind
+        && !instance.offline_mode
+    {
+        let agent = authlib_injector::ensure(&dirs.libraries, cancel).await?;
+        extra_jvm_args.extend(authlib_injector::jvm_args(&agent, server));
+    } else if let Some(server) = skin_server {
+        // Only the skin is at stake, so the game still starts without it.
+        // The server is on localhost, which offline mode doesn't block.
+        match authlib_injector::ensure(&dirs.libraries, cancel).await {
+            Ok(agent) => extra_jvm_args.extend(authlib_injector::jvm_args(&agent, server)),
+            Err(e) => println!("Launching without the offline skin: {}", e),
         }
+    }
+    if instance.disable_telemetry {
+        disable_telemetry(&game_dir).await?;
+    }
 
-        // For performance, we should parallelize this. But strict sequential for now to avoid complexity.
-        // Or simple concurrency.
-        for (name, obj) in objects {
-            if let Some(hash) = obj["hash"].as_str()
-                && hash.len() >= 2
-            {
-                let prefix = &hash[..2];
-                let object_path = objects_dir.join(prefix).join(hash);
-
-                if !object_path.exists() {
-                    let url = format!(
-                        "https://resources.download.minecraft.net/{}/{}",
-                        prefix, hash
-                    );
-                    if let Some(parent) = object_path.parent() {
-                        fs::create_dir_all(parent)
-                            .await
-                            .map_err(|e| e.to_string())?;
-                    }
-                    match download_file(&url, &object_path).await {
-                        Ok(_) => {}
-                        Err(e) => println!("Failed to download asset {}: {}", hash, e),
-                    }
-                }
-
-                // Copy to resources if legacy (map_to_resources)
-                if map_to_resources && object_path.exists() {
-                    let res_path = resources_dir.join(name);
-                    if !res_path.exists() {
-                        if let Some(p) = res_path.parent() {
-                            fs::create_dir_all(p).await.map_err(|e| e.to_string())?;
-                        }
-                        fs::copy(&object_path, &res_path).await.map_err(|e| {
-                            format!("Failed to copy legacy resource {}: {}", name, e)
-                        })?;
-                    }
-                }
-
-                // Copy to virtual/legacy if virtual
-                if is_virtual && object_path.exists() {
-                    let virt_path = virtual_assets_dir.join(name);
-                    if !virt_path.exists() {
-                        if let Some(p) = virt_path.parent() {
-                            fs::create_dir_all(p).await.map_err(|e| e.to_string())?;
-                        }
-                        fs::copy(&object_path, &virt_path)
-                            .await
-                            .map_err(|e| format!("Failed to copy virtual asset {}: {}", name, e))?;
-                    }
-                }
+    // Offline-mode instances never receive the account's access token, even for
+    // online accounts, so the session can't authenticate against any server.
+    let auth = match &account.kind {
+        AccountKind::Microsoft { uuid, username } if !instance.offline_mode => {
+            LaunchAuth::Microsoft {
+                username: username.clone(),
+                uuid: uuid.clone(),
+                access_token: access_token.to_string(),
             }
         }
-
-        // Fix for VanillaTweakInjector looking in assets/icons instead of resources/icons
-        if map_to_resources {
-            let src_icons = resources_dir.join("icons");
-            let dst_icons = assets_dir.join("icons");
-            if src_icons.exists() && !dst_icons.exists() {
-                fs::create_dir_all(&dst_icons)
-                    .await
-                    .map_err(|e| e.to_string())?;
-
-                let mut entries = fs::read_dir(&src_icons).await.map_err(|e| e.to_string())?;
-                while let Ok(Some(entry)) = entries.next_entry().await {
-                    let path = entry.path();
-                    if path.is_file() {
-                        let name = entry.file_name();
-                        fs::copy(&path, dst_icons.join(name))
-                            .await
-                            .map_err(|e| e.to_string())?;
-                    }
-                }
-            }
-
-            // Also copy to virtual assets dir if active
-            if is_virtual {
-                let virt_icons = virtual_assets_dir.join("icons");
-                if src_icons.exists() && !virt_icons.exists() {
-                    fs::create_dir_all(&virt_icons)
-                        .await
-                        .map_err(|e| e.to_string())?;
-                    let mut entries = fs::read_dir(&src_icons).await.map_err(|e| e.to_string())?;
-                    while let Ok(Some(entry)) = entries.next_entry().await {
-                        let path = entry.path();
-                        if path.is_file() {
-                            let name = entry.file_name();
-                            fs::copy(&path, virt_icons.join(name))
-                                .await
-                                .map_err(|e| e.to_string())?;
-                        }
-                    }
-                }
+        AccountKind::Custom { uuid, username, .. } if !instance.offline_mode => {
+            LaunchAuth::Yggdrasil {
+                username: username.clone(),
+                uuid: uuid.clone(),
+                access_token: access_token.to_string(),
             }
         }
-    }
-
-    // 6. Build Config
-    let launch_assets_dir = if is_virtual {
-        assets_dir.join("virtual").join("legacy")
-    } else {
-        assets_dir.clone()
+        AccountKind::Microsoft { uuid, username }
+        | AccountKind::Offline { username, uuid }
+        | AccountKind::Custom { uuid, username, .. } => LaunchAuth::Offline {
+            username: username.clone(),
+            uuid: uuid.clone(),
+        },
     };
 
-    // Check for loader profile (written by loader_installer)
-    let loader_profile_path = game_dir
-        .parent()
-        .unwrap_or(&game_dir)
-        .join("loader_profile.json");
-
-    let mut main_class = version_data.main_class;
-    let mut extra_jvm_args = vec![];
-    let mut extra_game_args = vec![];
-
-    if loader_profile_path.exists() {
-        let profile_content = fs::read_to_string(&loader_profile_path)
-            .await
-            .map_err(|e| format!("Failed to read loader profile: {}", e))?;
-        if let Ok(profile) =
-            serde_json::from_str::<version_manager::LoaderProfile>(&profile_content)
-        {
-            println!("Using loader profile: main_class={}", profile.main_class);
-            main_class = profile.main_class;
-
-            // Prepend loader libraries to classpath (loader libs go first)
-            let mut loader_classpath = Vec::new();
-            for lib in &profile.libraries {
-                let lib_path = libraries_dir.join(maven_to_path(&lib.name));
-                if lib_path.exists() {
-                    loader_classpath.push(lib_path);
-                } else {
-                    println!(
-                        "Warning: loader library not found: {}",
-                        lib_path.display()
-                    );
-                }
-            }
-            loader_classpath.append(&mut classpath);
-            classpath = loader_classpath;
-
-            extra_jvm_args = profile.jvm_args;
-            extra_game_args = profile.game_args;
-        }
-    }
-
-    let config = VanillaLaunchConfig {
+    let options = LaunchOptions {
         java_path,
-        game_dir: game_dir.clone(),
-        assets_dir: launch_assets_dir,
-        classpath,
-        main_class,
-        version_name: version_id.to_string(),
-        asset_index: Some(version_data.asset_index.id),
-        resolution: Some(Resolution {
-            width: 1280,
-            height: 720,
-        }),
         memory: Some(MemorySettings {
-            min_megabytes: 1024,
-            max_megabytes: 4096,
+            min_megabytes: min_memory_mb,
+            max_megabytes: max_memory_mb,
         }),
         extra_jvm_args,
-        extra_game_args,
-        natives_dir: Some(natives_dir),
+        join_server: instance.join_server.clone(),
+        join_world: instance.join_world.clone(),
+        offline: instance.offline_mode,
+        display_server: instance.display_server,
+        env_vars: instance.env_vars.clone(),
     };
+    Ok(game_installer::build_command(&installation, options, &auth))
+}
 
-    // 7. Launch Auth
-    let auth = match &account.kind {
-        account_manager::AccountKind::Microsoft { uuid, username } => LaunchAuth::Microsoft {
-            username: username.clone(),
-            uuid: uuid.clone(),
-            access_token: access_token.to_string(),
-        },
-        account_manager::AccountKind::Offline { username, uuid } => LaunchAuth::Offline {
-            username: username.clone(),
-            uuid: uuid.clone(),
-        },
-    };
+/// `options.txt` settings that opt out of telemetry: the snooper (before
+/// 1.18) and optional telemetry (1.19.3+). Versions without one ignore it.
+/// The game has no JVM flag or environment variable for either, so they
+/// can't be passed with the launch arguments. There is no setting at all
+/// for the required telemetry of 1.18+; offline mode blocks it along with
+/// all other traffic.
+const TELEMETRY_OPTIONS: [(&str, &str); 2] = [
+    ("snooperEnabled", "false"),
+    ("telemetryOptInExtra", "false"),
+];
+
+/// Sets [`TELEMETRY_OPTIONS`] in the instance's `options.txt`. Every other
+/// line is kept as it is, and a file that already opts out isn't touched.
+async fn disable_telemetry(game_dir: &Path) -> Result<(), String> {
+    let path = game_dir.join("options.txt");
+    let current = fs::read_to_string(&path).await.unwrap_or_default();
+    let opted_out = TELEMETRY_OPTIONS.iter().all(|(key, value)| {
+        current
+            .lines()
+            .any(|line| line.split_once(':') == Some((key, value)))
+    });
+    if opted_out {
+        return Ok(());
+    }
 
-    Ok(config.build_command(&auth))
+    let mut lines: Vec<String> = current
+        .lines()
+        .filter(|line| {
+            let key = line.split_once(':').map_or(*line, |(key, _)| key);
+            !TELEMETRY_OPTIONS.iter().any(|(option, _)| *option == key)
+        })
+        .map(str::to_string)
+        .collect();
+    lines.extend(
+        TELEMETRY_OPTIONS
+            .iter()
+            .map(|(key, value)| format!("{}:{}", key, value)),
+    );
+    fs::write(&path, lines.join("\n") + "\n")
+        .await
+        .map_err(|e| format!("Failed to update options.txt: {}", e))
 }
 
-async fn fetch_manifest(
-    version_id: &str,
-    versions_dir: &Path,
-    json_path: &Path,
-) -> Result<VersionData, String> {
-    let manifest_url = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
-    println!("Fetching main manifest from {}", manifest_url);
-
-    // Async client
-    let client = reqwest::Client::new();
-    let resp = client
-        .get(manifest_url)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+/// `javaVersion.majorVersion` of `version_id`, if its JSON has already been
+/// downloaded into `game_dir`.
+pub fn required_java_major(game_dir: &Path, version_id: &str) -> Option<u32> {
+    let path = game_dir
+        .join("versions")
+        .join(version_id)
+        .join(format!("{}.json", version_id));
+    let version: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    version
+        .pointer("/javaVersion/majorVersion")?
+        .as_u64()
+        .map(|major| major as u32)
+}
 
-    if !resp.status().is_success() {
-        return Err(format!("Failed to fetch manifest: {}", resp.status()));
-    }
+/// Client log lines printed once the game is past JVM and mod loader startup
+/// and about to open its window: the session user (all versions) and the
+/// LWJGL backend (1.13+, in case the user line is filtered by a log config).
+const STARTED_MARKERS: [&str; 2] = ["Setting user:", "Backend library: LWJGL"];
 
-    let manifest_json: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+/// Whether a line of the game's output shows it actually started, as opposed
+/// to a JVM that spawned and died.
+pub fn is_started_line(line: &str) -> bool {
+    STARTED_MARKERS.iter().any(|marker| line.contains(marker))
+}
 
-    let version_url = manifest_json["versions"]
-        .as_array()
-        .ok_or("Invalid manifest format")?
-        .iter()
-        .find(|v| v["id"] == version_id)
-        .and_then(|v| v["url"].as_str())
-        .ok_or(format!("Version {} not found", version_id))?
-        .to_string();
+/// Where the JVM writes `.hprof` files for instances with
+/// [`InstanceMetadata::heap_dump_on_oom`] enabled.
+pub fn heap_dump_dir(game_dir: &Path) -> PathBuf {
+    game_dir.join("logs").join("heapdumps")
+}
 
-    println!("Found {} URL: {}", version_id, version_url);
+/// Newest heap dump written after `since`, i.e. by the session that just ended.
+pub fn find_heap_dump(game_dir: &Path, since: SystemTime) -> Option<PathBuf> {
+    std::fs::read_dir(heap_dump_dir(game_dir))
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "hprof"))
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .filter(|(modified, _)| *modified >= since)
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
 
-    let resp = client
-        .get(&version_url)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-    if !resp.status().is_success() {
-        return Err(format!("Failed to fetch version json: {}", resp.status()));
+/// Persists the missing-asset count so the instance list can offer a repair.
+fn record_missing_assets(instance: &InstanceMetadata, missing: usize) {
+    if instance.missing_assets == missing {
+        return;
+    }
+    if let Err(e) = InstanceManager::new().record_missing_assets(&instance.id, missing) {
+        println!("Failed to save instance {}: {}", instance.id, e);
     }
-    let content = resp.text().await.map_err(|e| e.to_string())?;
-
-    fs::create_dir_all(versions_dir.join(version_id))
-        .await
-        .map_err(|e| e.to_string())?;
-    fs::write(json_path, &content)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    serde_json::from_str(&content).map_err(|e| e.to_string())
 }
 
-pub async fn download_file(url: &str, path: &Path) -> Result<(), String> {
-    println!("Downloading {} to {:?}", url, path);
-    // Use reqwest async
-    let resp = reqwest::get(url)
-        .await
-        .map_err(|e| format!("Failed to GET {}: {}", url, e))?;
-    if !resp.status().is_success() {
-        return Err(format!("Download failed: {}", resp.status()));
+static DOWNLOADER: RwLock<Option<Downloader>> = RwLock::new(None);
+
+/// Shared downloader, configured from the `downloads` config section.
+pub fn downloader() -> Downloader {
+    if let Some(downloader) = DOWNLOADER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        return downloader.clone();
     }
-    let bytes = resp.bytes().await.map_err(|e| e.to_string())?;
+    DOWNLOADER
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(|| {
+            build_downloader(&FastmcConfig::load().unwrap_or_default().downloads)
+        })
+        .clone()
+}
 
-    fs::write(path, bytes)
-        .await
-        .map_err(|e| format!("Write failed: {}", e))?;
-    Ok(())
+/// Applies the `downloads` config section to the shared downloader and the
+/// mirror table, at startup and whenever Settings replaces the config.
+/// Downloads already in flight finish with the settings they started with.
+pub fn apply_download_settings(config: &DownloadsConfig) {
+    downloader::set_mirrors(
+        config
+            .mirrors
+            .iter()
+            .map(|rule| (rule.from.clone(), rule.to.clone()))
+            .collect(),
+    );
+    *DOWNLOADER.write().unwrap_or_else(|e| e.into_inner()) = Some(build_downloader(config));
 }
 
-pub fn maven_to_path(maven_id: &str) -> PathBuf {
-    let parts: Vec<&str> = maven_id.split(':').collect();
-    let domain = parts[0].replace('.', "/");
-    let name = parts[1];
-    let version = parts[2];
-    PathBuf::from(format!(
-        "{}/{}/{}/{}-{}.jar",
-        domain, name, version, name, version
-    ))
+fn build_downloader(config: &DownloadsConfig) -> Downloader {
+    let downloader = Downloader::new(RetryPolicy {
+        max_retries: config.max_retries,
+        initial_backoff: Duration::from_millis(config.initial_backoff_ms),
+        max_backoff: Duration::from_millis(config.max_backoff_ms),
+    })
+    .with_concurrency(config.max_concurrent);
+    match downloader.clone().with_ipv4_only(config.prefer_ipv4) {
+        Ok(downloader) => downloader,
+        Err(e) => {
+            println!("Failed to set up IPv4-only downloads, using both: {}", e);
+            downloader
+        }
+    }
 }
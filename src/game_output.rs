@@ -0,0 +1,49 @@
+//! Recent stdout/stderr lines of each instance's game, kept in memory for the
+//! Console screen. The output of a session stays until the next launch.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+/// Lines kept per instance; older ones are dropped first.
+const MAX_LINES: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub stream: Stream,
+    pub text: String,
+}
+
+fn buffers() -> MutexGuard<'static, HashMap<String, VecDeque<Line>>> {
+    static BUFFERS: OnceLock<Mutex<HashMap<String, VecDeque<Line>>>> = OnceLock::new();
+    BUFFERS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn push(instance_id: &str, stream: Stream, text: String) {
+    let mut buffers = buffers();
+    let lines = buffers.entry(instance_id.to_string()).or_default();
+    if lines.len() == MAX_LINES {
+        lines.pop_front();
+    }
+    lines.push_back(Line { stream, text });
+}
+
+pub fn clear(instance_id: &str) {
+    buffers().remove(instance_id);
+}
+
+/// Copy of the instance's lines, oldest first.
+pub fn lines(instance_id: &str) -> Vec<Line> {
+    buffers()
+        .get(instance_id)
+        .map(|lines| lines.iter().cloned().collect())
+        .unwrap_or_default()
+}
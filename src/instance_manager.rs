@@ -1,11 +1,21 @@
+use crate::activity::{self, Activity};
+use crate::data_migration;
+use crate::database;
+use crate::loader_installer;
+use crate::mod_metadata::{self, InstalledMod};
+use config_manager::FastmcConfig;
+use rusqlite::{Connection, params};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
+pub use game_installer::DisplayServer;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ModLoader {
     Vanilla,
@@ -13,6 +23,8 @@ pub enum ModLoader {
     Forge,
     NeoForge,
     Quilt,
+    /// Fabric for versions before 1.14, from Legacy Fabric's meta.
+    LegacyFabric,
 }
 
 impl fmt::Display for ModLoader {
@@ -23,18 +35,116 @@ impl fmt::Display for ModLoader {
             ModLoader::Forge => write!(f, "Forge"),
             ModLoader::NeoForge => write!(f, "NeoForge"),
             ModLoader::Quilt => write!(f, "Quilt"),
+            ModLoader::LegacyFabric => write!(f, "Legacy Fabric"),
         }
     }
 }
 
-pub const ALL_LOADERS: [ModLoader; 5] = [
+impl ModLoader {
+    /// Whether this loader can run mods built for `target`: Quilt and Legacy
+    /// Fabric load Fabric mods, and NeoForge still reads Forge's `mods.toml`.
+    pub fn runs_mods_for(&self, target: &ModLoader) -> bool {
+        self == target
+            || matches!(
+                (self, target),
+                (ModLoader::Quilt, ModLoader::Fabric)
+                    | (ModLoader::LegacyFabric, ModLoader::Fabric)
+                    | (ModLoader::NeoForge, ModLoader::Forge)
+            )
+    }
+}
+
+pub const ALL_LOADERS: [ModLoader; 6] = [
     ModLoader::Vanilla,
     ModLoader::Fabric,
+    ModLoader::LegacyFabric,
     ModLoader::Quilt,
     ModLoader::Forge,
     ModLoader::NeoForge,
 ];
 
+/// CPU architecture of the Java runtime an instance runs on. Only matters on
+/// ARM64 machines, which can also run x86_64 Java (under Rosetta 2 on macOS).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LaunchArch {
+    /// Native, except for versions without ARM64 natives when an x86_64
+    /// runtime is installed.
+    #[default]
+    Auto,
+    Native,
+    X86_64,
+}
+
+impl fmt::Display for LaunchArch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LaunchArch::Auto => write!(f, "Auto"),
+            LaunchArch::Native => write!(f, "ARM64"),
+            LaunchArch::X86_64 => write!(f, "x86_64 (Rosetta)"),
+        }
+    }
+}
+
+pub const ALL_LAUNCH_ARCHES: [LaunchArch; 3] =
+    [LaunchArch::Auto, LaunchArch::Native, LaunchArch::X86_64];
+
+pub const ALL_DISPLAY_SERVERS: [DisplayServer; 3] = [
+    DisplayServer::Auto,
+    DisplayServer::X11,
+    DisplayServer::Wayland,
+];
+
+/// Disk usage figures shown on instance cards. Walking `.minecraft` is slow
+/// for big modpacks, so these are cached in `instance.json` and refreshed in
+/// the background once they are older than [`STATS_TTL_SECS`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InstanceStats {
+    pub mod_count: u32,
+    pub size_bytes: u64,
+    pub computed_at: u64,
+}
+
+pub const STATS_TTL_SECS: u64 = 600;
+
+impl InstanceStats {
+    pub fn is_stale(&self) -> bool {
+        current_timestamp().saturating_sub(self.computed_at) > STATS_TTL_SECS
+    }
+}
+
+/// Problem found by [`InstanceManager::check_health`] that would make the
+/// next launch fail or misbehave.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthIssue {
+    /// The Java override points at a path that no longer exists.
+    MissingJava(String),
+    /// A loader is selected but its install never finished.
+    LoaderNotInstalled,
+    /// Mod jars in an instance without a loader.
+    ModsWithoutLoader(usize),
+    /// Mod jars built for a loader the instance doesn't run.
+    ForeignMods(Vec<String>),
+    /// The instance has been played but its version files are gone.
+    IncompleteInstall,
+}
+
+impl fmt::Display for HealthIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HealthIssue::MissingJava(path) => write!(f, "Java not found at {}", path),
+            HealthIssue::LoaderNotInstalled => write!(f, "Mod loader is not installed"),
+            HealthIssue::ModsWithoutLoader(1) => write!(f, "1 mod, but no mod loader"),
+            HealthIssue::ModsWithoutLoader(count) => {
+                write!(f, "{} mods, but no mod loader", count)
+            }
+            HealthIssue::ForeignMods(files) => {
+                write!(f, "Built for another loader: {}", files.join(", "))
+            }
+            HealthIssue::IncompleteInstall => write!(f, "Game files are incomplete"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstanceMetadata {
     pub id: String,
@@ -59,15 +169,83 @@ pub struct InstanceMetadata {
     #[serde(default)]
     pub auto_discover: Option<bool>,
 
+    /// Extra environment for the game process, e.g.
+    /// `MESA_GL_VERSION_OVERRIDE`. Set on top of the launcher's own environment.
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, String>,
+
     #[serde(default)]
     pub loader_installed: bool,
 
+    /// Singleplayer-only: launch with network access blocked and an offline session.
+    #[serde(default)]
+    pub offline_mode: bool,
+
+    /// Opt out of the game's optional telemetry before each launch.
+    #[serde(default)]
+    pub disable_telemetry: bool,
+
+    /// Debug: have the JVM write a heap dump to `logs/heapdumps` when the game
+    /// runs out of memory.
+    #[serde(default)]
+    pub heap_dump_on_oom: bool,
+
+    /// Launch the game again this many times when it crashes after opening
+    /// its window, for unattended sessions. 0 turns it off.
+    #[serde(default)]
+    pub restart_on_crash: u32,
+
+    /// Architecture override for ARM64 machines.
+    #[serde(default)]
+    pub launch_arch: LaunchArch,
+
+    /// Linux: display server hints passed to GLFW and SDL.
+    #[serde(default)]
+    pub display_server: DisplayServer,
+
+    /// Quick Play: server address (`host[:port]`) to join on launch.
+    #[serde(default)]
+    pub join_server: Option<String>,
+    /// Quick Play: world folder to open on launch (1.20+). Ignored when
+    /// `join_server` is set.
+    #[serde(default)]
+    pub join_world: Option<String>,
+
+    #[serde(default)]
+    pub stats: Option<InstanceStats>,
+
+    /// Assets still missing after the last launch's retries. Non-zero means the
+    /// instance is incomplete and the next launch has to repair it.
+    #[serde(default)]
+    pub missing_assets: usize,
+
     // Legacy field: read but never written back
     #[serde(default, skip_serializing)]
     memory_mb: Option<u32>,
 }
 
 impl InstanceMetadata {
+    /// A fresh instance with default settings.
+    pub fn new(id: String, name: String, game_version: String) -> Self {
+        Self {
+            id,
+            name,
+            game_version,
+            ..Default::default()
+        }
+    }
+
+    /// Id of the version JSON the instance launches: the installed loader's
+    /// profile, or the game version itself.
+    pub fn version_id(&self) -> String {
+        match &self.loader_version {
+            Some(loader_version) if self.loader_installed => {
+                loader_installer::version_id(&self.loader, &self.game_version, loader_version)
+            }
+            _ => self.game_version.clone(),
+        }
+    }
+
     /// Migrate legacy fields to new format.
     pub fn migrate(&mut self) {
         if let Some(mem) = self.memory_mb.take() {
@@ -98,12 +276,113 @@ impl Default for InstanceMetadata {
             max_memory_mb: None,
             jvm_args: None,
             auto_discover: None,
+            env_vars: BTreeMap::new(),
             loader_installed: false,
+            offline_mode: false,
+            disable_telemetry: false,
+            heap_dump_on_oom: false,
+            restart_on_crash: 0,
+            launch_arch: LaunchArch::Auto,
+            display_server: DisplayServer::Auto,
+            join_server: None,
+            join_world: None,
+            stats: None,
+            missing_assets: 0,
             memory_mb: None,
         }
     }
 }
 
+/// Parts of `.minecraft` a template carries over. Worlds, logs, screenshots
+/// and caches stay with the original instance.
+const TEMPLATE_ENTRIES: [&str; 6] = [
+    "mods",
+    "config",
+    "resourcepacks",
+    "shaderpacks",
+    "options.txt",
+    "servers.dat",
+];
+
+/// An instance's setup captured under a name, to stamp out new instances
+/// from, e.g. one client per server. Its files live next to `template.json`
+/// in `files/`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InstanceTemplate {
+    pub id: String,
+    pub name: String,
+    pub created: u64,
+
+    pub game_version: String,
+    pub loader: ModLoader,
+    pub loader_version: Option<String>,
+
+    pub java_path: Option<String>,
+    #[serde(default)]
+    pub min_memory_mb: Option<u32>,
+    #[serde(default)]
+    pub max_memory_mb: Option<u32>,
+    pub jvm_args: Option<Vec<String>>,
+    #[serde(default)]
+    pub auto_discover: Option<bool>,
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, String>,
+    #[serde(default)]
+    pub display_server: DisplayServer,
+
+    #[serde(default)]
+    pub offline_mode: bool,
+    #[serde(default)]
+    pub disable_telemetry: bool,
+    #[serde(default)]
+    pub join_server: Option<String>,
+}
+
+impl InstanceTemplate {
+    fn from_instance(name: String, meta: &InstanceMetadata) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name,
+            created: current_timestamp(),
+            game_version: meta.game_version.clone(),
+            loader: meta.loader.clone(),
+            loader_version: meta.loader_version.clone(),
+            java_path: meta.java_path.clone(),
+            min_memory_mb: meta.min_memory_mb,
+            max_memory_mb: meta.max_memory_mb,
+            jvm_args: meta.jvm_args.clone(),
+            auto_discover: meta.auto_discover,
+            env_vars: meta.env_vars.clone(),
+            display_server: meta.display_server,
+            offline_mode: meta.offline_mode,
+            disable_telemetry: meta.disable_telemetry,
+            join_server: meta.join_server.clone(),
+        }
+    }
+
+    /// A fresh instance with this template's settings. The loader still has to
+    /// be installed for it.
+    fn instantiate(&self, name: String) -> InstanceMetadata {
+        InstanceMetadata {
+            name,
+            game_version: self.game_version.clone(),
+            loader: self.loader.clone(),
+            loader_version: self.loader_version.clone(),
+            java_path: self.java_path.clone(),
+            min_memory_mb: self.min_memory_mb,
+            max_memory_mb: self.max_memory_mb,
+            jvm_args: self.jvm_args.clone(),
+            auto_discover: self.auto_discover,
+            env_vars: self.env_vars.clone(),
+            display_server: self.display_server,
+            offline_mode: self.offline_mode,
+            disable_telemetry: self.disable_telemetry,
+            join_server: self.join_server.clone(),
+            ..Default::default()
+        }
+    }
+}
+
 fn current_timestamp() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -114,13 +393,88 @@ fn current_timestamp() -> u64 {
 #[derive(Clone)]
 pub struct InstanceManager {
     base_dir: PathBuf,
+    templates_dir: PathBuf,
+}
+
+/// Where instances live: the config's `profiles.instances_dir`, or
+/// [`default_instances_dir`] when it isn't set.
+pub fn instances_dir() -> PathBuf {
+    FastmcConfig::load()
+        .unwrap_or_default()
+        .profiles
+        .instances_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(default_instances_dir)
+}
+
+pub fn default_instances_dir() -> PathBuf {
+    let dirs = directories::ProjectDirs::from("com", "fastmc", "fastmc").unwrap();
+    dirs.data_local_dir().join("instances")
+}
+
+/// What [`move_instances`] got done.
+#[derive(Debug, Clone, Default)]
+pub struct InstancesMove {
+    pub moved: usize,
+    /// Instances still in the old folder, with why they weren't moved.
+    pub left_behind: Vec<(String, String)>,
+}
+
+/// Moves every instance folder in `from` into `to`. An instance that can't be
+/// moved, for instance because its folder name is already taken in `to`,
+/// stays put and the others are moved anyway.
+pub fn move_instances(from: &Path, to: &Path) -> io::Result<InstancesMove> {
+    if to.starts_with(from) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the new folder is inside the current one",
+        ));
+    }
+    fs::create_dir_all(to)?;
+    let mut report = InstancesMove::default();
+    if !from.exists() {
+        return Ok(report);
+    }
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let target = to.join(entry.file_name());
+        if target.exists() {
+            let reason = format!("{} already exists", target.display());
+            report.left_behind.push((name, reason));
+            continue;
+        }
+        // Renaming fails across filesystems
+        if fs::rename(entry.path(), &target).is_err() {
+            if let Err(e) = data_migration::copy_dir(&entry.path(), &target) {
+                let _ = fs::remove_dir_all(&target);
+                report.left_behind.push((name, e.to_string()));
+                continue;
+            }
+            if let Err(e) = fs::remove_dir_all(entry.path()) {
+                println!(
+                    "Moved instance {} but failed to remove its old folder: {}",
+                    name, e
+                );
+            }
+        }
+        report.moved += 1;
+    }
+    Ok(report)
 }
 
 impl InstanceManager {
     pub fn new() -> Self {
         let dirs = directories::ProjectDirs::from("com", "fastmc", "fastmc").unwrap();
-        let base_dir = dirs.data_local_dir().join("instances");
-        Self { base_dir }
+        let base_dir = instances_dir();
+        let templates_dir = dirs.data_local_dir().join("templates");
+        Self {
+            base_dir,
+            templates_dir,
+        }
     }
 
     pub fn init(&self) -> std::io::Result<()> {
@@ -131,6 +485,32 @@ impl InstanceManager {
     }
 
     pub fn list_instances(&self) -> Vec<InstanceMetadata> {
+        let mut instances = self.read_instances();
+
+        // Stats live in the database when it is enabled
+        if database::enabled() {
+            match database::open().and_then(|conn| load_stats(&conn)) {
+                Ok(mut stats) => {
+                    for meta in &mut instances {
+                        meta.stats = stats.remove(&meta.id);
+                    }
+                }
+                Err(e) => println!("Failed to read instance stats: {}", e),
+            }
+        }
+
+        // Sort by last played (descending), then created
+        instances.sort_by(|a, b| {
+            b.last_played
+                .cmp(&a.last_played)
+                .then(b.created.cmp(&a.created))
+        });
+
+        instances
+    }
+
+    /// Every readable `instance.json`, as stored on disk.
+    fn read_instances(&self) -> Vec<InstanceMetadata> {
         let mut instances = Vec::new();
 
         if let Ok(entries) = fs::read_dir(&self.base_dir) {
@@ -151,13 +531,6 @@ impl InstanceManager {
             }
         }
 
-        // Sort by last played (descending), then created
-        instances.sort_by(|a, b| {
-            b.last_played
-                .cmp(&a.last_played)
-                .then(b.created.cmp(&a.created))
-        });
-
         instances
     }
 
@@ -183,7 +556,87 @@ impl InstanceManager {
 
         let json = serde_json::to_string_pretty(&metadata)?;
         fs::write(instance_dir.join("instance.json"), json)?;
+        activity::record(
+            &instance_dir,
+            Activity::Created {
+                version: metadata.game_version.clone(),
+            },
+        );
+
+        Ok(metadata)
+    }
+
+    /// Captures an instance's settings, mods and options as a template named
+    /// `name`, replacing any template of the same name.
+    pub fn save_as_template(&self, id: &str, name: String) -> io::Result<InstanceTemplate> {
+        let meta = self.load_instance(id)?;
+        for existing in self.list_templates() {
+            if existing.name == name {
+                fs::remove_dir_all(self.templates_dir.join(&existing.id))?;
+            }
+        }
+
+        let template = InstanceTemplate::from_instance(name, &meta);
+        let template_dir = self.templates_dir.join(&template.id);
+        let game_dir = self.base_dir.join(id).join(".minecraft");
+        let files_dir = template_dir.join("files");
+        fs::create_dir_all(&files_dir)?;
+        for entry in TEMPLATE_ENTRIES {
+            copy_entry(&game_dir.join(entry), &files_dir.join(entry))?;
+        }
+
+        let json = serde_json::to_string_pretty(&template)?;
+        fs::write(template_dir.join("template.json"), json)?;
+        Ok(template)
+    }
+
+    pub fn list_templates(&self) -> Vec<InstanceTemplate> {
+        let mut templates: Vec<InstanceTemplate> = fs::read_dir(&self.templates_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| {
+                        let content =
+                            fs::read_to_string(entry.path().join("template.json")).ok()?;
+                        serde_json::from_str(&content).ok()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        templates
+    }
+
+    /// Creates an instance named `name` from a saved template.
+    pub fn create_from_template(
+        &self,
+        name: String,
+        template_id: &str,
+    ) -> io::Result<InstanceMetadata> {
+        self.init()?;
+
+        let template_dir = self.templates_dir.join(template_id);
+        let content = fs::read_to_string(template_dir.join("template.json"))?;
+        let template: InstanceTemplate = serde_json::from_str(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
+        let metadata = template.instantiate(name);
+        let instance_dir = self.base_dir.join(&metadata.id);
+        let game_dir = instance_dir.join(".minecraft");
+        let files_dir = template_dir.join("files");
+        fs::create_dir_all(&game_dir)?;
+        for entry in TEMPLATE_ENTRIES {
+            copy_entry(&files_dir.join(entry), &game_dir.join(entry))?;
+        }
+
+        self.save_instance(&metadata)?;
+        activity::record(
+            &instance_dir,
+            Activity::Created {
+                version: metadata.game_version.clone(),
+            },
+        );
+        activity::record_mod_changes(&instance_dir);
         Ok(metadata)
     }
 
@@ -202,6 +655,139 @@ impl InstanceManager {
         Ok(())
     }
 
+    /// Records a play session: `last_played` becomes `started` and `secs` are
+    /// added to `total_time`. Re-reads the instance so concurrent edits survive.
+    pub fn record_session(&self, id: &str, started: u64, secs: u64) -> io::Result<()> {
+        let mut meta = self.load_instance(id)?;
+        meta.last_played = started;
+        meta.total_time += secs;
+        self.save_instance(&meta)
+    }
+
+    /// Stores how many assets the last install could not download. Re-reads
+    /// the instance so edits made while the launch was downloading survive.
+    pub fn record_missing_assets(&self, id: &str, missing: usize) -> io::Result<()> {
+        let mut meta = self.load_instance(id)?;
+        meta.missing_assets = missing;
+        self.save_instance(&meta)
+    }
+
+    /// Stores freshly computed stats, in the database when it is enabled and
+    /// in `instance.json` otherwise.
+    pub fn save_stats(&self, metadata: &InstanceMetadata) -> Result<(), String> {
+        let Some(stats) = &metadata.stats else {
+            return Ok(());
+        };
+        if database::enabled() {
+            return insert_stats(&database::open()?, &metadata.id, stats);
+        }
+        self.save_instance(metadata).map_err(|e| e.to_string())
+    }
+
+    /// Copies the stats cached in each `instance.json` into a freshly created
+    /// database.
+    pub fn import_stats(&self, conn: &Connection) -> Result<(), String> {
+        for meta in self.read_instances() {
+            if let Some(stats) = &meta.stats {
+                insert_stats(conn, &meta.id, stats)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Counts mod jars and sums the size of the instance's `.minecraft` dir.
+    pub fn compute_stats(&self, id: &str) -> io::Result<InstanceStats> {
+        let game_dir = self.base_dir.join(id).join(".minecraft");
+        let mod_count = fs::read_dir(game_dir.join("mods"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| e.path().extension().is_some_and(|ext| ext == "jar"))
+                    .count() as u32
+            })
+            .unwrap_or(0);
+
+        Ok(InstanceStats {
+            mod_count,
+            size_bytes: dir_size(&game_dir)?,
+            computed_at: current_timestamp(),
+        })
+    }
+
+    /// The instance's `.minecraft` directory.
+    pub fn game_dir(&self, id: &str) -> PathBuf {
+        self.base_dir.join(id).join(".minecraft")
+    }
+
+    /// Mod jars of the instance with their (cached) metadata.
+    pub fn list_mods(&self, id: &str) -> Result<Vec<InstalledMod>, String> {
+        activity::record_mod_changes(&self.base_dir.join(id));
+        mod_metadata::list_mods(&self.base_dir.join(id).join(".minecraft").join("mods"))
+    }
+
+    /// Looks for problems that can be detected without launching. Reads every
+    /// mod jar's manifest, so call it off the UI thread.
+    pub fn check_health(&self, metadata: &InstanceMetadata) -> Vec<HealthIssue> {
+        let mut issues = Vec::new();
+        if let Some(path) = metadata
+            .java_path
+            .as_ref()
+            .filter(|p| !Path::new(p).exists())
+        {
+            issues.push(HealthIssue::MissingJava(path.clone()));
+        }
+        if metadata.loader != ModLoader::Vanilla && !metadata.loader_installed {
+            issues.push(HealthIssue::LoaderNotInstalled);
+        }
+
+        let game_dir = self.game_dir(&metadata.id);
+        let jars: Vec<PathBuf> = fs::read_dir(game_dir.join("mods"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().is_some_and(|ext| ext == "jar"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if metadata.loader == ModLoader::Vanilla {
+            if !jars.is_empty() {
+                issues.push(HealthIssue::ModsWithoutLoader(jars.len()));
+            }
+        } else {
+            let mut foreign: Vec<String> = jars
+                .iter()
+                .filter(|jar| {
+                    let declared = mod_metadata::declared_loaders(jar);
+                    !declared.is_empty()
+                        && !declared.iter().any(|l| metadata.loader.runs_mods_for(l))
+                })
+                .filter_map(|jar| jar.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .collect();
+            if !foreign.is_empty() {
+                foreign.sort();
+                issues.push(HealthIssue::ForeignMods(foreign));
+            }
+        }
+
+        let version_dir = game_dir.join("versions").join(&metadata.game_version);
+        let version_files = ["json", "jar"].iter().all(|ext| {
+            version_dir
+                .join(format!("{}.{}", metadata.game_version, ext))
+                .exists()
+        });
+        if metadata.last_played > 0 && !version_files {
+            issues.push(HealthIssue::IncompleteInstall);
+        }
+        issues
+    }
+
+    /// The instance's activity log, oldest first.
+    pub fn activity(&self, id: &str) -> Vec<activity::Entry> {
+        activity::read(&self.base_dir.join(id))
+    }
+
     pub fn load_instance(&self, id: &str) -> io::Result<InstanceMetadata> {
         let json_path = self.base_dir.join(id).join("instance.json");
         let content = fs::read_to_string(&json_path)?;
@@ -211,3 +797,62 @@ impl InstanceManager {
         Ok(meta)
     }
 }
+
+fn insert_stats(conn: &Connection, id: &str, stats: &InstanceStats) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO instance_stats (instance_id, mod_count, size_bytes, computed_at)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![id, stats.mod_count, stats.size_bytes, stats.computed_at],
+    )
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+fn load_stats(conn: &Connection) -> Result<HashMap<String, InstanceStats>, String> {
+    let mut stmt = conn
+        .prepare("SELECT instance_id, mod_count, size_bytes, computed_at FROM instance_stats")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                InstanceStats {
+                    mod_count: row.get(1)?,
+                    size_bytes: row.get(2)?,
+                    computed_at: row.get(3)?,
+                },
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+/// Copies a file or a whole directory; a missing source is skipped.
+fn copy_entry(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)?.flatten() {
+            copy_entry(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else if from.is_file() {
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+fn dir_size(path: &Path) -> io::Result<u64> {
+    if !path.exists() {
+        return Ok(0);
+    }
+
+    let mut total = 0;
+    for entry in fs::read_dir(path)?.flatten() {
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            total += dir_size(&entry.path())?;
+        } else if file_type.is_file() {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
@@ -1,93 +1,251 @@
-use crate::game::{download_file, maven_to_path};
+use crate::game::{downloader, maven_to_path};
 use crate::instance_manager::ModLoader;
+use downloader::{CancellationToken, DownloadError, DownloadRequest};
 use std::path::Path;
-use version_manager::models::LoaderProfile;
+use version_manager::fabric::{FABRIC, FabricMeta, LEGACY_FABRIC};
+use version_manager::models::{ForgeLibEntry, LoaderProfile};
+
+/// Steps of [`install_loader`], in the order they happen.
+#[derive(Debug, Clone)]
+pub enum InstallEvent {
+    /// Fetching the loader profile, or the installer JAR for Forge/NeoForge.
+    DownloadingInstaller,
+    DownloadingLibraries,
+    /// `done` of `total` libraries bundled in the installer are extracted.
+    Extracting {
+        done: usize,
+        total: usize,
+    },
+    /// Processor `index` (1-based) of `total` is running.
+    RunningProcessor {
+        index: usize,
+        total: usize,
+    },
+    Done,
+}
+
+pub type EventSender = tokio::sync::mpsc::UnboundedSender<InstallEvent>;
 
+/// Installs `loader` into the instance, reporting each step on `events`.
+/// Cancelling `cancel` stops downloads and kills a running processor.
 pub async fn install_loader(
     instance_dir: &Path,
     game_version: &str,
     loader: ModLoader,
     loader_version: &str,
     java_path: Option<&Path>,
+    events: &EventSender,
+    cancel: &CancellationToken,
 ) -> Result<(), String> {
-    match loader {
+    let ctx = Context { events, cancel };
+    let profile = match loader {
         ModLoader::Vanilla => Err("Cannot install Vanilla as a loader".to_string()),
-        ModLoader::Fabric => install_fabric(instance_dir, game_version, loader_version).await,
-        ModLoader::Quilt => install_quilt(instance_dir, game_version, loader_version).await,
+        ModLoader::Fabric => {
+            install_fabric(&FABRIC, instance_dir, game_version, loader_version, &ctx).await
+        }
+        ModLoader::LegacyFabric => {
+            install_fabric(
+                &LEGACY_FABRIC,
+                instance_dir,
+                game_version,
+                loader_version,
+                &ctx,
+            )
+            .await
+        }
+        ModLoader::Quilt => install_quilt(instance_dir, game_version, loader_version, &ctx).await,
         ModLoader::Forge => {
-            install_forge(instance_dir, game_version, loader_version, java_path).await
+            install_forge(instance_dir, game_version, loader_version, java_path, &ctx).await
         }
         ModLoader::NeoForge => {
-            install_neoforge(instance_dir, game_version, loader_version, java_path).await
+            install_neoforge(instance_dir, game_version, loader_version, java_path, &ctx).await
+        }
+    }?;
+    let id = version_id(&loader, game_version, loader_version);
+    save_version_json(instance_dir, &id, game_version, &profile).await?;
+    let _ = events.send(InstallEvent::Done);
+    Ok(())
+}
+
+/// What every install step needs besides its own arguments.
+struct Context<'a> {
+    events: &'a EventSender,
+    cancel: &'a CancellationToken,
+}
+
+impl Context<'_> {
+    /// Reports the next step, or fails if the install was cancelled in the
+    /// meantime.
+    fn step(&self, event: InstallEvent) -> Result<(), String> {
+        if self.cancel.is_cancelled() {
+            return Err(DownloadError::Cancelled.to_string());
         }
+        let _ = self.events.send(event);
+        Ok(())
     }
 }
 
 async fn download_loader_libraries(
     libraries_dir: &Path,
     profile: &LoaderProfile,
+    cancel: &CancellationToken,
 ) -> Result<(), String> {
-    for lib in &profile.libraries {
+    let requests = profile
+        .libraries
+        .iter()
+        .map(|lib| {
+            let rel_path = maven_to_path(&lib.name);
+            let base_url = lib
+                .url
+                .as_deref()
+                .unwrap_or("https://libraries.minecraft.net/");
+            DownloadRequest::new(
+                format!("{}{}", base_url, rel_path.display()),
+                libraries_dir.join(&rel_path),
+            )
+            .with_sha1(lib.sha1.clone())
+        })
+        .collect();
+
+    downloader()
+        .download_all(requests, cancel, |_| {})
+        .await
+        .map_err(|e| e.to_string())?
+        .ensure_complete()
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Downloads Forge-style libraries. Entries with an artifact URL are required;
+/// the rest are guessed from `fallback_maven` and allowed to fail, since many
+/// of them are produced locally by the installer processors.
+async fn download_forge_libraries<'a>(
+    libraries: impl Iterator<Item = &'a ForgeLibEntry>,
+    libraries_dir: &Path,
+    fallback_maven: &str,
+    cancel: &CancellationToken,
+) -> Result<(), String> {
+    let mut required = vec![];
+    let mut optional = vec![];
+    for lib in libraries {
         let rel_path = maven_to_path(&lib.name);
         let lib_path = libraries_dir.join(&rel_path);
-
-        if lib_path.exists() {
-            continue;
+        match lib
+            .downloads
+            .as_ref()
+            .and_then(|d| d.artifact.as_ref())
+            .filter(|artifact| !artifact.url.is_empty())
+        {
+            Some(artifact) => required.push(
+                DownloadRequest::new(&artifact.url, lib_path).with_sha1(artifact.sha1.clone()),
+            ),
+            None => optional.push(DownloadRequest::new(
+                format!("{}{}", fallback_maven, rel_path.display()),
+                lib_path,
+            )),
         }
+    }
 
-        let base_url = lib
-            .url
-            .as_deref()
-            .unwrap_or("https://libraries.minecraft.net/");
-
-        let url = format!("{}{}", base_url, rel_path.display());
+    downloader()
+        .download_all(required, cancel, |_| {})
+        .await
+        .map_err(|e| e.to_string())?
+        .ensure_complete()
+        .map_err(|e| e.to_string())?;
+    downloader()
+        .download_all(optional, cancel, |_| {})
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
 
-        if let Some(parent) = lib_path.parent() {
-            tokio::fs::create_dir_all(parent)
-                .await
-                .map_err(|e| format!("Failed to create lib dir: {}", e))?;
+/// Id the official launcher gives `loader` at `loader_version` on top of
+/// `game_version`, and under which its version JSON is saved in `versions/`.
+pub fn version_id(loader: &ModLoader, game_version: &str, loader_version: &str) -> String {
+    match loader {
+        ModLoader::Vanilla => game_version.to_string(),
+        ModLoader::Fabric | ModLoader::LegacyFabric => {
+            format!("fabric-loader-{}-{}", loader_version, game_version)
         }
-
-        download_file(&url, &lib_path).await?;
+        ModLoader::Quilt => format!("quilt-loader-{}-{}", loader_version, game_version),
+        ModLoader::Forge => format!("{}-forge-{}", game_version, loader_version),
+        ModLoader::NeoForge => format!("neoforge-{}", loader_version),
     }
-    Ok(())
 }
 
-async fn save_loader_profile(instance_dir: &Path, profile: &LoaderProfile) -> Result<(), String> {
-    let path = instance_dir.join("loader_profile.json");
-    let json =
-        serde_json::to_string_pretty(profile).map_err(|e| format!("Failed to serialize: {}", e))?;
-    tokio::fs::write(&path, json)
+/// Version JSON layering `profile` over `game_version` through
+/// `inheritsFrom`, the way the official launcher stores loader installs.
+pub fn version_json(id: &str, game_version: &str, profile: &LoaderProfile) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "inheritsFrom": game_version,
+        "mainClass": profile.main_class,
+        "libraries": profile.libraries,
+        "arguments": {
+            "jvm": profile.jvm_args,
+            "game": profile.game_args,
+        },
+    })
+}
+
+async fn save_version_json(
+    instance_dir: &Path,
+    id: &str,
+    game_version: &str,
+    profile: &LoaderProfile,
+) -> Result<(), String> {
+    let dir = instance_dir.join(".minecraft").join("versions").join(id);
+    tokio::fs::create_dir_all(&dir)
         .await
-        .map_err(|e| format!("Failed to write loader_profile.json: {}", e))?;
-    Ok(())
+        .map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&version_json(id, game_version, profile))
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+    tokio::fs::write(dir.join(format!("{}.json", id)), json)
+        .await
+        .map_err(|e| format!("Failed to write the {} version JSON: {}", id, e))
 }
 
 // === Fabric ===
 
+/// Installs upstream Fabric or Legacy Fabric, depending on `meta`.
 async fn install_fabric(
+    meta: &FabricMeta,
     instance_dir: &Path,
     game_version: &str,
     loader_version: &str,
-) -> Result<(), String> {
+    ctx: &Context<'_>,
+) -> Result<LoaderProfile, String> {
     println!(
         "Installing Fabric {} for MC {}",
         loader_version, game_version
     );
 
-    let profile =
-        version_manager::fabric::fetch_fabric_profile(game_version, loader_version).await?;
+    ctx.step(InstallEvent::DownloadingInstaller)?;
+    let compatible = version_manager::fabric::fetch_compatible_loaders(meta, game_version)
+        .await
+        .map_err(|e| e.to_string())?;
+    let profile = if compatible.is_empty() {
+        // Meta lags behind brand-new releases; build the profile ourselves.
+        println!(
+            "Fabric meta doesn't list MC {} yet, using experimental profile",
+            game_version
+        );
+        version_manager::fabric::fetch_experimental_profile(meta, game_version, loader_version)
+            .await?
+    } else {
+        version_manager::fabric::fetch_fabric_profile(meta, game_version, loader_version).await?
+    };
 
     let libraries_dir = instance_dir.join(".minecraft").join("libraries");
     tokio::fs::create_dir_all(&libraries_dir)
         .await
         .map_err(|e| e.to_string())?;
 
-    download_loader_libraries(&libraries_dir, &profile).await?;
-    save_loader_profile(instance_dir, &profile).await?;
+    ctx.step(InstallEvent::DownloadingLibraries)?;
+    download_loader_libraries(&libraries_dir, &profile, ctx.cancel).await?;
 
     println!("Fabric installation complete");
-    Ok(())
+    Ok(profile)
 }
 
 // === Quilt ===
@@ -96,25 +254,26 @@ async fn install_quilt(
     instance_dir: &Path,
     game_version: &str,
     loader_version: &str,
-) -> Result<(), String> {
+    ctx: &Context<'_>,
+) -> Result<LoaderProfile, String> {
     println!(
         "Installing Quilt {} for MC {}",
         loader_version, game_version
     );
 
-    let profile =
-        version_manager::quilt::fetch_quilt_profile(game_version, loader_version).await?;
+    ctx.step(InstallEvent::DownloadingInstaller)?;
+    let profile = version_manager::quilt::fetch_quilt_profile(game_version, loader_version).await?;
 
     let libraries_dir = instance_dir.join(".minecraft").join("libraries");
     tokio::fs::create_dir_all(&libraries_dir)
         .await
         .map_err(|e| e.to_string())?;
 
-    download_loader_libraries(&libraries_dir, &profile).await?;
-    save_loader_profile(instance_dir, &profile).await?;
+    ctx.step(InstallEvent::DownloadingLibraries)?;
+    download_loader_libraries(&libraries_dir, &profile, ctx.cancel).await?;
 
     println!("Quilt installation complete");
-    Ok(())
+    Ok(profile)
 }
 
 // === Forge ===
@@ -124,25 +283,45 @@ async fn install_forge(
     game_version: &str,
     forge_version: &str,
     java_path: Option<&Path>,
-) -> Result<(), String> {
-    println!(
-        "Installing Forge {} for MC {}",
-        forge_version, game_version
-    );
+    ctx: &Context<'_>,
+) -> Result<LoaderProfile, String> {
+    println!("Installing Forge {} for MC {}", forge_version, game_version);
 
     let java = java_path.ok_or("Java path required for Forge installation")?;
     let libraries_dir = instance_dir.join(".minecraft").join("libraries");
     let installer_path = instance_dir.join("forge-installer.jar");
 
     // 1. Download installer
-    version_manager::forge::download_forge_installer(game_version, forge_version, &installer_path)
-        .await?;
+    ctx.step(InstallEvent::DownloadingInstaller)?;
+    version_manager::forge::download_forge_installer(
+        &downloader(),
+        game_version,
+        forge_version,
+        &installer_path,
+        ctx.cancel,
+    )
+    .await?;
+
+    let format = version_manager::forge::installer_format(&installer_path)?;
+    if format == version_manager::forge::InstallerFormat::Legacy {
+        let profile = install_legacy_forge(instance_dir, &installer_path, ctx).await?;
+        let _ = tokio::fs::remove_file(&installer_path).await;
+        println!("Forge installation complete");
+        return Ok(profile);
+    }
 
     // 2. Extract install_profile.json, version.json, and maven/ libs
     let libraries_dir_clone = libraries_dir.clone();
     let installer_path_clone = installer_path.clone();
+    let events = ctx.events.clone();
     let (install_profile, version_json) = tokio::task::spawn_blocking(move || {
-        version_manager::forge::extract_forge_installer(&installer_path_clone, &libraries_dir_clone)
+        version_manager::forge::extract_forge_installer(
+            &installer_path_clone,
+            &libraries_dir_clone,
+            |done, total| {
+                let _ = events.send(InstallEvent::Extracting { done, total });
+            },
+        )
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -152,59 +331,17 @@ async fn install_forge(
         .await
         .map_err(|e| e.to_string())?;
 
-    // Download install_profile libraries
-    for lib in &install_profile.libraries {
-        let lib_path = libraries_dir.join(maven_to_path(&lib.name));
-        if lib_path.exists() {
-            continue;
-        }
-        if let Some(parent) = lib_path.parent() {
-            tokio::fs::create_dir_all(parent)
-                .await
-                .map_err(|e| e.to_string())?;
-        }
-
-        if let Some(ref downloads) = lib.downloads {
-            if let Some(ref artifact) = downloads.artifact {
-                if !artifact.url.is_empty() {
-                    download_file(&artifact.url, &lib_path).await?;
-                    continue;
-                }
-            }
-        }
-        // Fallback: try Forge maven
-        let url = format!(
-            "https://maven.minecraftforge.net/{}",
-            maven_to_path(&lib.name).display()
-        );
-        let _ = download_file(&url, &lib_path).await;
-    }
-
-    // Download version_json libraries
-    for lib in &version_json.libraries {
-        let lib_path = libraries_dir.join(maven_to_path(&lib.name));
-        if lib_path.exists() {
-            continue;
-        }
-        if let Some(parent) = lib_path.parent() {
-            tokio::fs::create_dir_all(parent)
-                .await
-                .map_err(|e| e.to_string())?;
-        }
-        if let Some(ref downloads) = lib.downloads {
-            if let Some(ref artifact) = downloads.artifact {
-                if !artifact.url.is_empty() {
-                    download_file(&artifact.url, &lib_path).await?;
-                    continue;
-                }
-            }
-        }
-        let url = format!(
-            "https://maven.minecraftforge.net/{}",
-            maven_to_path(&lib.name).display()
-        );
-        let _ = download_file(&url, &lib_path).await;
-    }
+    ctx.step(InstallEvent::DownloadingLibraries)?;
+    download_forge_libraries(
+        install_profile
+            .libraries
+            .iter()
+            .chain(version_json.libraries.iter()),
+        &libraries_dir,
+        "https://maven.minecraftforge.net/",
+        ctx.cancel,
+    )
+    .await?;
 
     // 4. Run processors (client-side only)
     run_forge_processors(
@@ -213,18 +350,49 @@ async fn install_forge(
         instance_dir,
         game_version,
         java,
+        ctx,
     )
     .await?;
 
-    // 5. Build LoaderProfile from version_json
+    // 5. Build the profile from version_json
     let profile = forge_version_to_loader_profile(&version_json);
-    save_loader_profile(instance_dir, &profile).await?;
 
     // 6. Cleanup installer JAR
     let _ = tokio::fs::remove_file(&installer_path).await;
 
     println!("Forge installation complete");
-    Ok(())
+    Ok(profile)
+}
+
+/// Installs Forge from a 1.12.2-and-older installer: the universal jar goes
+/// on the classpath ahead of the client and LaunchWrapper starts the game
+/// through Forge's tweak class, so there are no processors to run.
+async fn install_legacy_forge(
+    instance_dir: &Path,
+    installer_path: &Path,
+    ctx: &Context<'_>,
+) -> Result<LoaderProfile, String> {
+    let libraries_dir = instance_dir.join(".minecraft").join("libraries");
+    let libraries_dir_clone = libraries_dir.clone();
+    let installer_path_clone = installer_path.to_path_buf();
+    let events = ctx.events.clone();
+    let install_profile = tokio::task::spawn_blocking(move || {
+        version_manager::forge::extract_legacy_forge_installer(
+            &installer_path_clone,
+            &libraries_dir_clone,
+            |done, total| {
+                let _ = events.send(InstallEvent::Extracting { done, total });
+            },
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    // The universal jar was just extracted, so only the rest is fetched
+    ctx.step(InstallEvent::DownloadingLibraries)?;
+    let profile = legacy_forge_to_loader_profile(&install_profile);
+    download_loader_libraries(&libraries_dir, &profile, ctx.cancel).await?;
+    Ok(profile)
 }
 
 // === NeoForge ===
@@ -234,7 +402,8 @@ async fn install_neoforge(
     game_version: &str,
     neoforge_version: &str,
     java_path: Option<&Path>,
-) -> Result<(), String> {
+    ctx: &Context<'_>,
+) -> Result<LoaderProfile, String> {
     println!(
         "Installing NeoForge {} for MC {}",
         neoforge_version, game_version
@@ -245,14 +414,27 @@ async fn install_neoforge(
     let installer_path = instance_dir.join("neoforge-installer.jar");
 
     // 1. Download installer
-    version_manager::neoforge::download_neoforge_installer(neoforge_version, &installer_path)
-        .await?;
+    ctx.step(InstallEvent::DownloadingInstaller)?;
+    version_manager::neoforge::download_neoforge_installer(
+        &downloader(),
+        neoforge_version,
+        &installer_path,
+        ctx.cancel,
+    )
+    .await?;
 
     // 2. Extract — reuse Forge extraction (same format)
     let libraries_dir_clone = libraries_dir.clone();
     let installer_path_clone = installer_path.clone();
+    let events = ctx.events.clone();
     let (install_profile, version_json) = tokio::task::spawn_blocking(move || {
-        version_manager::forge::extract_forge_installer(&installer_path_clone, &libraries_dir_clone)
+        version_manager::forge::extract_forge_installer(
+            &installer_path_clone,
+            &libraries_dir_clone,
+            |done, total| {
+                let _ = events.send(InstallEvent::Extracting { done, total });
+            },
+        )
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -262,35 +444,17 @@ async fn install_neoforge(
         .await
         .map_err(|e| e.to_string())?;
 
-    for lib in install_profile
-        .libraries
-        .iter()
-        .chain(version_json.libraries.iter())
-    {
-        let lib_path = libraries_dir.join(maven_to_path(&lib.name));
-        if lib_path.exists() {
-            continue;
-        }
-        if let Some(parent) = lib_path.parent() {
-            tokio::fs::create_dir_all(parent)
-                .await
-                .map_err(|e| e.to_string())?;
-        }
-        if let Some(ref downloads) = lib.downloads {
-            if let Some(ref artifact) = downloads.artifact {
-                if !artifact.url.is_empty() {
-                    download_file(&artifact.url, &lib_path).await?;
-                    continue;
-                }
-            }
-        }
-        // Fallback: NeoForge maven
-        let url = format!(
-            "https://maven.neoforged.net/releases/{}",
-            maven_to_path(&lib.name).display()
-        );
-        let _ = download_file(&url, &lib_path).await;
-    }
+    ctx.step(InstallEvent::DownloadingLibraries)?;
+    download_forge_libraries(
+        install_profile
+            .libraries
+            .iter()
+            .chain(version_json.libraries.iter()),
+        &libraries_dir,
+        "https://maven.neoforged.net/releases/",
+        ctx.cancel,
+    )
+    .await?;
 
     // 4. Run processors
     run_forge_processors(
@@ -299,18 +463,18 @@ async fn install_neoforge(
         instance_dir,
         game_version,
         java,
+        ctx,
     )
     .await?;
 
-    // 5. Build LoaderProfile
+    // 5. Build the profile
     let profile = forge_version_to_loader_profile(&version_json);
-    save_loader_profile(instance_dir, &profile).await?;
 
     // 6. Cleanup
     let _ = tokio::fs::remove_file(&installer_path).await;
 
     println!("NeoForge installation complete");
-    Ok(())
+    Ok(profile)
 }
 
 // === Forge processor pipeline ===
@@ -321,6 +485,7 @@ async fn run_forge_processors(
     instance_dir: &Path,
     game_version: &str,
     java_path: &Path,
+    ctx: &Context<'_>,
 ) -> Result<(), String> {
     let game_dir = instance_dir.join(".minecraft");
     let versions_dir = game_dir.join("versions");
@@ -367,12 +532,36 @@ async fn run_forge_processors(
         data_map.insert(key.clone(), resolved);
     }
 
-    for processor in &install_profile.processors {
-        // Skip server-side-only processors
-        if let Some(ref sides) = processor.sides {
-            if !sides.iter().any(|s| s == "client") {
-                continue;
-            }
+    // Server-side-only processors are skipped
+    let processors: Vec<_> = install_profile
+        .processors
+        .iter()
+        .filter(|processor| {
+            processor
+                .sides
+                .as_ref()
+                .is_none_or(|sides| sides.iter().any(|s| s == "client"))
+        })
+        .collect();
+    let total = processors.len();
+
+    for (i, processor) in processors.into_iter().enumerate() {
+        ctx.step(InstallEvent::RunningProcessor {
+            index: i + 1,
+            total,
+        })?;
+
+        // A reinstall or repair finds most outputs already in place
+        if !processor.outputs.is_empty()
+            && mismatched_output(&processor.outputs, &data_map, libraries_dir)
+                .await
+                .is_none()
+        {
+            println!(
+                "Skipping processor {}: outputs are up to date",
+                processor.jar
+            );
+            continue;
         }
 
         // Build classpath for this processor
@@ -414,9 +603,13 @@ async fn run_forge_processors(
             .arg(&classpath)
             .arg(&main_class)
             .args(&resolved_args)
-            .output()
-            .await
-            .map_err(|e| format!("Failed to run processor {}: {}", main_class, e))?;
+            .kill_on_drop(true)
+            .output();
+        let output = tokio::select! {
+            output = output => output
+                .map_err(|e| format!("Failed to run processor {}: {}", main_class, e))?,
+            _ = ctx.cancel.cancelled() => return Err(DownloadError::Cancelled.to_string()),
+        };
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -426,11 +619,38 @@ async fn run_forge_processors(
                 main_class, stdout, stderr
             ));
         }
+
+        // A bad patch would otherwise only surface as a crash at launch
+        if let Some(path) = mismatched_output(&processor.outputs, &data_map, libraries_dir).await {
+            return Err(format!(
+                "Forge processor {} produced {} with an unexpected checksum; reinstall the loader to retry",
+                main_class, path
+            ));
+        }
     }
 
     Ok(())
 }
 
+/// First output a processor declares that is missing or doesn't match the
+/// SHA1 expected for it, usually one of the data map's `{*_SHA}` entries.
+async fn mismatched_output(
+    outputs: &std::collections::HashMap<String, String>,
+    data_map: &std::collections::HashMap<String, String>,
+    libraries_dir: &Path,
+) -> Option<String> {
+    for (path, sha1) in outputs {
+        let path = resolve_forge_token(path, data_map, libraries_dir);
+        let sha1 = resolve_forge_token(sha1, data_map, libraries_dir);
+        // Hashes are quoted literals in the data map
+        let sha1 = sha1.trim_matches('\'');
+        if !downloader::file_matches_sha1(Path::new(&path), sha1).await {
+            return Some(path);
+        }
+    }
+    None
+}
+
 fn resolve_forge_token(
     token: &str,
     data_map: &std::collections::HashMap<String, String>,
@@ -450,6 +670,36 @@ fn resolve_forge_token(
     }
 }
 
+fn legacy_forge_to_loader_profile(
+    install_profile: &version_manager::models::LegacyForgeInstallProfile,
+) -> LoaderProfile {
+    let version_info = &install_profile.version_info;
+    let libraries = version_info
+        .libraries
+        .iter()
+        .filter(|lib| lib.natives.is_none())
+        .map(|lib| version_manager::models::LoaderLibrary {
+            name: lib.name.clone(),
+            // Forge's old Maven host now redirects to the new one
+            url: lib.url.as_ref().map(|url| {
+                url.replace(
+                    "files.minecraftforge.net/maven/",
+                    "maven.minecraftforge.net/",
+                )
+                .replace("http://", "https://")
+            }),
+            sha1: None,
+        })
+        .collect();
+
+    LoaderProfile {
+        main_class: version_info.main_class.clone(),
+        libraries,
+        jvm_args: vec![],
+        game_args: version_manager::forge::tweak_class_args(&version_info.minecraft_arguments),
+    }
+}
+
 fn forge_version_to_loader_profile(
     version_json: &version_manager::models::ForgeVersionJson,
 ) -> LoaderProfile {
@@ -471,6 +721,11 @@ fn forge_version_to_loader_profile(
                         .unwrap_or("https://maven.minecraftforge.net/")
                         .to_string()
                 }),
+            sha1: lib
+                .downloads
+                .as_ref()
+                .and_then(|d| d.artifact.as_ref())
+                .and_then(|a| a.sha1.clone()),
         })
         .collect();
 
@@ -493,6 +748,12 @@ fn forge_version_to_loader_profile(
             }
         }
     }
+    // 1.12.2 builds of the new installer still launch through LaunchWrapper
+    if let Some(minecraft_arguments) = &version_json.minecraft_arguments {
+        game_args.extend(version_manager::forge::tweak_class_args(
+            minecraft_arguments,
+        ));
+    }
 
     LoaderProfile {
         main_class: version_json.main_class.clone(),
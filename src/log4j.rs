@@ -0,0 +1,113 @@
+//! Log4Shell (CVE-2021-44228) mitigation. Minecraft 1.7 through 1.18.0 ship a
+//! log4j that resolves `${jndi:...}` lookups in logged chat messages.
+
+use downloader::{CancellationToken, DownloadRequest, Downloader};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use version_manager::LoggingConfig;
+
+const PATCHED_CONFIG_FILE: &str = "fastmc-log4j2-patched.xml";
+
+/// Replaces the game's own log4j config on log4j before 2.10, which has no
+/// switch for lookups. Logs the same way vanilla does, but drops any message
+/// that contains a lookup before it is formatted.
+const PATCHED_CONFIG: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Configuration status="WARN">
+    <Appenders>
+        <Console name="SysOut" target="SYSTEM_OUT">
+            <PatternLayout pattern="[%d{HH:mm:ss}] [%t/%level]: %msg%n" />
+        </Console>
+        <RollingRandomAccessFile name="File" fileName="logs/latest.log" filePattern="logs/%d{yyyy-MM-dd}-%i.log.gz">
+            <PatternLayout pattern="[%d{HH:mm:ss}] [%t/%level]: %msg%n" />
+            <Policies>
+                <TimeBasedTriggeringPolicy />
+                <OnStartupTriggeringPolicy />
+            </Policies>
+        </RollingRandomAccessFile>
+    </Appenders>
+    <Loggers>
+        <Root level="info">
+            <filters>
+                <MarkerFilter marker="NETWORK_PACKETS" onMatch="DENY" onMismatch="NEUTRAL" />
+                <RegexFilter regex="(?s).*\$\{[^}]*\}.*" onMatch="DENY" onMismatch="NEUTRAL" />
+            </filters>
+            <AppenderRef ref="SysOut" />
+            <AppenderRef ref="File" />
+        </Root>
+    </Loggers>
+</Configuration>
+"#;
+
+/// JVM arguments that close the lookup hole in the game's log4j, if it has
+/// one. The config `logging` names comes first: Mojang reissued those with
+/// lookups filtered out. Without one, older log4j gets a patched config of
+/// our own, written to `assets_dir/log_configs`; 2.10 to 2.14 can also turn
+/// lookups off with a flag.
+pub async fn mitigation_args(
+    downloader: &Downloader,
+    logging: Option<&LoggingConfig>,
+    library_names: &HashSet<&str>,
+    assets_dir: &Path,
+    cancel: &CancellationToken,
+) -> Result<Vec<String>, String> {
+    let dir = assets_dir.join("log_configs");
+    let version = log4j_version(library_names);
+    let mut args = vec![];
+    if matches!(version, Some((2, minor)) if (10..15).contains(&minor)) {
+        args.push("-Dlog4j2.formatMsgNoLookups=true".to_string());
+    }
+
+    if let Some(config) = logging {
+        match download_config(downloader, config, &dir, cancel).await {
+            Ok(path) => {
+                let path = path.display().to_string();
+                args.push(config.argument.replace("${path}", &path));
+                return Ok(args);
+            }
+            Err(e) => println!("Using the patched log4j config instead: {}", e),
+        }
+    }
+
+    if matches!(version, Some((2, minor)) if minor < 10) {
+        fs::create_dir_all(&dir).await.map_err(|e| e.to_string())?;
+        let path = dir.join(PATCHED_CONFIG_FILE);
+        fs::write(&path, PATCHED_CONFIG)
+            .await
+            .map_err(|e| e.to_string())?;
+        args.push(format!("-Dlog4j.configurationFile={}", path.display()));
+    }
+    Ok(args)
+}
+
+/// Downloads the config `logging` names into `dir`, checked against its SHA1.
+async fn download_config(
+    downloader: &Downloader,
+    logging: &LoggingConfig,
+    dir: &Path,
+    cancel: &CancellationToken,
+) -> Result<PathBuf, String> {
+    let id = &logging.file.id;
+    if Path::new(id).file_name() != Some(id.as_ref()) {
+        return Err(format!("Invalid log config name: {}", id));
+    }
+    let path = dir.join(id);
+    let request =
+        DownloadRequest::new(&logging.file.url, &path).with_sha1(Some(logging.file.sha1.clone()));
+    downloader
+        .ensure(&request, cancel)
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", id, e))?;
+    Ok(path)
+}
+
+/// `(major, minor)` of the log4j-core library; `2.0-beta9` reads as `(2, 0)`.
+pub(crate) fn log4j_version(library_names: &HashSet<&str>) -> Option<(u32, u32)> {
+    let version = library_names
+        .iter()
+        .find_map(|name| name.strip_prefix("org.apache.logging.log4j:log4j-core:"))?;
+    let mut parts = version
+        .split(['.', '-'])
+        .map(|part| part.parse::<u32>().unwrap_or(0));
+    Some((parts.next()?, parts.next()?))
+}
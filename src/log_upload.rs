@@ -0,0 +1,149 @@
+//! Shares game logs and crash reports through mclo.gs. Account names, UUIDs,
+//! tokens and the user's home directory are scrubbed before anything leaves
+//! the machine.
+
+use account_manager::{AccountKind, AccountStore};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const API_URL: &str = "https://api.mclo.gs/1/log";
+/// Long enough for a few megabytes of log on a slow uplink.
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+struct UploadResponse {
+    success: bool,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Identifiers of the signed-in account, removed from uploads.
+#[derive(Debug, Clone, Default)]
+pub struct Secrets {
+    pub username: Option<String>,
+    pub uuid: Option<String>,
+    pub tokens: Vec<String>,
+}
+
+impl Secrets {
+    /// Identifiers and tokens of the active account.
+    pub fn of_active(store: &AccountStore) -> Self {
+        let Some(account) = store
+            .active
+            .and_then(|id| store.accounts.iter().find(|a| a.id == id))
+        else {
+            return Self::default();
+        };
+        let (username, uuid) = match &account.kind {
+            AccountKind::Offline { username, uuid } => (username, uuid),
+            AccountKind::Microsoft { username, uuid } => (username, uuid),
+            AccountKind::Custom { username, uuid, .. } => (username, uuid),
+        };
+        let tokens = match &account.kind {
+            AccountKind::Custom { .. } => store
+                .custom_tokens(&account.id)
+                .ok()
+                .flatten()
+                .map(|s| vec![s.access_token, s.client_token]),
+            _ => store
+                .microsoft_tokens(&account.id)
+                .ok()
+                .flatten()
+                .map(|s| vec![s.access_token, s.refresh_token]),
+        }
+        .unwrap_or_default();
+        Self {
+            username: Some(username.clone()),
+            uuid: Some(uuid.clone()),
+            tokens,
+        }
+    }
+}
+
+/// Minecraft's own log of the last session.
+pub fn latest_log(game_dir: &Path) -> PathBuf {
+    game_dir.join("logs").join("latest.log")
+}
+
+/// Uploads the file at `path` after scrubbing it and returns the link to
+/// share.
+pub async fn upload_file(path: &Path, secrets: &Secrets) -> Result<String, String> {
+    let content = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let content = String::from_utf8_lossy(&content);
+    if content.trim().is_empty() {
+        return Err("The log is empty".to_string());
+    }
+    upload(scrub(&content, secrets)).await
+}
+
+async fn upload(content: String) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(UPLOAD_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Upload failed: {}", e))?;
+    let response: UploadResponse = client
+        .post(API_URL)
+        .form(&[("content", content)])
+        .send()
+        .await
+        .map_err(|e| format!("Upload failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected response from mclo.gs: {}", e))?;
+    match response {
+        UploadResponse {
+            success: true,
+            url: Some(url),
+            ..
+        } => Ok(url),
+        UploadResponse { error, .. } => Err(format!(
+            "mclo.gs rejected the log: {}",
+            error.unwrap_or_else(|| "unknown error".to_string())
+        )),
+    }
+}
+
+/// Replaces the account's identifiers, anything passed as `--accessToken`
+/// and the home directory.
+pub fn scrub(content: &str, secrets: &Secrets) -> String {
+    let mut out = content
+        .lines()
+        .map(scrub_token_args)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    for token in secrets.tokens.iter().filter(|token| !token.is_empty()) {
+        out = out.replace(token.as_str(), "[token]");
+    }
+    if let Some(uuid) = secrets.uuid.as_deref().filter(|uuid| !uuid.is_empty()) {
+        // Logs use both the dashed and the plain form
+        out = out.replace(uuid, "[uuid]");
+        out = out.replace(&uuid.replace('-', ""), "[uuid]");
+    }
+    if let Some(home) = directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf()) {
+        let home = home.to_string_lossy();
+        if home.len() > 1 {
+            out = out.replace(home.as_ref(), "~");
+        }
+    }
+    // Short names would match ordinary words
+    if let Some(name) = secrets.username.as_deref().filter(|name| name.len() >= 3) {
+        out = out.replace(name, "[player]");
+    }
+    out
+}
+
+pub(crate) fn scrub_token_args(line: &str) -> String {
+    let mut words: Vec<&str> = line.split(' ').collect();
+    for i in 1..words.len() {
+        if matches!(words[i - 1], "--accessToken" | "--session") {
+            words[i] = "[token]";
+        }
+    }
+    words.join(" ")
+}
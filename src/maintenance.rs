@@ -0,0 +1,350 @@
+//! Background upkeep: each job runs at launcher start or at most once a day,
+//! and can be switched off in Settings.
+
+use crate::database;
+use crate::instance_manager;
+use account_manager::AccountStore;
+use config_manager::MaintenanceConfig;
+use directories::ProjectDirs;
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const STATE_FILE: &str = "maintenance.json";
+const DAY_SECS: u64 = 60 * 60 * 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Job {
+    RefreshManifest,
+    PruneLogs,
+    GcAssets,
+    RefreshAvatars,
+}
+
+pub const ALL_JOBS: [Job; 4] = [
+    Job::RefreshManifest,
+    Job::PruneLogs,
+    Job::GcAssets,
+    Job::RefreshAvatars,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    EveryStart,
+    Daily,
+}
+
+impl Job {
+    fn key(self) -> &'static str {
+        match self {
+            Job::RefreshManifest => "refresh_manifest",
+            Job::PruneLogs => "prune_logs",
+            Job::GcAssets => "gc_assets",
+            Job::RefreshAvatars => "refresh_avatars",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Job::RefreshManifest => "Refresh version list",
+            Job::PruneLogs => "Prune old logs",
+            Job::GcAssets => "Clean up unused assets",
+            Job::RefreshAvatars => "Refresh account avatars",
+        }
+    }
+
+    pub fn schedule(self) -> Schedule {
+        match self {
+            Job::RefreshManifest => Schedule::EveryStart,
+            Job::PruneLogs | Job::GcAssets | Job::RefreshAvatars => Schedule::Daily,
+        }
+    }
+
+    pub fn is_enabled(self, config: &MaintenanceConfig) -> bool {
+        match self {
+            Job::RefreshManifest => config.refresh_manifest,
+            Job::PruneLogs => config.prune_logs,
+            Job::GcAssets => config.gc_assets,
+            Job::RefreshAvatars => config.refresh_avatars,
+        }
+    }
+
+    pub fn set_enabled(self, config: &mut MaintenanceConfig, enabled: bool) {
+        let flag = match self {
+            Job::RefreshManifest => &mut config.refresh_manifest,
+            Job::PruneLogs => &mut config.prune_logs,
+            Job::GcAssets => &mut config.gc_assets,
+            Job::RefreshAvatars => &mut config.refresh_avatars,
+        };
+        *flag = enabled;
+    }
+}
+
+/// Unix time of each job's last successful run, keyed by [`Job::key`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct State {
+    last_run: HashMap<String, u64>,
+}
+
+impl State {
+    fn load() -> Self {
+        if database::enabled() {
+            return database::open()
+                .and_then(|conn| Self::load_db(&conn))
+                .unwrap_or_else(|e| {
+                    println!("Failed to read maintenance history: {}", e);
+                    Self::default()
+                });
+        }
+        Self::load_json()
+    }
+
+    fn load_json() -> Self {
+        state_file()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    fn load_db(conn: &Connection) -> Result<Self, String> {
+        let mut stmt = conn
+            .prepare("SELECT job, MAX(ran_at) FROM task_history WHERE ok = 1 GROUP BY job")
+            .map_err(|e| e.to_string())?;
+        let last_run = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
+        Ok(Self { last_run })
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = state_file().ok_or("Data directory unavailable")?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| e.to_string())
+    }
+}
+
+fn state_file() -> Option<PathBuf> {
+    let dirs = ProjectDirs::from("com", "fastmc", "fastmc")?;
+    Some(dirs.data_local_dir().join(STATE_FILE))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Unix time of the job's last successful run; zero if it never ran.
+pub fn last_run(job: Job) -> u64 {
+    State::load()
+        .last_run
+        .get(job.key())
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Enabled jobs that should run now. `at_start` is true once per launcher
+/// start, when [`Schedule::EveryStart`] jobs are due as well.
+pub fn due_jobs(config: &MaintenanceConfig, at_start: bool) -> Vec<Job> {
+    let state = State::load();
+    let now = now();
+    ALL_JOBS
+        .into_iter()
+        .filter(|job| job.is_enabled(config))
+        .filter(|job| match job.schedule() {
+            Schedule::EveryStart => at_start,
+            Schedule::Daily => {
+                let last = state.last_run.get(job.key()).copied().unwrap_or_default();
+                now.saturating_sub(last) >= DAY_SECS
+            }
+        })
+        .collect()
+}
+
+/// Copies `maintenance.json` into a freshly created database.
+pub fn import_state(conn: &Connection) -> Result<(), String> {
+    for (job, ran_at) in State::load_json().last_run {
+        conn.execute(
+            "INSERT INTO task_history (job, ran_at, ok) VALUES (?1, ?2, 1)",
+            params![job, ran_at],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Runs one job and records it; the `Ok` string summarizes what it did. With
+/// the database enabled failed runs are kept in the history as well.
+pub async fn run(job: Job, config: MaintenanceConfig) -> Result<String, String> {
+    let result = run_job(job, config).await;
+    if database::enabled() {
+        let (ok, summary) = match &result {
+            Ok(summary) => (true, summary),
+            Err(e) => (false, e),
+        };
+        let recorded = database::open().and_then(|conn| {
+            conn.execute(
+                "INSERT INTO task_history (job, ran_at, ok, summary) VALUES (?1, ?2, ?3, ?4)",
+                params![job.key(), now(), ok, summary],
+            )
+            .map_err(|e| e.to_string())
+        });
+        if let Err(e) = recorded {
+            println!("Failed to record maintenance run: {}", e);
+        }
+        return result;
+    }
+
+    let summary = result?;
+    let mut state = State::load();
+    state.last_run.insert(job.key().to_string(), now());
+    state.save()?;
+    Ok(summary)
+}
+
+async fn run_job(job: Job, config: MaintenanceConfig) -> Result<String, String> {
+    let summary = match job {
+        Job::RefreshManifest => {
+            let manifest = version_manager::fetch_manifest().await?;
+            format!("{} versions available", manifest.versions.len())
+        }
+        Job::PruneLogs => {
+            let retention = Duration::from_secs(u64::from(config.log_retention_days) * DAY_SECS);
+            tokio::task::spawn_blocking(move || {
+                prune_logs(&instance_manager::instances_dir(), retention)
+            })
+            .await
+            .map_err(|e| e.to_string())??
+        }
+        Job::GcAssets => {
+            tokio::task::spawn_blocking(|| gc_assets(&instance_manager::instances_dir()))
+                .await
+                .map_err(|e| e.to_string())??
+        }
+        Job::RefreshAvatars => {
+            let mut store = AccountStore::load().map_err(|e| e.to_string())?;
+            let refreshed = store.refresh_skins().await.map_err(|e| e.to_string())?;
+            format!("{} avatars refreshed", refreshed)
+        }
+    };
+    Ok(summary)
+}
+
+/// `.minecraft` of every instance.
+fn game_dirs(instances_dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(instances_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path().join(".minecraft"))
+                .filter(|dir| dir.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Deletes game logs, the launcher's session logs, crash reports and heap
+/// dumps older than `retention`. The log of the current or last session
+/// (`latest.log`) is always kept.
+fn prune_logs(instances_dir: &Path, retention: Duration) -> Result<String, String> {
+    let cutoff = SystemTime::now()
+        .checked_sub(retention)
+        .unwrap_or(UNIX_EPOCH);
+    let (mut removed, mut freed) = (0, 0);
+    for game_dir in game_dirs(instances_dir) {
+        for dir in [
+            game_dir.join("logs"),
+            game_dir.join("logs").join("heapdumps"),
+            game_dir.join("crash-reports"),
+            game_dir.with_file_name(crate::session_log::LOGS_DIR),
+        ] {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let Ok(meta) = entry.metadata() else {
+                    continue;
+                };
+                let expired = meta.modified().is_ok_and(|modified| modified < cutoff);
+                if meta.is_file() && expired && entry.file_name() != "latest.log" {
+                    fs::remove_file(entry.path()).map_err(|e| e.to_string())?;
+                    removed += 1;
+                    freed += meta.len();
+                }
+            }
+        }
+    }
+    Ok(format!(
+        "{} old logs removed ({})",
+        removed,
+        crate::format::size(freed)
+    ))
+}
+
+/// Deletes asset objects that none of the instance's asset indexes refer to,
+/// e.g. left behind by a version the instance no longer uses.
+fn gc_assets(instances_dir: &Path) -> Result<String, String> {
+    let (mut removed, mut freed) = (0, 0);
+    for game_dir in game_dirs(instances_dir) {
+        let assets_dir = game_dir.join("assets");
+        let Some(referenced) = referenced_objects(&assets_dir.join("indexes")) else {
+            // Without a readable index every object would look unused
+            continue;
+        };
+        let Ok(prefixes) = fs::read_dir(assets_dir.join("objects")) else {
+            continue;
+        };
+        for prefix in prefixes.flatten() {
+            let Ok(objects) = fs::read_dir(prefix.path()) else {
+                continue;
+            };
+            for object in objects.flatten() {
+                let hash = object.file_name().to_string_lossy().to_string();
+                if referenced.contains(&hash) {
+                    continue;
+                }
+                let size = object.metadata().map(|m| m.len()).unwrap_or_default();
+                fs::remove_file(object.path()).map_err(|e| e.to_string())?;
+                removed += 1;
+                freed += size;
+            }
+        }
+    }
+    Ok(format!(
+        "{} unused assets removed ({})",
+        removed,
+        crate::format::size(freed)
+    ))
+}
+
+/// Hashes listed by every index in `indexes_dir`, or `None` when there is no
+/// index or one of them can't be read.
+fn referenced_objects(indexes_dir: &Path) -> Option<HashSet<String>> {
+    let mut hashes = HashSet::new();
+    let mut found = false;
+    for entry in fs::read_dir(indexes_dir).ok()?.flatten() {
+        if entry.path().extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let raw = fs::read_to_string(entry.path()).ok()?;
+        let index: serde_json::Value = serde_json::from_str(&raw).ok()?;
+        let objects = index["objects"].as_object()?;
+        hashes.extend(
+            objects
+                .values()
+                .filter_map(|object| object["hash"].as_str())
+                .map(str::to_string),
+        );
+        found = true;
+    }
+    found.then_some(hashes)
+}
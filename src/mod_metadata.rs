@@ -0,0 +1,505 @@
+//! Name, version and icon of the mod jars in an instance, read from the
+//! loader metadata inside each jar. Parsed metadata is cached on disk by the
+//! jar's SHA-1 and shared by all instances, so only new or changed jars are
+//! opened; a jar is re-hashed only when its size or mtime changes.
+
+use crate::database;
+use crate::instance_manager::ModLoader;
+use directories::ProjectDirs;
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const CACHE_FILE: &str = "mod_cache.json";
+const ICONS_DIR: &str = "mod_icons";
+/// Bumped when `ModInfo` gains fields, so cached entries are parsed again.
+const CACHE_FORMAT: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModInfo {
+    pub name: String,
+    pub version: Option<String>,
+    /// Homepage or source repository declared by the jar.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Icon extracted from the jar into the launcher's data dir.
+    pub icon: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstalledMod {
+    pub file_name: String,
+    pub sha1: String,
+    pub info: ModInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct FileStamp {
+    size: u64,
+    modified: u64,
+    sha1: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cache {
+    #[serde(default)]
+    format: u32,
+    /// Parsed metadata by jar SHA-1.
+    mods: HashMap<String, ModInfo>,
+    /// Hash each jar had when last seen.
+    files: HashMap<PathBuf, FileStamp>,
+}
+
+impl Cache {
+    fn load(data_dir: &Path) -> Self {
+        if database::enabled() {
+            return database::open()
+                .and_then(|conn| Self::load_db(&conn))
+                .unwrap_or_else(|e| {
+                    println!("Failed to read the mod cache database: {}", e);
+                    Self::default()
+                });
+        }
+        Self::load_json(data_dir)
+    }
+
+    fn save(&self, data_dir: &Path) -> Result<(), String> {
+        if database::enabled() {
+            return self.save_db(&database::open()?);
+        }
+        fs::create_dir_all(data_dir).map_err(|e| e.to_string())?;
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(data_dir.join(CACHE_FILE), json).map_err(|e| e.to_string())
+    }
+
+    fn load_json(data_dir: &Path) -> Self {
+        let mut cache: Self = fs::read_to_string(data_dir.join(CACHE_FILE))
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        if cache.format < CACHE_FORMAT {
+            cache.mods.clear();
+            cache.format = CACHE_FORMAT;
+        }
+        cache
+    }
+
+    fn load_db(conn: &Connection) -> Result<Self, String> {
+        let mut cache = Self::default();
+        let mut stmt = conn
+            .prepare("SELECT sha1, name, version, icon, url FROM mod_info")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| {
+                let icon: Option<String> = row.get(3)?;
+                Ok((
+                    row.get::<_, String>(0)?,
+                    ModInfo {
+                        name: row.get(1)?,
+                        version: row.get(2)?,
+                        url: row.get(4)?,
+                        icon: icon.map(PathBuf::from),
+                    },
+                ))
+            })
+            .map_err(|e| e.to_string())?;
+        for row in rows {
+            let (sha1, info) = row.map_err(|e| e.to_string())?;
+            cache.mods.insert(sha1, info);
+        }
+
+        let mut stmt = conn
+            .prepare("SELECT path, size, modified, sha1 FROM mod_files")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    PathBuf::from(row.get::<_, String>(0)?),
+                    FileStamp {
+                        size: row.get(1)?,
+                        modified: row.get(2)?,
+                        sha1: row.get(3)?,
+                    },
+                ))
+            })
+            .map_err(|e| e.to_string())?;
+        for row in rows {
+            let (path, stamp) = row.map_err(|e| e.to_string())?;
+            cache.files.insert(path, stamp);
+        }
+        Ok(cache)
+    }
+
+    /// Replaces the cached rows with this cache.
+    fn save_db(&self, conn: &Connection) -> Result<(), String> {
+        let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        tx.execute_batch("DELETE FROM mod_info; DELETE FROM mod_files;")
+            .map_err(|e| e.to_string())?;
+        for (sha1, info) in &self.mods {
+            let icon = info.icon.as_ref().map(|p| p.to_string_lossy().to_string());
+            tx.execute(
+                "INSERT INTO mod_info (sha1, name, version, icon, url) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![sha1, info.name, info.version, icon, info.url],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        for (path, stamp) in &self.files {
+            tx.execute(
+                "INSERT INTO mod_files (path, size, modified, sha1) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    path.to_string_lossy().to_string(),
+                    stamp.size,
+                    stamp.modified,
+                    stamp.sha1
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        tx.commit().map_err(|e| e.to_string())
+    }
+
+    /// Drops jars that left `mods_dir`, then metadata and icons no jar
+    /// refers to anymore.
+    fn prune(&mut self, mods_dir: &Path, present: &HashSet<PathBuf>, icons_dir: &Path) {
+        self.files
+            .retain(|path, _| path.parent() != Some(mods_dir) || present.contains(path));
+        let referenced: HashSet<&str> = self.files.values().map(|s| s.sha1.as_str()).collect();
+        self.mods.retain(|sha1, _| {
+            let keep = referenced.contains(sha1.as_str());
+            if !keep {
+                let _ = fs::remove_file(icons_dir.join(format!("{}.png", sha1)));
+            }
+            keep
+        });
+    }
+}
+
+fn data_dir() -> Result<PathBuf, String> {
+    let dirs = ProjectDirs::from("com", "fastmc", "fastmc").ok_or("Data directory unavailable")?;
+    Ok(dirs.data_local_dir().to_path_buf())
+}
+
+/// Copies `mod_cache.json` into a freshly created database.
+pub fn import_cache(conn: &Connection) -> Result<(), String> {
+    Cache::load_json(&data_dir()?).save_db(conn)
+}
+
+/// Mods in `mods_dir`, sorted by name. Disabled jars (`.jar.disabled`) are
+/// skipped like the game does.
+pub fn list_mods(mods_dir: &Path) -> Result<Vec<InstalledMod>, String> {
+    let data_dir = data_dir()?;
+    let icons_dir = data_dir.join(ICONS_DIR);
+    let mut cache = Cache::load(&data_dir);
+    let before = (cache.mods.clone(), cache.files.clone());
+
+    let entries = match fs::read_dir(mods_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut mods = Vec::new();
+    let mut present = HashSet::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "jar") {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        let size = meta.len();
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+
+        let known = cache
+            .files
+            .get(&path)
+            .filter(|stamp| stamp.size == size && stamp.modified == modified)
+            .map(|stamp| stamp.sha1.clone());
+        let (sha1, bytes) = match known {
+            Some(sha1) => (sha1, None),
+            None => {
+                let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+                (downloader::sha1_hex(&bytes), Some(bytes))
+            }
+        };
+
+        let info = match cache.mods.get(&sha1) {
+            Some(info) => info.clone(),
+            None => {
+                let bytes = match bytes {
+                    Some(bytes) => bytes,
+                    None => fs::read(&path).map_err(|e| e.to_string())?,
+                };
+                let info = parse_jar(&bytes, &sha1, &file_name, &icons_dir);
+                cache.mods.insert(sha1.clone(), info.clone());
+                info
+            }
+        };
+
+        cache.files.insert(
+            path.clone(),
+            FileStamp {
+                size,
+                modified,
+                sha1: sha1.clone(),
+            },
+        );
+        present.insert(path);
+        mods.push(InstalledMod {
+            file_name,
+            sha1,
+            info,
+        });
+    }
+
+    cache.prune(mods_dir, &present, &icons_dir);
+    if (&cache.mods, &cache.files) != (&before.0, &before.1) {
+        cache.save(&data_dir)?;
+    }
+
+    mods.sort_by_key(|m| m.info.name.to_lowercase());
+    Ok(mods)
+}
+
+/// Reads Fabric, Quilt, Forge or NeoForge metadata; jars without any are
+/// listed under their file name.
+fn parse_jar(bytes: &[u8], sha1: &str, file_name: &str, icons_dir: &Path) -> ModInfo {
+    let fallback = ModInfo {
+        name: file_name.trim_end_matches(".jar").to_string(),
+        version: None,
+        url: None,
+        icon: None,
+    };
+    let Ok(mut archive) = zip::ZipArchive::new(Cursor::new(bytes)) else {
+        return fallback;
+    };
+
+    let (info, icon_entry) = if let Some(json) = read_entry(&mut archive, "fabric.mod.json") {
+        fabric_info(&json)
+    } else if let Some(json) = read_entry(&mut archive, "quilt.mod.json") {
+        quilt_info(&json)
+    } else if let Some(toml) = read_entry(&mut archive, "META-INF/neoforge.mods.toml")
+        .or_else(|| read_entry(&mut archive, "META-INF/mods.toml"))
+    {
+        let manifest = read_entry(&mut archive, "META-INF/MANIFEST.MF").unwrap_or_default();
+        forge_info(&toml, &manifest)
+    } else {
+        (None, None)
+    };
+    let Some(mut info) = info else {
+        return fallback;
+    };
+    if info.name.is_empty() {
+        info.name = fallback.name;
+    }
+
+    if let Some(entry) = icon_entry {
+        let mut icon = Vec::new();
+        let extracted = archive
+            .by_name(entry.trim_start_matches('/'))
+            .is_ok_and(|mut file| file.read_to_end(&mut icon).is_ok());
+        let path = icons_dir.join(format!("{}.png", sha1));
+        if extracted && fs::create_dir_all(icons_dir).is_ok() && fs::write(&path, icon).is_ok() {
+            info.icon = Some(path);
+        }
+    }
+    info
+}
+
+/// Loaders a jar ships metadata for, judged by which descriptor files it
+/// contains. Empty for plain libraries and unreadable jars.
+pub fn declared_loaders(path: &Path) -> Vec<ModLoader> {
+    let Ok(Ok(archive)) = fs::File::open(path).map(zip::ZipArchive::new) else {
+        return Vec::new();
+    };
+    let descriptors = [
+        ("fabric.mod.json", ModLoader::Fabric),
+        ("quilt.mod.json", ModLoader::Quilt),
+        ("META-INF/mods.toml", ModLoader::Forge),
+        ("META-INF/neoforge.mods.toml", ModLoader::NeoForge),
+    ];
+    let names: HashSet<&str> = archive.file_names().collect();
+    descriptors
+        .into_iter()
+        .filter(|(name, _)| names.contains(name))
+        .map(|(_, loader)| loader)
+        .collect()
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<Cursor<&[u8]>>, name: &str) -> Option<String> {
+    let mut file = archive.by_name(name).ok()?;
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+type Parsed = (Option<ModInfo>, Option<String>);
+
+fn fabric_info(json: &str) -> Parsed {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return (None, None);
+    };
+    let info = ModInfo {
+        name: value["name"]
+            .as_str()
+            .or(value["id"].as_str())
+            .unwrap_or_default()
+            .to_string(),
+        version: value["version"].as_str().map(str::to_string),
+        url: contact_url(&value["contact"]),
+        icon: None,
+    };
+    (Some(info), icon_path(&value["icon"]))
+}
+
+fn quilt_info(json: &str) -> Parsed {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return (None, None);
+    };
+    let loader = &value["quilt_loader"];
+    let info = ModInfo {
+        name: loader["metadata"]["name"]
+            .as_str()
+            .or(loader["id"].as_str())
+            .unwrap_or_default()
+            .to_string(),
+        version: loader["version"].as_str().map(str::to_string),
+        url: contact_url(&loader["metadata"]["contact"]),
+        icon: None,
+    };
+    (Some(info), icon_path(&loader["metadata"]["icon"]))
+}
+
+/// Fabric and Quilt `contact` block; the homepage is preferred over sources.
+fn contact_url(contact: &serde_json::Value) -> Option<String> {
+    ["homepage", "sources"]
+        .iter()
+        .find_map(|key| contact[key].as_str())
+        .map(str::to_string)
+}
+
+/// Icons are either a path or a map of sizes to paths; the largest wins.
+fn icon_path(icon: &serde_json::Value) -> Option<String> {
+    match icon {
+        serde_json::Value::String(path) => Some(path.clone()),
+        serde_json::Value::Object(sizes) => sizes
+            .iter()
+            .max_by_key(|(size, _)| size.parse::<u32>().unwrap_or_default())
+            .and_then(|(_, path)| path.as_str())
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+/// `mods.toml` is only scanned for the handful of keys shown, so no TOML
+/// parser is pulled in; the first `[[mods]]` entry describes the jar.
+fn forge_info(toml: &str, manifest: &str) -> Parsed {
+    let Some(id) = toml_value(toml, "modId") else {
+        return (None, None);
+    };
+    // `${file.jarVersion}` is filled in from the manifest at runtime
+    let version = toml_value(toml, "version").and_then(|version| {
+        if version.starts_with("${") {
+            manifest
+                .lines()
+                .find_map(|line| line.strip_prefix("Implementation-Version:"))
+                .map(|v| v.trim().to_string())
+        } else {
+            Some(version)
+        }
+    });
+    let info = ModInfo {
+        name: toml_value(toml, "displayName").unwrap_or(id),
+        version,
+        url: toml_value(toml, "displayURL"),
+        icon: None,
+    };
+    (Some(info), toml_value(toml, "logoFile"))
+}
+
+fn toml_value(toml: &str, key: &str) -> Option<String> {
+    toml.lines().find_map(|line| {
+        let (name, value) = line.split_once('=')?;
+        if name.trim() != key {
+            return None;
+        }
+        let value = value.split('#').next()?.trim();
+        let value = value.trim_matches(|c| c == '"' || c == '\'');
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// File formats the mod list can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Mod list as a Markdown table or CSV, one row per jar with its name,
+/// version, source URL and SHA-1.
+pub fn export_mods(mods: &[InstalledMod], format: ExportFormat) -> String {
+    let header = ["Mod", "Version", "Source", "SHA-1", "File"];
+    let rows = mods.iter().map(|m| {
+        [
+            m.info.name.as_str(),
+            m.info.version.as_deref().unwrap_or_default(),
+            m.info.url.as_deref().unwrap_or_default(),
+            m.sha1.as_str(),
+            m.file_name.as_str(),
+        ]
+    });
+    let mut out = String::new();
+    match format {
+        ExportFormat::Markdown => {
+            let line = |cells: [&str; 5]| {
+                let cells: Vec<String> = cells.iter().map(|c| c.replace('|', "\\|")).collect();
+                format!("| {} |\n", cells.join(" | "))
+            };
+            out.push_str(&line(header));
+            out.push_str("|---|---|---|---|---|\n");
+            for row in rows {
+                out.push_str(&line(row));
+            }
+        }
+        ExportFormat::Csv => {
+            let line = |cells: [&str; 5]| {
+                let cells: Vec<String> = cells.iter().map(|c| csv_field(c)).collect();
+                format!("{}\n", cells.join(","))
+            };
+            out.push_str(&line(header));
+            for row in rows {
+                out.push_str(&line(row));
+            }
+        }
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,199 @@
+use crate::game::LaunchStatus;
+use iced::widget::{button, canvas, row, text};
+use iced::{Alignment, Border, Color, Element, Length, Point, Radians, Rectangle, mouse};
+use std::f32::consts::{FRAC_PI_2, TAU};
+use std::time::Duration;
+
+const PLAY_BASE: Color = Color::from_rgb(0.13, 0.77, 0.36);
+const PLAY_HOVER: Color = Color::from_rgb(0.15, 0.85, 0.40);
+const BUSY_BASE: Color = Color::from_rgb(0.20, 0.21, 0.24);
+const BUSY_HOVER: Color = Color::from_rgb(0.26, 0.27, 0.30);
+const STOP_BASE: Color = Color::from_rgb(0.80, 0.26, 0.26);
+const STOP_HOVER: Color = Color::from_rgb(0.90, 0.32, 0.32);
+const DISABLED: Color = Color::from_rgb(0.3, 0.3, 0.3);
+
+const RING_WIDTH: f32 = 2.5;
+
+/// Where a launch stands, as far as its Play button is concerned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayState {
+    Idle,
+    /// Files are being prepared; fraction of the current phase.
+    Preparing(f32),
+    /// The game process is up.
+    Running(Duration),
+}
+
+/// Where the button sits: the Play screen hero, an instance card, or the
+/// round icon button of the profile list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayButtonSize {
+    Hero,
+    Card,
+    Icon,
+}
+
+impl PlayButtonSize {
+    fn text_size(self) -> f32 {
+        match self {
+            PlayButtonSize::Hero => 16.0,
+            PlayButtonSize::Card => 12.0,
+            PlayButtonSize::Icon => 14.0,
+        }
+    }
+
+    fn padding(self) -> [u16; 2] {
+        match self {
+            PlayButtonSize::Hero => [12, 24],
+            PlayButtonSize::Card => [5, 10],
+            PlayButtonSize::Icon => [10, 10],
+        }
+    }
+
+    fn radius(self) -> f32 {
+        match self {
+            PlayButtonSize::Icon => 20.0,
+            PlayButtonSize::Hero | PlayButtonSize::Card => 6.0,
+        }
+    }
+}
+
+impl From<&LaunchStatus> for PlayState {
+    fn from(status: &LaunchStatus) -> Self {
+        match status.running_since {
+            Some(since) => PlayState::Running(since.elapsed()),
+            None => PlayState::Preparing(status.fraction()),
+        }
+    }
+}
+
+/// Play button shared by the Play screen and instance cards. Idle it shows
+/// `label` and sends `on_play` (disabled when `None`, e.g. while another
+/// instance launches); once a launch is under way it shows a progress ring
+/// and then the session time, and sends `on_stop` to cancel the preparation
+/// or stop the game. The icon size drops the text next to the ring.
+pub fn play_button<'a, Message: Clone + 'a>(
+    state: PlayState,
+    label: &'a str,
+    size: PlayButtonSize,
+    on_play: Option<Message>,
+    on_stop: Message,
+) -> Element<'a, Message> {
+    let text_size = size.text_size();
+    let compact = size == PlayButtonSize::Icon;
+    let (content, base, hover, on_press): (Element<'a, Message>, _, _, _) = match state {
+        PlayState::Idle => {
+            let (base, hover) = if on_play.is_some() {
+                (PLAY_BASE, PLAY_HOVER)
+            } else {
+                (DISABLED, DISABLED)
+            };
+            (
+                text(label).size(text_size).color(Color::WHITE).into(),
+                base,
+                hover,
+                on_play,
+            )
+        }
+        PlayState::Preparing(fraction) => {
+            let ring = progress_ring(fraction, text_size + 4.0);
+            let content = if compact {
+                ring
+            } else {
+                row![ring, text("Cancel").size(text_size).color(Color::WHITE)]
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .into()
+            };
+            (content, BUSY_BASE, BUSY_HOVER, Some(on_stop))
+        }
+        PlayState::Running(elapsed) => {
+            let label = if compact {
+                "■".to_string()
+            } else {
+                format!("■ Stop • {}", crate::format::duration(elapsed))
+            };
+            (
+                text(label).size(text_size).color(Color::WHITE).into(),
+                STOP_BASE,
+                STOP_HOVER,
+                Some(on_stop),
+            )
+        }
+    };
+
+    button(content)
+        .padding(size.padding())
+        .on_press_maybe(on_press)
+        .style(move |_theme, status| {
+            let background = match status {
+                button::Status::Hovered | button::Status::Pressed => hover,
+                _ => base,
+            };
+            button::Style {
+                background: Some(background.into()),
+                border: Border {
+                    radius: size.radius().into(),
+                    ..Border::default()
+                },
+                text_color: Color::WHITE,
+                ..button::Style::default()
+            }
+        })
+        .into()
+}
+
+fn progress_ring<'a, Message: 'a>(fraction: f32, size: f32) -> Element<'a, Message> {
+    canvas(ProgressRing { fraction })
+        .width(Length::Fixed(size))
+        .height(Length::Fixed(size))
+        .into()
+}
+
+struct ProgressRing {
+    fraction: f32,
+}
+
+impl<Message> canvas::Program<Message> for ProgressRing {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &iced::Renderer,
+        _theme: &iced::Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let center = Point::new(bounds.width / 2.0, bounds.height / 2.0);
+        let radius = bounds.width.min(bounds.height) / 2.0 - RING_WIDTH;
+
+        let track = canvas::Path::circle(center, radius);
+        frame.stroke(
+            &track,
+            canvas::Stroke::default()
+                .with_width(RING_WIDTH)
+                .with_color(Color::from_rgba(1.0, 1.0, 1.0, 0.2)),
+        );
+
+        let start = -FRAC_PI_2;
+        let arc = canvas::Path::new(|path| {
+            path.arc(canvas::path::Arc {
+                center,
+                radius,
+                start_angle: Radians(start),
+                end_angle: Radians(start + self.fraction.clamp(0.0, 1.0) * TAU),
+            })
+        });
+        frame.stroke(
+            &arc,
+            canvas::Stroke::default()
+                .with_width(RING_WIDTH)
+                .with_color(PLAY_BASE)
+                .with_line_cap(canvas::LineCap::Round),
+        );
+
+        vec![frame.into_geometry()]
+    }
+}
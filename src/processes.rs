@@ -0,0 +1,112 @@
+//! Game processes this launcher started, keyed by instance id, so any screen
+//! can tell whether an instance is running and stop it.
+
+use downloader::CancellationToken;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::Instant;
+
+struct RunningGame {
+    pid: Option<u32>,
+    since: Instant,
+    kill: CancellationToken,
+}
+
+fn registry() -> MutexGuard<'static, HashMap<String, RunningGame>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, RunningGame>>> = OnceLock::new();
+    REGISTRY
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Keeps an instance registered as running until dropped.
+pub struct Registration {
+    instance_id: String,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        registry().remove(&self.instance_id);
+    }
+}
+
+/// Records a freshly spawned game. Cancelling `kill` must make the launch
+/// task kill the child.
+pub fn register(instance_id: &str, pid: Option<u32>, kill: CancellationToken) -> Registration {
+    registry().insert(
+        instance_id.to_string(),
+        RunningGame {
+            pid,
+            since: Instant::now(),
+            kill,
+        },
+    );
+    Registration {
+        instance_id: instance_id.to_string(),
+    }
+}
+
+/// When the instance's game process was spawned, if it is still running.
+pub fn running_since(instance_id: &str) -> Option<Instant> {
+    registry().get(instance_id).map(|game| game.since)
+}
+
+/// OS process id of the instance's game, if it is running.
+pub fn pid(instance_id: &str) -> Option<u32> {
+    registry().get(instance_id).and_then(|game| game.pid)
+}
+
+pub fn any_running() -> bool {
+    !registry().is_empty()
+}
+
+/// Force-stops the instance's game. Returns false if it wasn't running.
+pub fn kill(instance_id: &str) -> bool {
+    match registry().get(instance_id) {
+        Some(game) => {
+            game.kill.cancel();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Instances from the start of a launch until their game exits. Two games
+/// sharing one `.minecraft` corrupt its worlds.
+fn launches() -> MutexGuard<'static, HashSet<String>> {
+    static LAUNCHES: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    LAUNCHES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Keeps other launches of an instance out until dropped.
+pub struct LaunchLock {
+    instance_id: String,
+}
+
+impl Drop for LaunchLock {
+    fn drop(&mut self) {
+        launches().remove(&self.instance_id);
+    }
+}
+
+/// Claims the instance for a launch, or explains why it can't be launched
+/// again.
+pub fn lock_launch(instance_id: &str) -> Result<LaunchLock, String> {
+    if !launches().insert(instance_id.to_string()) {
+        return Err(match pid(instance_id) {
+            Some(pid) => format!(
+                "This instance is already running (PID {}). Switch to its game window, \
+                 or stop it before launching it again.",
+                pid
+            ),
+            None => "This instance is already being launched.".to_string(),
+        });
+    }
+    Ok(LaunchLock {
+        instance_id: instance_id.to_string(),
+    })
+}
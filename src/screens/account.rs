@@ -1,7 +1,17 @@
-use account_manager::{Account, AccountError, AccountKind, AccountService, AccountStore};
-use iced::widget::{button, column, container, row, scrollable, text, text_input};
+use crate::skin_preview::{SkinTexture, skin_preview};
+use account_manager::{
+    Account, AccountError, AccountKind, AccountService, AccountStore, ImportOutcome,
+    ImportedAccount, ImportedKind, server_host,
+};
+use config_manager::{ALL_LOGIN_FLOWS, FastmcConfig, LoginFlow};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, qr_code, row, scrollable, text, text_input,
+};
 use iced::{Alignment, Background, Border, Color, Element, Length, Shadow, Task};
-use microsoft_auth::DeviceCodeInfo;
+use microsoft_auth::{CancellationToken, DeviceCodeInfo};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -9,11 +19,46 @@ pub enum Message {
     OfflineNameChanged(String),
     AddOffline,
     AddMicrosoft,
+    LoginFlowSelected(LoginFlow),
     MicrosoftCodeReady(Box<Result<DeviceCodeInfo, String>>),
     MicrosoftComplete,
+    CopyDeviceCode,
+    /// Open the device code's verification page in the system browser.
+    OpenVerificationPage,
+    /// Stop polling for the device-code login.
+    CancelMicrosoft,
     MicrosoftFinished(Box<Result<AccountStore, String>>),
     SelectAccount(Uuid),
+    /// Forget the account and its tokens on this device only.
     DeleteAccount(Uuid),
+    /// Forget the account, then open Microsoft's page for withdrawing the
+    /// launcher's access on every device.
+    RevokeAccess(Uuid),
+    /// Pick a skin PNG for an offline account.
+    ChooseSkin(Uuid),
+    /// `None` when the dialog was dismissed.
+    SkinChosen(Uuid, Option<PathBuf>),
+    ClearSkin(Uuid),
+    /// Look for accounts in other launchers' default locations.
+    ScanImports,
+    ImportsFound(Vec<ImportedAccount>),
+    /// Pick another launcher's account file, e.g. from a portable MultiMC.
+    ImportFromFile,
+    /// `None` when the dialog was dismissed.
+    ImportFileRead(Option<Result<Vec<ImportedAccount>, String>>),
+    ToggleImport(usize, bool),
+    ImportSelected,
+    CloseImport,
+    /// How many accounts were added, and the updated store.
+    ImportFinished(Box<Result<(ImportOutcome, AccountStore), String>>),
+    /// Open the sign-in form for a third-party Yggdrasil server.
+    ShowCustomLogin,
+    CustomServerChanged(String),
+    CustomUsernameChanged(String),
+    CustomPasswordChanged(String),
+    AddCustom,
+    CloseCustomLogin,
+    CustomFinished(Box<Result<AccountStore, String>>),
     BackToLauncher,
 }
 
@@ -23,13 +68,38 @@ pub enum AccountUpdate {
     EnterLauncher,
 }
 
+#[derive(Debug, Default)]
+struct CustomLogin {
+    server: String,
+    username: String,
+    password: String,
+}
+
 pub struct AccountScreen {
     store: AccountStore,
     offline_username: String,
     error: Option<String>,
+    notice: Option<String>,
     microsoft_client_id: Option<String>,
     device_code: Option<DeviceCodeInfo>,
+    /// QR code of the device code's verification link, for finishing the
+    /// login on a phone.
+    device_qr: Option<qr_code::Data>,
     microsoft_in_progress: bool,
+    login_flow: LoginFlow,
+    /// Cancels the device-code poll in flight.
+    login_cancel: Option<CancellationToken>,
+    /// Accounts found in other launchers and whether each is ticked for
+    /// import; `None` while the import panel is closed.
+    import_candidates: Option<Vec<(ImportedAccount, bool)>>,
+    importing: bool,
+    /// Server, username and password of the custom server sign-in form;
+    /// `None` while it is closed.
+    custom_login: Option<CustomLogin>,
+    custom_in_progress: bool,
+    /// Skin texture of the active account with the file's path and
+    /// modification time, reloaded when either changes.
+    skin: Option<(PathBuf, SystemTime, Arc<SkinTexture>)>,
 }
 
 impl AccountScreen {
@@ -46,14 +116,25 @@ impl AccountScreen {
             let _ = store.save();
         }
 
-        Self {
+        let mut screen = Self {
             store,
             offline_username: String::new(),
             error,
+            notice: None,
             microsoft_client_id,
             device_code: None,
+            device_qr: None,
             microsoft_in_progress: false,
-        }
+            login_flow: FastmcConfig::load().unwrap_or_default().accounts.login_flow,
+            login_cancel: None,
+            import_candidates: None,
+            importing: false,
+            custom_login: None,
+            custom_in_progress: false,
+            skin: None,
+        };
+        screen.sync_skin();
+        screen
     }
 
     pub fn has_accounts(&self) -> bool {
@@ -64,6 +145,12 @@ impl AccountScreen {
         self.store.clone()
     }
 
+    /// Takes in the store as saved elsewhere.
+    pub fn replace_store(&mut self, store: AccountStore) {
+        self.store = store;
+        self.sync_skin();
+    }
+
     #[allow(dead_code)]
     pub fn get_microsoft_tokens(
         &self,
@@ -79,7 +166,48 @@ impl AccountScreen {
             .or_else(|| self.store.accounts.first())
     }
 
+    /// Loads the skin of the active Microsoft account for the preview, if it
+    /// changed since last time.
+    fn sync_skin(&mut self) {
+        let path = self
+            .active_account()
+            .and_then(|account| match &account.kind {
+                AccountKind::Microsoft { uuid, .. } => {
+                    account_manager::skin_texture_path(uuid).ok()
+                }
+                AccountKind::Offline { .. } => account_manager::offline_skin_path(&account.id).ok(),
+                AccountKind::Custom { .. } => None,
+            });
+        let Some((path, modified)) = path.and_then(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        }) else {
+            self.skin = None;
+            return;
+        };
+        if self
+            .skin
+            .as_ref()
+            .is_some_and(|(loaded, at, _)| *loaded == path && *at == modified)
+        {
+            return;
+        }
+        self.skin = match SkinTexture::load(&path) {
+            Ok(texture) => Some((path, modified, Arc::new(texture))),
+            Err(e) => {
+                println!("{}", e);
+                None
+            }
+        };
+    }
+
     pub fn update(&mut self, message: Message) -> (AccountUpdate, Task<Message>) {
+        let result = self.handle(message);
+        self.sync_skin();
+        result
+    }
+
+    fn handle(&mut self, message: Message) -> (AccountUpdate, Task<Message>) {
         match message {
             Message::OfflineNameChanged(name) => {
                 self.offline_username = name;
@@ -112,6 +240,24 @@ impl AccountScreen {
                     self.device_code = None;
 
                     let client_id = client_id.clone();
+                    if self.login_flow == LoginFlow::Browser {
+                        let task = Task::perform(
+                            async move {
+                                let mut service =
+                                    AccountService::new(client_id).map_err(|e| e.to_string())?;
+                                service
+                                    .login_with_browser(|url| {
+                                        open::that(url).map_err(|e| e.to_string())
+                                    })
+                                    .await
+                                    .map_err(login_error)?;
+                                AccountStore::load().map_err(|e| e.to_string())
+                            },
+                            |result| Message::MicrosoftFinished(Box::new(result)),
+                        );
+                        return (AccountUpdate::None, task);
+                    }
+
                     let task = Task::perform(
                         async move {
                             let service =
@@ -130,10 +276,25 @@ impl AccountScreen {
                     (AccountUpdate::None, Task::none())
                 }
             }
+            Message::LoginFlowSelected(flow) => {
+                self.login_flow = flow;
+                let mut config = FastmcConfig::load().unwrap_or_default();
+                config.accounts.login_flow = flow;
+                self.error = config
+                    .save()
+                    .err()
+                    .map(|e| format!("Couldn't save settings: {}", e));
+                (AccountUpdate::None, Task::none())
+            }
             Message::MicrosoftCodeReady(result) => {
                 match *result {
                     Ok(code) => {
                         self.error = None;
+                        let link = code
+                            .verification_uri_complete
+                            .as_ref()
+                            .unwrap_or(&code.verification_uri);
+                        self.device_qr = qr_code::Data::new(link).ok();
                         self.device_code = Some(code);
                         // Trigger polling immediately
                         return (
@@ -166,14 +327,16 @@ impl AccountScreen {
                 };
 
                 self.microsoft_in_progress = true;
+                let cancel = CancellationToken::new();
+                self.login_cancel = Some(cancel.clone());
                 let task = Task::perform(
                     async move {
                         let mut service =
                             AccountService::new(client_id).map_err(|e| e.to_string())?;
                         service
-                            .complete_microsoft_login(&code)
+                            .complete_microsoft_login(&code, &cancel)
                             .await
-                            .map_err(|e| e.to_string())?;
+                            .map_err(login_error)?;
                         AccountStore::load().map_err(|e| e.to_string())
                     },
                     |result| Message::MicrosoftFinished(Box::new(result)),
@@ -181,8 +344,42 @@ impl AccountScreen {
 
                 (AccountUpdate::None, task)
             }
+            Message::CopyDeviceCode => {
+                let task = self.device_code.as_ref().map_or_else(Task::none, |code| {
+                    iced::clipboard::write(code.user_code.clone())
+                });
+                (AccountUpdate::None, task)
+            }
+            Message::OpenVerificationPage => {
+                if let Some(code) = &self.device_code {
+                    let link = code
+                        .verification_uri_complete
+                        .as_ref()
+                        .unwrap_or(&code.verification_uri);
+                    self.error = open::that(link)
+                        .err()
+                        .map(|e| format!("Failed to open {}: {}", link, e));
+                }
+                (AccountUpdate::None, Task::none())
+            }
+            Message::CancelMicrosoft => {
+                if let Some(cancel) = &self.login_cancel {
+                    cancel.cancel();
+                }
+                self.microsoft_in_progress = false;
+                self.device_code = None;
+                (AccountUpdate::None, Task::none())
+            }
             Message::MicrosoftFinished(result) => {
                 self.microsoft_in_progress = false;
+                // A cancelled poll only reports that it stopped
+                if self
+                    .login_cancel
+                    .take()
+                    .is_some_and(|cancel| cancel.is_cancelled())
+                {
+                    return (AccountUpdate::None, Task::none());
+                }
                 match *result {
                     Ok(store) => {
                         self.store = store;
@@ -196,16 +393,88 @@ impl AccountScreen {
                     }
                 }
             }
-            Message::SelectAccount(id) => match self.set_active(id) {
-                Ok(_) => (AccountUpdate::EnterLauncher, Task::none()),
+            Message::SelectAccount(id) => {
+                // Custom server accounts sign in again through the form
+                if let Some(Account {
+                    kind:
+                        AccountKind::Custom {
+                            server, username, ..
+                        },
+                    requires_login: true,
+                    ..
+                }) = self.store.accounts.iter().find(|a| a.id == id)
+                {
+                    self.custom_login = Some(CustomLogin {
+                        server: server.clone(),
+                        username: username.clone(),
+                        password: String::new(),
+                    });
+                    return (AccountUpdate::None, Task::none());
+                }
+                match self.set_active(id) {
+                    Ok(_) => (AccountUpdate::EnterLauncher, Task::none()),
+                    Err(err) => {
+                        self.error = Some(err.to_string());
+                        (AccountUpdate::None, Task::none())
+                    }
+                }
+            }
+            Message::DeleteAccount(id) => match self.remove_account(id) {
+                Ok(_) => {
+                    self.error = None;
+                    self.notice = None;
+                    (AccountUpdate::None, Task::none())
+                }
                 Err(err) => {
                     self.error = Some(err.to_string());
                     (AccountUpdate::None, Task::none())
                 }
             },
-            Message::DeleteAccount(id) => match self.remove_account(id) {
+            Message::ChooseSkin(id) => {
+                let task = Task::perform(
+                    async {
+                        rfd::FileDialog::new()
+                            .add_filter("Skin", &["png"])
+                            .pick_file()
+                    },
+                    move |path| Message::SkinChosen(id, path),
+                );
+                (AccountUpdate::None, task)
+            }
+            Message::SkinChosen(_, None) => (AccountUpdate::None, Task::none()),
+            Message::SkinChosen(id, Some(path)) => {
+                // Checked here so a bad file is reported now, not at launch
+                let result = SkinTexture::load(&path).and_then(|_| {
+                    self.store
+                        .set_offline_skin(&id, Some(&path))
+                        .map_err(|e| e.to_string())
+                });
+                self.error = result.err();
+                (AccountUpdate::None, Task::none())
+            }
+            Message::ClearSkin(id) => {
+                self.error = self
+                    .store
+                    .set_offline_skin(&id, None)
+                    .err()
+                    .map(|e| e.to_string());
+                (AccountUpdate::None, Task::none())
+            }
+            Message::RevokeAccess(id) => match self.remove_account(id) {
                 Ok(_) => {
-                    self.error = None;
+                    self.error = open::that(microsoft_auth::CONSENT_MANAGEMENT_URL)
+                        .err()
+                        .map(|e| {
+                            format!(
+                                "Failed to open {}: {}",
+                                microsoft_auth::CONSENT_MANAGEMENT_URL,
+                                e
+                            )
+                        });
+                    self.notice = Some(
+                        "Signed out on this device. To revoke access everywhere, remove fastMC from the apps listed on the Microsoft page that opened."
+                            .to_string(),
+                    );
                     (AccountUpdate::None, Task::none())
                 }
                 Err(err) => {
@@ -213,6 +482,199 @@ impl AccountScreen {
                     (AccountUpdate::None, Task::none())
                 }
             },
+            Message::ScanImports => {
+                self.error = None;
+                self.import_candidates = Some(Vec::new());
+                let task = Task::perform(
+                    async {
+                        tokio::task::spawn_blocking(account_manager::find_importable)
+                            .await
+                            .unwrap_or_default()
+                    },
+                    Message::ImportsFound,
+                );
+                (AccountUpdate::None, task)
+            }
+            Message::ImportsFound(found) => {
+                self.add_import_candidates(found);
+                (AccountUpdate::None, Task::none())
+            }
+            Message::ImportFromFile => {
+                let task = Task::perform(
+                    async {
+                        let path = rfd::FileDialog::new()
+                            .add_filter("Launcher accounts", &["json"])
+                            .pick_file()?;
+                        Some(account_manager::read_accounts_file(&path).map_err(|e| e.to_string()))
+                    },
+                    Message::ImportFileRead,
+                );
+                (AccountUpdate::None, task)
+            }
+            Message::ImportFileRead(None) => (AccountUpdate::None, Task::none()),
+            Message::ImportFileRead(Some(result)) => {
+                match result {
+                    Ok(found) if found.is_empty() => {
+                        self.error = Some("No accounts found in that file.".to_string());
+                    }
+                    Ok(found) => {
+                        self.error = None;
+                        self.add_import_candidates(found);
+                    }
+                    Err(err) => self.error = Some(format!("Couldn't read that file: {}", err)),
+                }
+                (AccountUpdate::None, Task::none())
+            }
+            Message::ToggleImport(index, selected) => {
+                if let Some(entry) = self
+                    .import_candidates
+                    .as_mut()
+                    .and_then(|candidates| candidates.get_mut(index))
+                {
+                    entry.1 = selected;
+                }
+                (AccountUpdate::None, Task::none())
+            }
+            Message::ImportSelected => {
+                let selected: Vec<ImportedAccount> = self
+                    .import_candidates
+                    .iter()
+                    .flatten()
+                    .filter(|(_, selected)| *selected)
+                    .map(|(account, _)| account.clone())
+                    .collect();
+                if selected.is_empty() {
+                    return (AccountUpdate::None, Task::none());
+                }
+                self.importing = true;
+                let client_id = self.microsoft_client_id.clone();
+                let task = Task::perform(
+                    async move {
+                        // Without a client id, refresh tokens can't be tried
+                        let outcome = match client_id {
+                            Some(client_id) => AccountService::new(client_id)
+                                .map_err(|e| e.to_string())?
+                                .import_accounts(&selected)
+                                .await
+                                .map_err(|e| e.to_string())?,
+                            None => ImportOutcome {
+                                added: AccountStore::load()
+                                    .and_then(|mut store| store.import(&selected))
+                                    .map_err(|e| e.to_string())?,
+                                errors: Vec::new(),
+                            },
+                        };
+                        let store = AccountStore::load().map_err(|e| e.to_string())?;
+                        Ok((outcome, store))
+                    },
+                    |result| Message::ImportFinished(Box::new(result)),
+                );
+                (AccountUpdate::None, task)
+            }
+            Message::CloseImport => {
+                self.import_candidates = None;
+                (AccountUpdate::None, Task::none())
+            }
+            Message::ImportFinished(result) => {
+                self.importing = false;
+                match *result {
+                    Ok((outcome, store)) => {
+                        let signed_out = store.accounts.iter().any(|a| a.requires_login);
+                        self.store = store;
+                        self.import_candidates = None;
+                        self.error = (!outcome.errors.is_empty()).then(|| {
+                            let failures: Vec<String> = outcome
+                                .errors
+                                .iter()
+                                .map(|(name, e)| format!("{} ({})", name, e))
+                                .collect();
+                            format!(
+                                "Couldn't keep the session of {}; sign in again to play.",
+                                failures.join(", ")
+                            )
+                        });
+                        self.notice = Some(if signed_out {
+                            format!(
+                                "Imported {} account(s). Microsoft accounts marked Re-login need to sign in once before they can play.",
+                                outcome.added
+                            )
+                        } else {
+                            format!("Imported {} account(s).", outcome.added)
+                        });
+                    }
+                    Err(err) => self.error = Some(format!("Import failed: {}", err)),
+                }
+                (AccountUpdate::None, Task::none())
+            }
+            Message::ShowCustomLogin => {
+                self.error = None;
+                self.custom_login = Some(CustomLogin::default());
+                (AccountUpdate::None, Task::none())
+            }
+            Message::CustomServerChanged(server) => {
+                if let Some(login) = &mut self.custom_login {
+                    login.server = server;
+                }
+                (AccountUpdate::None, Task::none())
+            }
+            Message::CustomUsernameChanged(username) => {
+                if let Some(login) = &mut self.custom_login {
+                    login.username = username;
+                }
+                (AccountUpdate::None, Task::none())
+            }
+            Message::CustomPasswordChanged(password) => {
+                if let Some(login) = &mut self.custom_login {
+                    login.password = password;
+                }
+                (AccountUpdate::None, Task::none())
+            }
+            Message::AddCustom => {
+                let Some(login) = &self.custom_login else {
+                    return (AccountUpdate::None, Task::none());
+                };
+                if login.server.trim().is_empty() || login.username.trim().is_empty() {
+                    self.error = Some("Enter the server address and your username.".to_string());
+                    return (AccountUpdate::None, Task::none());
+                }
+
+                self.error = None;
+                self.custom_in_progress = true;
+                let mut store = self.store.clone();
+                let server = login.server.clone();
+                let username = login.username.trim().to_string();
+                let password = login.password.clone();
+                let task = Task::perform(
+                    async move {
+                        store
+                            .login_custom(&server, &username, &password)
+                            .await
+                            .map_err(|e| e.to_string())?;
+                        Ok(store)
+                    },
+                    |result| Message::CustomFinished(Box::new(result)),
+                );
+                (AccountUpdate::None, task)
+            }
+            Message::CloseCustomLogin => {
+                self.custom_login = None;
+                (AccountUpdate::None, Task::none())
+            }
+            Message::CustomFinished(result) => {
+                self.custom_in_progress = false;
+                match *result {
+                    Ok(store) => {
+                        self.store = store;
+                        self.custom_login = None;
+                        self.error = None;
+                        (AccountUpdate::EnterLauncher, Task::none())
+                    }
+                    Err(err) => {
+                        self.error = Some(format!("Sign-in failed: {}", err));
+                        (AccountUpdate::None, Task::none())
+                    }
+                }
+            }
             Message::BackToLauncher => {
                 if self.has_accounts() {
                     (AccountUpdate::EnterLauncher, Task::none())
@@ -307,13 +769,8 @@ impl AccountScreen {
             .on_press(Message::AddMicrosoft);
 
         let microsoft_box: Element<'_, Message> = if let Some(code) = &self.device_code {
-            container(
+            let mut instructions = row![
                 column![
-                    text("Waiting for your login...").size(18).style(move |_| {
-                        iced::widget::text::Style {
-                            color: Some(text_primary),
-                        }
-                    }),
                     text(format!(
                         "Use code {} at {}",
                         code.user_code, code.verification_uri
@@ -322,22 +779,49 @@ impl AccountScreen {
                     .style(move |_| iced::widget::text::Style {
                         color: Some(text_muted),
                     }),
-                    if let Some(full) = &code.verification_uri_complete {
-                        text(full)
-                            .size(14)
-                            .style(move |_| iced::widget::text::Style {
-                                color: Some(text_muted),
-                            })
-                    } else {
-                        text("").style(move |_| iced::widget::text::Style {
+                    text("Or scan the QR code to sign in on your phone.")
+                        .size(14)
+                        .style(move |_| iced::widget::text::Style {
                             color: Some(text_muted),
-                        })
-                    },
+                        }),
                     text("The launcher will automatically connect once you finish.")
                         .size(14)
                         .style(move |_| iced::widget::text::Style {
                             color: Some(text_muted),
                         }),
+                    row![
+                        button(text("Copy code").size(14))
+                            .on_press(Message::CopyDeviceCode)
+                            .padding([8, 14])
+                            .style(iced::widget::button::secondary),
+                        button(text("Open in browser").size(14))
+                            .on_press(Message::OpenVerificationPage)
+                            .padding([8, 14])
+                            .style(iced::widget::button::secondary),
+                        button(text("Cancel").size(14))
+                            .on_press(Message::CancelMicrosoft)
+                            .padding([8, 14])
+                            .style(iced::widget::button::secondary),
+                    ]
+                    .spacing(8),
+                ]
+                .spacing(10)
+                .width(Length::Fill),
+            ]
+            .spacing(16)
+            .align_y(Alignment::Center);
+            if let Some(qr) = &self.device_qr {
+                instructions = instructions.push(qr_code(qr).total_size(160.0));
+            }
+
+            container(
+                column![
+                    text("Waiting for your login...").size(18).style(move |_| {
+                        iced::widget::text::Style {
+                            color: Some(text_primary),
+                        }
+                    }),
+                    instructions,
                 ]
                 .spacing(10),
             )
@@ -353,10 +837,12 @@ impl AccountScreen {
             })
             .into()
         } else if self.microsoft_in_progress {
-            container(text("Starting Microsoft login...").style(move |_| {
-                iced::widget::text::Style {
-                    color: Some(text_muted),
-                }
+            let status = match self.login_flow {
+                LoginFlow::DeviceCode => "Starting Microsoft login...",
+                LoginFlow::Browser => "Finish signing in in your browser...",
+            };
+            container(text(status).style(move |_| iced::widget::text::Style {
+                color: Some(text_muted),
             }))
             .padding(12)
             .width(Length::Fill)
@@ -373,6 +859,142 @@ impl AccountScreen {
             container(iced::widget::Space::new()).into()
         };
 
+        let import_box = self.import_candidates.as_ref().map(|candidates| {
+            let listed: Element<'_, Message> = if candidates.is_empty() {
+                text("No accounts found in other launchers yet. Choose their account file to import from a portable install.")
+                    .size(14)
+                    .style(move |_| iced::widget::text::Style {
+                        color: Some(text_muted),
+                    })
+                    .into()
+            } else {
+                column(candidates.iter().enumerate().map(|(index, (account, selected))| {
+                    let kind = match account.kind {
+                        ImportedKind::Offline => "Offline",
+                        ImportedKind::Microsoft { .. } => "Microsoft",
+                    };
+                    row![
+                        checkbox(*selected)
+                            .on_toggle(move |selected| Message::ToggleImport(index, selected))
+                            .size(16),
+                        text(format!("{} · {} · {}", account.username, kind, account.source))
+                            .size(14)
+                            .style(move |_| iced::widget::text::Style {
+                                color: Some(text_primary),
+                            }),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .into()
+                }))
+                .spacing(8)
+                .into()
+            };
+            let any_selected = candidates.iter().any(|(_, selected)| *selected);
+
+            container(
+                column![
+                    text("Import accounts").size(18).style(move |_| {
+                        iced::widget::text::Style {
+                            color: Some(text_primary),
+                        }
+                    }),
+                    listed,
+                    row![
+                        button(text("Import selected").size(14))
+                            .on_press_maybe(
+                                (any_selected && !self.importing).then_some(Message::ImportSelected)
+                            )
+                            .padding([8, 14])
+                            .style(iced::widget::button::primary),
+                        button(text("Choose file...").size(14))
+                            .on_press(Message::ImportFromFile)
+                            .padding([8, 14])
+                            .style(iced::widget::button::secondary),
+                        button(text("Cancel").size(14))
+                            .on_press(Message::CloseImport)
+                            .padding([8, 14])
+                            .style(iced::widget::button::secondary),
+                    ]
+                    .spacing(10),
+                ]
+                .spacing(10),
+            )
+            .padding(16)
+            .width(Length::Fill)
+            .style(move |_| iced::widget::container::Style {
+                background: Some(surface.into()),
+                border: iced::Border {
+                    radius: 12.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            })
+        });
+
+        let custom_box = self.custom_login.as_ref().map(|login| {
+            let can_submit = !self.custom_in_progress
+                && !login.server.trim().is_empty()
+                && !login.username.trim().is_empty();
+            container(
+                column![
+                    text("Sign in to a custom server").size(18).style(move |_| {
+                        iced::widget::text::Style {
+                            color: Some(text_primary),
+                        }
+                    }),
+                    text("Ely.by or a self-hosted Yggdrasil server. The game connects through authlib-injector, and your password is only sent to that server.")
+                        .size(14)
+                        .style(move |_| iced::widget::text::Style {
+                            color: Some(text_muted),
+                        }),
+                    text_input("Server address, e.g. ely.by", &login.server)
+                        .on_input(Message::CustomServerChanged)
+                        .padding([10, 12])
+                        .size(14),
+                    text_input("Username or email", &login.username)
+                        .on_input(Message::CustomUsernameChanged)
+                        .padding([10, 12])
+                        .size(14),
+                    text_input("Password", &login.password)
+                        .on_input(Message::CustomPasswordChanged)
+                        .on_submit_maybe(can_submit.then_some(Message::AddCustom))
+                        .secure(true)
+                        .padding([10, 12])
+                        .size(14),
+                    row![
+                        button(
+                            text(if self.custom_in_progress {
+                                "Signing in..."
+                            } else {
+                                "Sign in"
+                            })
+                            .size(14)
+                        )
+                        .on_press_maybe(can_submit.then_some(Message::AddCustom))
+                        .padding([8, 14])
+                        .style(iced::widget::button::primary),
+                        button(text("Cancel").size(14))
+                            .on_press(Message::CloseCustomLogin)
+                            .padding([8, 14])
+                            .style(iced::widget::button::secondary),
+                    ]
+                    .spacing(10),
+                ]
+                .spacing(10),
+            )
+            .padding(16)
+            .width(Length::Fill)
+            .style(move |_| iced::widget::container::Style {
+                background: Some(surface.into()),
+                border: iced::Border {
+                    radius: 12.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            })
+        });
+
         let error_banner = self.error.as_ref().map(|err| {
             container(
                 text(err)
@@ -430,9 +1052,64 @@ impl AccountScreen {
             .align_x(Alignment::Center)
             .max_width(680);
 
+        if let Some((_, _, texture)) = &self.skin {
+            content = content.push(
+                container(
+                    column![
+                        skin_preview(texture.clone(), 180.0, 240.0),
+                        text("Drag to rotate")
+                            .size(12)
+                            .style(move |_| iced::widget::text::Style {
+                                color: Some(text_muted),
+                            }),
+                    ]
+                    .spacing(8)
+                    .align_x(Alignment::Center),
+                )
+                .padding(16)
+                .width(Length::Fill)
+                .align_x(Alignment::Center)
+                .style(move |_| iced::widget::container::Style {
+                    background: Some(surface.into()),
+                    border: iced::Border {
+                        radius: 12.0.into(),
+                        ..iced::Border::default()
+                    },
+                    ..iced::widget::container::Style::default()
+                }),
+            );
+        }
+
+        if let Some(import_box) = import_box {
+            content = content.push(import_box);
+        }
+        if let Some(custom_box) = custom_box {
+            content = content.push(custom_box);
+        }
         if let Some(error) = error_banner {
             content = content.push(error);
         }
+        if let Some(notice) = &self.notice {
+            content = content.push(
+                container(
+                    text(notice)
+                        .size(14)
+                        .style(move |_| iced::widget::text::Style {
+                            color: Some(text_muted),
+                        }),
+                )
+                .padding([10, 12])
+                .width(Length::Fill)
+                .style(move |_| iced::widget::container::Style {
+                    background: Some(surface.into()),
+                    border: iced::Border {
+                        radius: 10.0.into(),
+                        ..iced::Border::default()
+                    },
+                    ..iced::widget::container::Style::default()
+                }),
+            );
+        }
 
         let back_button =
             button(
@@ -461,7 +1138,29 @@ impl AccountScreen {
 
         let footer = column![
             input_row,
-            row![add_offline, add_microsoft].spacing(12),
+            row![
+                add_offline,
+                add_microsoft,
+                pick_list(
+                    ALL_LOGIN_FLOWS,
+                    Some(self.login_flow),
+                    Message::LoginFlowSelected
+                )
+                .padding([12, 14])
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center),
+            row![
+                button(text("Import from other launchers").size(14))
+                    .on_press(Message::ScanImports)
+                    .padding([8, 14])
+                    .style(iced::widget::button::secondary),
+                button(text("Custom auth server").size(14))
+                    .on_press(Message::ShowCustomLogin)
+                    .padding([8, 14])
+                    .style(iced::widget::button::secondary),
+            ]
+            .spacing(12),
             back_button
         ]
         .spacing(20)
@@ -594,6 +1293,9 @@ impl AccountScreen {
         let subtitle = match &account.kind {
             AccountKind::Microsoft { username, .. } => format!("Microsoft • {username}"),
             AccountKind::Offline { username, .. } => format!("Offline • {username}"),
+            AccountKind::Custom {
+                server, username, ..
+            } => format!("{} • {username}", server_host(server)),
         };
 
         let details = column![
@@ -669,7 +1371,11 @@ impl AccountScreen {
             })
             // Re-login just triggers the AddMicrosoft flow;
             // since we handle upsert, it will update the existing account entry by UUID match.
-            .on_press(Message::AddMicrosoft)
+            // Custom server accounts reopen their sign-in form instead.
+            .on_press(match account.kind {
+                AccountKind::Custom { .. } => Message::SelectAccount(account.id),
+                _ => Message::AddMicrosoft,
+            })
         } else {
             button(row![badge, details].spacing(12).align_y(Alignment::Center))
                 .padding([12, 14])
@@ -705,38 +1411,44 @@ impl AccountScreen {
                 .on_press(Message::SelectAccount(account.id))
         };
 
-        let delete_button = button(text("Delete").style(move |_| iced::widget::text::Style {
-            color: Some(Color::from_rgb(0.96, 0.47, 0.47)),
-        }))
-        .padding([10, 14])
-        .style(move |_theme, status| {
-            let base = Color::from_rgb(0.24, 0.12, 0.12);
-            let hover = Color::from_rgb(0.28, 0.14, 0.14);
-            iced::widget::button::Style {
-                background: Some(
-                    match status {
-                        iced::widget::button::Status::Hovered
-                        | iced::widget::button::Status::Pressed => hover,
-                        _ => base,
-                    }
-                    .into(),
-                ),
-                text_color: Color::from_rgb(0.96, 0.47, 0.47),
-                border: iced::Border {
-                    radius: 10.0.into(),
-                    ..iced::Border::default()
-                },
-                ..iced::widget::button::Style::default()
+        let mut actions = row![select_button].spacing(12).align_y(Alignment::Center);
+        if matches!(account.kind, AccountKind::Offline { .. }) {
+            let has_skin =
+                account_manager::offline_skin_path(&account.id).is_ok_and(|path| path.exists());
+            actions = actions.push(
+                button(text(if has_skin { "Change skin" } else { "Set skin" }).size(14))
+                    .on_press(Message::ChooseSkin(account.id))
+                    .padding([8, 14])
+                    .style(iced::widget::button::secondary),
+            );
+            if has_skin {
+                actions = actions.push(danger_button("Clear skin", Message::ClearSkin(account.id)));
             }
-        })
-        .on_press(Message::DeleteAccount(account.id));
-
-        row![select_button, delete_button]
-            .spacing(12)
-            .align_y(Alignment::Center)
+        }
+        if matches!(account.kind, AccountKind::Microsoft { .. }) {
+            actions = actions.push(danger_button(
+                "Revoke access",
+                Message::RevokeAccess(account.id),
+            ));
+        }
+        actions
+            .push(danger_button("Remove", Message::DeleteAccount(account.id)))
             .into()
     }
 
+    /// Adds accounts to the import panel, skipping ones already known here
+    /// or already listed. New ones start ticked.
+    fn add_import_candidates(&mut self, found: Vec<ImportedAccount>) {
+        let candidates = self.import_candidates.get_or_insert_with(Vec::new);
+        for account in found {
+            if !self.store.has_imported(&account)
+                && !candidates.iter().any(|(listed, _)| listed == &account)
+            {
+                candidates.push((account, true));
+            }
+        }
+    }
+
     fn set_active(&mut self, account_id: Uuid) -> Result<(), AccountError> {
         if self
             .store
@@ -757,7 +1469,9 @@ impl AccountScreen {
             .iter()
             .position(|account| account.id == account_id)
         {
-            if matches!(self.store.accounts[pos].kind, AccountKind::Microsoft { .. }) {
+            if matches!(self.store.accounts[pos].kind, AccountKind::Offline { .. }) {
+                self.store.set_offline_skin(&account_id, None)?;
+            } else {
                 self.store.clear_microsoft_tokens(&account_id)?;
             }
 
@@ -772,3 +1486,40 @@ impl AccountScreen {
         Ok(())
     }
 }
+
+/// A failed Microsoft sign-in, with what to do about it when Xbox Live
+/// refused the account.
+fn login_error(err: AccountError) -> String {
+    match err.remediation() {
+        Some(remediation) => format!("Sign-in failed: {}. {}", err, remediation),
+        None => err.to_string(),
+    }
+}
+
+fn danger_button(label: &str, on_press: Message) -> iced::widget::Button<'_, Message> {
+    button(text(label).style(move |_| iced::widget::text::Style {
+        color: Some(Color::from_rgb(0.96, 0.47, 0.47)),
+    }))
+    .padding([10, 14])
+    .style(move |_theme, status| {
+        let base = Color::from_rgb(0.24, 0.12, 0.12);
+        let hover = Color::from_rgb(0.28, 0.14, 0.14);
+        iced::widget::button::Style {
+            background: Some(
+                match status {
+                    iced::widget::button::Status::Hovered
+                    | iced::widget::button::Status::Pressed => hover,
+                    _ => base,
+                }
+                .into(),
+            ),
+            text_color: Color::from_rgb(0.96, 0.47, 0.47),
+            border: iced::Border {
+                radius: 10.0.into(),
+                ..iced::Border::default()
+            },
+            ..iced::widget::button::Style::default()
+        }
+    })
+    .on_press(on_press)
+}
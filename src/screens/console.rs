@@ -0,0 +1,271 @@
+use crate::game_output::{self, Line, Stream};
+use crate::instance_manager::InstanceMetadata;
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, text, text_input,
+};
+use iced::{Alignment, Color, Element, Font, Length, Task};
+
+/// Lines rendered at once; the buffer itself keeps more.
+const MAX_VISIBLE_LINES: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    InstancesLoaded(Vec<InstanceMetadata>),
+    /// Opens the console of an instance, e.g. from its card.
+    ShowInstance(String, String),
+    InstanceSelected(ConsoleTarget),
+    SearchChanged(String),
+    ToggleAutoscroll(bool),
+    Copy,
+    Clear,
+    /// Shares the instance's `latest.log`; handled by the parent, which
+    /// knows the account to scrub from it.
+    UploadLog(String),
+    LogUploaded(Result<String, String>),
+    CopyLink(String),
+}
+
+/// Entry of the instance picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleTarget {
+    pub id: String,
+    pub name: String,
+}
+
+impl std::fmt::Display for ConsoleTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+pub struct ConsoleScreen {
+    targets: Vec<ConsoleTarget>,
+    selected: Option<ConsoleTarget>,
+    search: String,
+    autoscroll: bool,
+    uploading: bool,
+    /// Link to the last upload, or why it failed.
+    upload: Option<Result<String, String>>,
+}
+
+impl Default for ConsoleScreen {
+    fn default() -> Self {
+        Self {
+            targets: Vec::new(),
+            selected: None,
+            search: String::new(),
+            autoscroll: true,
+            uploading: false,
+            upload: None,
+        }
+    }
+}
+
+impl ConsoleScreen {
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::InstancesLoaded(instances) => {
+                self.targets = instances
+                    .into_iter()
+                    .map(|inst| ConsoleTarget {
+                        id: inst.id,
+                        name: inst.name,
+                    })
+                    .collect();
+                if self.selected.is_none() {
+                    self.selected = self.targets.first().cloned();
+                }
+                Task::none()
+            }
+            Message::ShowInstance(id, name) => {
+                self.selected = Some(ConsoleTarget { id, name });
+                Task::none()
+            }
+            Message::InstanceSelected(target) => {
+                self.selected = Some(target);
+                Task::none()
+            }
+            Message::SearchChanged(search) => {
+                self.search = search;
+                Task::none()
+            }
+            Message::ToggleAutoscroll(enabled) => {
+                self.autoscroll = enabled;
+                Task::none()
+            }
+            Message::Copy => {
+                let joined = self
+                    .visible_lines()
+                    .into_iter()
+                    .map(|line| line.text)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                iced::clipboard::write(joined)
+            }
+            Message::Clear => {
+                if let Some(target) = &self.selected {
+                    game_output::clear(&target.id);
+                }
+                Task::none()
+            }
+            Message::UploadLog(_) => {
+                self.uploading = true;
+                self.upload = None;
+                Task::none()
+            }
+            Message::LogUploaded(result) => {
+                self.uploading = false;
+                self.upload = Some(result);
+                Task::none()
+            }
+            Message::CopyLink(url) => iced::clipboard::write(url),
+        }
+    }
+
+    /// Lines of the selected instance that match the search, oldest first.
+    fn visible_lines(&self) -> Vec<Line> {
+        let Some(target) = &self.selected else {
+            return Vec::new();
+        };
+        let needle = self.search.trim().to_lowercase();
+        game_output::lines(&target.id)
+            .into_iter()
+            .filter(|line| needle.is_empty() || line.text.to_lowercase().contains(&needle))
+            .collect()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let title = text("Console")
+            .size(28)
+            .style(|_| iced::widget::text::Style {
+                color: Some(Color::WHITE),
+            });
+
+        let picker = pick_list(
+            self.targets.as_slice(),
+            self.selected.clone(),
+            Message::InstanceSelected,
+        )
+        .placeholder("Select Instance")
+        .width(Length::Fixed(200.0));
+
+        let search = text_input("Search output", &self.search)
+            .on_input(Message::SearchChanged)
+            .padding(8)
+            .width(Length::Fixed(260.0));
+
+        let autoscroll = row![
+            checkbox(self.autoscroll)
+                .on_toggle(Message::ToggleAutoscroll)
+                .size(16),
+            text("Autoscroll").size(14).color(Color::WHITE)
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        let copy_btn = button(text("Copy").size(14))
+            .on_press(Message::Copy)
+            .padding([6, 12])
+            .style(iced::widget::button::secondary);
+        let clear_btn = button(text("Clear").size(14))
+            .on_press(Message::Clear)
+            .padding([6, 12])
+            .style(iced::widget::button::secondary);
+
+        let upload_label = if self.uploading {
+            "Uploading..."
+        } else {
+            "Share log"
+        };
+        let mut upload_btn = button(text(upload_label).size(14))
+            .padding([6, 12])
+            .style(iced::widget::button::secondary);
+        if let Some(target) = &self.selected
+            && !self.uploading
+        {
+            upload_btn = upload_btn.on_press(Message::UploadLog(target.id.clone()));
+        }
+
+        let toolbar = row![picker, search, autoscroll, copy_btn, clear_btn, upload_btn]
+            .spacing(10)
+            .align_y(Alignment::Center);
+
+        let upload_status: Element<'_, Message> = match &self.upload {
+            Some(Ok(url)) => row![
+                text(format!("Log uploaded: {}", url))
+                    .size(13)
+                    .color(Color::from_rgb(0.2, 0.8, 0.4)),
+                button(text("Copy link").size(12))
+                    .on_press(Message::CopyLink(url.clone()))
+                    .padding([4, 10])
+                    .style(iced::widget::button::secondary),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into(),
+            Some(Err(e)) => text(e)
+                .size(13)
+                .color(Color::from_rgb(0.95, 0.45, 0.45))
+                .into(),
+            None => column![].into(),
+        };
+
+        let lines = self.visible_lines();
+        let hidden = lines.len().saturating_sub(MAX_VISIBLE_LINES);
+        let output: Element<'_, Message> = if lines.is_empty() {
+            let hint = if self.selected.is_none() {
+                "Select an instance to see its game output."
+            } else if self.search.trim().is_empty() {
+                "No output yet. Launch the instance to see its log here."
+            } else {
+                "No lines match the search."
+            };
+            text(hint)
+                .size(14)
+                .color(Color::from_rgb(0.6, 0.6, 0.6))
+                .into()
+        } else {
+            let mut list = column![].spacing(2);
+            if hidden > 0 {
+                list = list.push(
+                    text(format!("{} earlier lines not shown", hidden))
+                        .size(12)
+                        .color(Color::from_rgb(0.6, 0.6, 0.6)),
+                );
+            }
+            for line in lines.into_iter().skip(hidden) {
+                let color = match line.stream {
+                    Stream::Stdout => Color::from_rgb(0.85, 0.85, 0.85),
+                    Stream::Stderr => Color::from_rgb(0.95, 0.45, 0.45),
+                };
+                list = list.push(text(line.text).size(12).font(Font::MONOSPACE).color(color));
+            }
+            let output = scrollable(list.width(Length::Fill))
+                .width(Length::Fill)
+                .height(Length::Fill);
+            if self.autoscroll {
+                output.anchor_bottom().into()
+            } else {
+                output.into()
+            }
+        };
+
+        let panel = container(output)
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(|_| iced::widget::container::Style {
+                background: Some(Color::from_rgb(0.12, 0.12, 0.14).into()),
+                border: iced::Border {
+                    radius: 6.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            });
+
+        column![title, toolbar, upload_status, panel]
+            .spacing(20)
+            .padding(20)
+            .into()
+    }
+}
@@ -1,9 +1,23 @@
-use crate::instance_manager::{InstanceManager, InstanceMetadata, ModLoader, ALL_LOADERS};
+use crate::activity;
+use crate::backup_import::{self, ImportEvent};
+use crate::format;
+use crate::game::{GameExit, LaunchProgress, LaunchStatus};
+use crate::instance_manager::{
+    ALL_DISPLAY_SERVERS, ALL_LAUNCH_ARCHES, ALL_LOADERS, DisplayServer, HealthIssue,
+    InstanceManager, InstanceMetadata, InstanceStats, InstanceTemplate, LaunchArch, ModLoader,
+};
+use crate::loader_installer::InstallEvent;
+use crate::mod_metadata::{self, ExportFormat, InstalledMod};
+use crate::play_button::{PlayButtonSize, PlayState, play_button};
+use crate::processes;
+use crate::status::Status;
 use iced::widget::{
     button, checkbox, column, container, pick_list, row, scrollable, text, text_input,
 };
 use iced::{Alignment, Color, Element, Length, Task};
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use version_manager::{VersionCapabilities, VersionType, fabric};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -11,23 +25,111 @@ pub enum Message {
     Loaded(Vec<InstanceMetadata>),
     CreateNameChanged(String),
     CreateInstance,
-    InstanceCreated(Result<InstanceMetadata, String>),
+    InstanceCreated(Result<Box<InstanceMetadata>, String>),
+    /// Create an instance from a zipped `.minecraft` folder.
+    ImportBackup,
+    /// The zip chosen to import; `None` when the dialog was dismissed.
+    BackupPicked(Option<PathBuf>),
+    ImportProgress(ImportEvent),
     DeleteInstance(String),
     InstanceDeleted(Result<String, String>),
     VersionsLoaded(Result<Vec<version_manager::VanillaVersion>, String>),
     VersionSelected(Option<String>),
     ToggleSnapshots(bool),
+    /// Show the pre-release alpha and beta versions in the picker.
+    ToggleOldVersions(bool),
+    TemplatesLoaded(Vec<InstanceTemplate>),
+    TemplateSelected(TemplateChoice),
+    SaveAsTemplate(String),
+    TemplateSaved(Result<InstanceTemplate, String>),
     LaunchInstance(String),
-    LaunchFinished(Result<(), String>),
+    /// Launch in a temporary copy of the instance, discarded when the game exits.
+    LaunchEphemeral(String),
+    CancelLaunch(String),
+    ForceStop(String),
+    LaunchProgress(String, LaunchProgress),
+    LaunchFinished(String, Result<GameExit, String>),
+    /// The instance is already running or being launched.
+    LaunchRefused(String),
     OpenJavaSettings(String, String),
+    OpenConsole(String, String),
+    ToggleOfflineMode(String, bool),
+    ToggleTelemetry(String, bool),
+    ToggleHeapDump(String, bool),
+    /// Restarts allowed after crashes; 0 turns them off.
+    RestartOnCrashChanged(String, u32),
+    LaunchArchSelected(String, LaunchArch),
+    DisplayServerSelected(String, DisplayServer),
+    JoinServerChanged(String, String),
+    StatsComputed(String, Result<InstanceStats, String>),
+    HealthChecked(String, Vec<HealthIssue>),
+    ToggleMods(String),
+    ModsLoaded(String, Result<Vec<InstalledMod>, String>),
+    ToggleActivity(String),
+    ExportMods(String, ExportFormat),
+    /// Where the list was saved; `None` when the dialog was dismissed.
+    ModsExported(Result<Option<PathBuf>, String>),
     // Loader messages
     LoaderSelected(String, ModLoader),
     LoaderVersionSelected(String, String),
     InstallLoader(String),
-    LoaderInstalled(Result<String, String>),
-    LoaderVersionsLoaded(String, Result<Vec<String>, String>),
+    /// Installs the instance's saved loader again, or lets the user pick a
+    /// version when none was saved.
+    ReinstallLoader(String),
+    LoaderInstallProgress(String, InstallEvent),
+    CancelLoaderInstall(String),
+    LoaderInstalled(String, Result<(), String>),
+    LoaderVersionsLoaded(String, Result<LoaderVersionList, String>),
+}
+
+/// Loader versions offered for an instance.
+#[derive(Debug, Clone, Default)]
+pub struct LoaderVersionList {
+    pub versions: Vec<String>,
+    /// Fabric meta doesn't list the game version yet, so these are generic
+    /// loader builds paired with freshly published intermediary.
+    pub experimental: bool,
 }
 
+impl From<Vec<String>> for LoaderVersionList {
+    fn from(versions: Vec<String>) -> Self {
+        Self {
+            versions,
+            experimental: false,
+        }
+    }
+}
+
+/// Entry of the template picker next to "Create"; `id` is `None` for a blank
+/// instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateChoice {
+    id: Option<String>,
+    name: String,
+}
+
+impl std::fmt::Display for TemplateChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl TemplateChoice {
+    fn blank() -> Self {
+        Self {
+            id: None,
+            name: "No template".to_string(),
+        }
+    }
+}
+
+type StatsUpdate = (String, Result<InstanceStats, String>);
+type HealthUpdate = (String, Vec<HealthIssue>);
+
+/// Restart limits offered once "Restart on crash" is ticked.
+const RESTART_LIMITS: [u32; 5] = [1, 2, 3, 5, 10];
+const DEFAULT_RESTARTS: u32 = 3;
+
 pub struct InstancesScreen {
     instances: Vec<InstanceMetadata>,
     manager: InstanceManager,
@@ -35,12 +137,27 @@ pub struct InstancesScreen {
     available_versions: Vec<version_manager::VanillaVersion>,
     selected_version: Option<String>,
     show_snapshots: bool,
-    status_msg: Option<String>,
+    show_old_versions: bool,
+    templates: Vec<InstanceTemplate>,
+    selected_template: Option<String>,
+    status: Status,
+    /// Entries extracted and total of the backup being imported.
+    importing: Option<(usize, usize)>,
     // Loader state
     pending_loader: HashMap<String, ModLoader>,
     pending_loader_version: HashMap<String, Option<String>>,
-    available_loader_versions: HashMap<String, Vec<String>>,
-    installing: HashSet<String>,
+    available_loader_versions: HashMap<String, LoaderVersionList>,
+    /// Loader installs in progress and their latest step.
+    installing: HashMap<String, Option<InstallEvent>>,
+    /// Launches in progress, by instance.
+    launches: HashMap<String, LaunchStatus>,
+    scanning: HashSet<String>,
+    /// Problems found by the last health check, by instance.
+    health: HashMap<String, Vec<HealthIssue>>,
+    /// Mod lists of the cards that show them; `None` while loading.
+    open_mods: HashMap<String, Option<Result<Vec<InstalledMod>, String>>>,
+    /// Activity logs of the cards that show them.
+    open_activity: HashMap<String, Vec<activity::Entry>>,
 }
 
 impl InstancesScreen {
@@ -55,11 +172,20 @@ impl InstancesScreen {
             available_versions: Vec::new(),
             selected_version: None,
             show_snapshots: false,
-            status_msg: None,
+            show_old_versions: false,
+            templates: Vec::new(),
+            selected_template: None,
+            status: Status::default(),
+            importing: None,
             pending_loader: HashMap::new(),
             pending_loader_version: HashMap::new(),
             available_loader_versions: HashMap::new(),
-            installing: HashSet::new(),
+            installing: HashMap::new(),
+            launches: HashMap::new(),
+            scanning: HashSet::new(),
+            health: HashMap::new(),
+            open_mods: HashMap::new(),
+            open_activity: HashMap::new(),
         }
     }
 
@@ -76,17 +202,197 @@ impl InstancesScreen {
 
     pub fn refresh(&self) -> Task<Message> {
         let manager = self.manager.clone();
+        let templates_manager = self.manager.clone();
         Task::batch(vec![
-            Task::perform(async move { manager.list_instances() }, Message::Loaded),
+            Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || manager.list_instances())
+                        .await
+                        .unwrap_or_default()
+                },
+                Message::Loaded,
+            ),
+            Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || templates_manager.list_templates())
+                        .await
+                        .unwrap_or_default()
+                },
+                Message::TemplatesLoaded,
+            ),
             self.fetch_versions(),
         ])
     }
 
+    /// Rescans instances whose cached stats are missing or stale. The scan runs
+    /// one instance at a time on a blocking thread and reports each result as
+    /// soon as it is ready, so cards fill in progressively.
+    fn compute_missing_stats(&mut self) -> Task<Message> {
+        let pending: Vec<String> = self
+            .instances
+            .iter()
+            .filter(|inst| !self.scanning.contains(&inst.id))
+            .filter(|inst| inst.stats.as_ref().is_none_or(InstanceStats::is_stale))
+            .map(|inst| inst.id.clone())
+            .collect();
+        if pending.is_empty() {
+            return Task::none();
+        }
+        self.scanning.extend(pending.iter().cloned());
+
+        let manager = self.manager.clone();
+        let scan = iced::stream::channel(
+            16,
+            move |mut output: iced::futures::channel::mpsc::Sender<StatsUpdate>| async move {
+                use iced::futures::SinkExt;
+
+                for id in pending {
+                    let manager = manager.clone();
+                    let scan_id = id.clone();
+                    let stats =
+                        tokio::task::spawn_blocking(move || manager.compute_stats(&scan_id))
+                            .await
+                            .map_err(|e| e.to_string())
+                            .and_then(|res| res.map_err(|e| e.to_string()));
+                    if output.send((id, stats)).await.is_err() {
+                        break;
+                    }
+                }
+            },
+        );
+
+        Task::run(scan, |(id, stats)| Message::StatsComputed(id, stats))
+    }
+
+    /// Runs the health checks for every instance in the background, one at a
+    /// time, reporting each as it finishes.
+    fn check_health(&self) -> Task<Message> {
+        let manager = self.manager.clone();
+        let instances = self.instances.clone();
+        let checks = iced::stream::channel(
+            16,
+            move |mut output: iced::futures::channel::mpsc::Sender<HealthUpdate>| async move {
+                use iced::futures::SinkExt;
+
+                for inst in instances {
+                    let manager = manager.clone();
+                    let id = inst.id.clone();
+                    let issues = tokio::task::spawn_blocking(move || manager.check_health(&inst))
+                        .await
+                        .unwrap_or_default();
+                    if output.send((id, issues)).await.is_err() {
+                        break;
+                    }
+                }
+            },
+        );
+
+        Task::run(checks, |(id, issues)| Message::HealthChecked(id, issues))
+    }
+
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::Refresh => self.refresh(),
             Message::Loaded(instances) => {
                 self.instances = instances;
+                self.health
+                    .retain(|id, _| self.instances.iter().any(|i| &i.id == id));
+                Task::batch([self.compute_missing_stats(), self.check_health()])
+            }
+            Message::ToggleMods(id) => {
+                if self.open_mods.remove(&id).is_some() {
+                    return Task::none();
+                }
+                self.open_mods.insert(id.clone(), None);
+                let manager = self.manager.clone();
+                Task::perform(
+                    async move {
+                        let list_id = id.clone();
+                        let task = tokio::task::spawn_blocking(move || manager.list_mods(&list_id));
+                        let mods = match task.await {
+                            Ok(mods) => mods,
+                            Err(e) => Err(e.to_string()),
+                        };
+                        (id, mods)
+                    },
+                    |(id, mods)| Message::ModsLoaded(id, mods),
+                )
+            }
+            Message::ModsLoaded(id, mods) => {
+                // Ignore lists that were closed while loading
+                if let Some(slot) = self.open_mods.get_mut(&id) {
+                    *slot = Some(mods);
+                }
+                Task::none()
+            }
+            Message::ToggleActivity(id) => {
+                if self.open_activity.remove(&id).is_none() {
+                    let entries = self.manager.activity(&id);
+                    self.open_activity.insert(id, entries);
+                }
+                Task::none()
+            }
+            Message::ExportMods(id, format) => {
+                let Some(Some(Ok(mods))) = self.open_mods.get(&id) else {
+                    return Task::none();
+                };
+                let content = mod_metadata::export_mods(mods, format);
+                let name = self
+                    .instances
+                    .iter()
+                    .find(|i| i.id == id)
+                    .map_or("instance", |i| i.name.as_str());
+                let file_name = format!("{} mods.{}", name, format.extension());
+                Task::perform(
+                    async move {
+                        let Some(path) = rfd::FileDialog::new()
+                            .set_file_name(&file_name)
+                            .add_filter("Mod list", &[format.extension()])
+                            .save_file()
+                        else {
+                            return Ok(None);
+                        };
+                        tokio::fs::write(&path, content)
+                            .await
+                            .map(|_| Some(path))
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::ModsExported,
+                )
+            }
+            Message::ModsExported(result) => {
+                match result {
+                    Ok(Some(path)) => {
+                        self.status
+                            .show(format!("Exported the mod list to {}", path.display()));
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        self.status
+                            .show(format!("Failed to export the mod list: {}", e));
+                    }
+                }
+                Task::none()
+            }
+            Message::HealthChecked(id, issues) => {
+                self.health.insert(id, issues);
+                Task::none()
+            }
+            Message::StatsComputed(id, result) => {
+                self.scanning.remove(&id);
+                match result {
+                    Ok(stats) => {
+                        if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+                            inst.stats = Some(stats);
+                            if let Err(e) = self.manager.save_stats(inst) {
+                                eprintln!("Failed to save stats for {}: {}", id, e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to compute stats for {}: {}", id, e);
+                    }
+                }
                 Task::none()
             }
             Message::CreateNameChanged(name) => {
@@ -103,30 +409,115 @@ impl InstancesScreen {
                     .selected_version
                     .clone()
                     .unwrap_or_else(|| "1.21".to_string());
+                let template = self.selected_template.clone();
                 let manager = self.manager.clone();
 
-                self.status_msg = Some("Creating instance...".to_string());
+                self.status.hold("Creating instance...".to_string());
 
                 Task::perform(
                     async move {
-                        manager
-                            .create_instance(name, version)
-                            .map_err(|e| e.to_string())
+                        tokio::task::spawn_blocking(move || match template {
+                            Some(template_id) => manager.create_from_template(name, &template_id),
+                            None => manager.create_instance(name, version),
+                        })
+                        .await
+                        .map_err(|e| e.to_string())?
+                        .map(Box::new)
+                        .map_err(|e| e.to_string())
                     },
                     Message::InstanceCreated,
                 )
             }
             Message::InstanceCreated(result) => match result {
-                Ok(_) => {
+                Ok(meta) => {
+                    // Templated instances only need their loader installed
+                    if meta.loader != ModLoader::Vanilla && !meta.loader_installed {
+                        self.pending_loader
+                            .insert(meta.id.clone(), meta.loader.clone());
+                        self.pending_loader_version
+                            .insert(meta.id.clone(), meta.loader_version.clone());
+                    }
                     self.create_name.clear();
-                    self.status_msg = Some("Instance created!".to_string());
+                    self.status.show("Instance created!".to_string());
                     self.refresh()
                 }
                 Err(e) => {
-                    self.status_msg = Some(format!("Error: {}", e));
+                    self.status.show(format!("Error: {}", e));
                     Task::none()
                 }
             },
+            Message::ImportBackup => Task::perform(
+                async {
+                    rfd::FileDialog::new()
+                        .add_filter(".minecraft backup", &["zip"])
+                        .pick_file()
+                },
+                Message::BackupPicked,
+            ),
+            Message::BackupPicked(None) => Task::none(),
+            Message::BackupPicked(Some(path)) => {
+                if self.importing.is_some() {
+                    return Task::none();
+                }
+                let name = match self.create_name.trim() {
+                    "" => path
+                        .file_stem()
+                        .map_or("Imported".to_string(), |s| s.to_string_lossy().to_string()),
+                    name => name.to_string(),
+                };
+                self.importing = Some((0, 0));
+                self.status.hold(format!("Importing {}...", path.display()));
+
+                let manager = self.manager.clone();
+                let import = iced::stream::channel(
+                    16,
+                    move |mut output: iced::futures::channel::mpsc::Sender<ImportEvent>| async move {
+                        use iced::futures::SinkExt;
+
+                        let mut progress = output.clone();
+                        let result = tokio::task::spawn_blocking(move || {
+                            backup_import::import(&manager, name, &path, |done, total| {
+                                // Dropped when the UI lags behind; the next one catches up
+                                let _ = progress.try_send(ImportEvent::Extracting { done, total });
+                            })
+                        })
+                        .await
+                        .map_err(|e| e.to_string())
+                        .and_then(|res| res);
+                        let _ = output
+                            .send(ImportEvent::Finished(result.map(Box::new)))
+                            .await;
+                    },
+                );
+                Task::run(import, Message::ImportProgress)
+            }
+            Message::ImportProgress(ImportEvent::Extracting { done, total }) => {
+                self.importing = Some((done, total));
+                Task::none()
+            }
+            Message::ImportProgress(ImportEvent::Finished(result)) => {
+                self.importing = None;
+                match result {
+                    Ok(meta) => {
+                        if meta.loader != ModLoader::Vanilla {
+                            self.pending_loader
+                                .insert(meta.id.clone(), meta.loader.clone());
+                            self.pending_loader_version
+                                .insert(meta.id.clone(), meta.loader_version.clone());
+                        }
+                        self.create_name.clear();
+                        self.status.show(format!(
+                            "Imported \"{}\": Minecraft {} with {}.",
+                            meta.name, meta.game_version, meta.loader
+                        ));
+                        self.refresh()
+                    }
+                    Err(e) => {
+                        self.status.show(format!("Import failed: {}", e));
+                        Task::none()
+                    }
+                }
+            }
             Message::DeleteInstance(id) => {
                 let manager = self.manager.clone();
                 Task::perform(
@@ -139,23 +530,111 @@ impl InstancesScreen {
             }
             Message::InstanceDeleted(result) => match result {
                 Ok(_) => {
-                    self.status_msg = Some("Instance deleted.".to_string());
+                    self.status.show("Instance deleted.".to_string());
                     self.refresh()
                 }
                 Err(e) => {
-                    self.status_msg = Some(format!("Delete error: {}", e));
+                    self.status.show(format!("Delete error: {}", e));
                     Task::none()
                 }
             },
-            Message::LaunchInstance(_) => Task::none(),
+            Message::LaunchInstance(_) | Message::LaunchEphemeral(_) => Task::none(),
+            Message::CancelLaunch(_) => Task::none(),
+            Message::ForceStop(id) => {
+                if processes::kill(&id) {
+                    self.status.show("Stopping the game…".to_string());
+                }
+                Task::none()
+            }
+            Message::LaunchRefused(e) => {
+                self.status.show(e);
+                Task::none()
+            }
             Message::OpenJavaSettings(_, _) => Task::none(),
-            Message::LaunchFinished(result) => {
+            Message::OpenConsole(_, _) => Task::none(),
+            Message::ToggleOfflineMode(id, enabled) => {
+                if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+                    inst.offline_mode = enabled;
+                    if let Err(e) = self.manager.save_instance(inst) {
+                        self.status.show(format!("Failed to save instance: {}", e));
+                    }
+                }
+                Task::none()
+            }
+            Message::ToggleTelemetry(id, disabled) => {
+                if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+                    inst.disable_telemetry = disabled;
+                    if let Err(e) = self.manager.save_instance(inst) {
+                        self.status.show(format!("Failed to save instance: {}", e));
+                    }
+                }
+                Task::none()
+            }
+            Message::ToggleHeapDump(id, enabled) => {
+                if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+                    inst.heap_dump_on_oom = enabled;
+                    if let Err(e) = self.manager.save_instance(inst) {
+                        self.status.show(format!("Failed to save instance: {}", e));
+                    }
+                }
+                Task::none()
+            }
+            Message::RestartOnCrashChanged(id, restarts) => {
+                if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+                    inst.restart_on_crash = restarts;
+                    if let Err(e) = self.manager.save_instance(inst) {
+                        self.status.show(format!("Failed to save instance: {}", e));
+                    }
+                }
+                Task::none()
+            }
+            Message::LaunchArchSelected(id, arch) => {
+                if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+                    inst.launch_arch = arch;
+                    if let Err(e) = self.manager.save_instance(inst) {
+                        self.status.show(format!("Failed to save instance: {}", e));
+                    }
+                }
+                Task::none()
+            }
+            Message::DisplayServerSelected(id, server) => {
+                if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+                    inst.display_server = server;
+                    if let Err(e) = self.manager.save_instance(inst) {
+                        self.status.show(format!("Failed to save instance: {}", e));
+                    }
+                }
+                Task::none()
+            }
+            Message::JoinServerChanged(id, address) => {
+                if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+                    let address = address.trim();
+                    inst.join_server = (!address.is_empty()).then(|| address.to_string());
+                    if let Err(e) = self.manager.save_instance(inst) {
+                        self.status.show(format!("Failed to save instance: {}", e));
+                    }
+                }
+                Task::none()
+            }
+            Message::LaunchProgress(id, event) => {
+                if let Some(status) = self.launches.get_mut(&id) {
+                    status.apply(event);
+                }
+                Task::none()
+            }
+            Message::LaunchFinished(id, result) => {
+                self.launches.remove(&id);
+                let name = self
+                    .instances
+                    .iter()
+                    .find(|i| i.id == id)
+                    .map_or(id.as_str(), |i| i.name.as_str());
                 match result {
-                    Ok(_) => {
-                        self.status_msg = Some("Instance launched!".to_string());
+                    Ok(exit) => {
+                        self.status.show(format!("{} exited ({})", name, exit));
                     }
                     Err(e) => {
-                        self.status_msg = Some(format!("Launch failed: {}", e));
+                        self.status.show(format!("{}: launch failed: {}", name, e));
                     }
                 }
                 Task::none()
@@ -175,7 +654,7 @@ impl InstancesScreen {
                         }
                     }
                     Err(e) => {
-                        self.status_msg = Some(format!("Failed to fetch versions: {}", e));
+                        self.status.show(format!("Failed to fetch versions: {}", e));
                     }
                 }
                 Task::none()
@@ -188,6 +667,55 @@ impl InstancesScreen {
                 self.show_snapshots = show;
                 Task::none()
             }
+            Message::ToggleOldVersions(show) => {
+                self.show_old_versions = show;
+                Task::none()
+            }
+            Message::TemplatesLoaded(templates) => {
+                if let Some(id) = &self.selected_template
+                    && !templates.iter().any(|t| &t.id == id)
+                {
+                    self.selected_template = None;
+                }
+                self.templates = templates;
+                Task::none()
+            }
+            Message::TemplateSelected(choice) => {
+                self.selected_template = choice.id;
+                Task::none()
+            }
+            Message::SaveAsTemplate(id) => {
+                let Some(name) = self
+                    .instances
+                    .iter()
+                    .find(|i| i.id == id)
+                    .map(|i| i.name.clone())
+                else {
+                    return Task::none();
+                };
+                let manager = self.manager.clone();
+                self.status.hold(format!("Saving template \"{}\"...", name));
+                Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || manager.save_as_template(&id, name))
+                            .await
+                            .map_err(|e| e.to_string())?
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::TemplateSaved,
+                )
+            }
+            Message::TemplateSaved(result) => match result {
+                Ok(template) => {
+                    self.status
+                        .show(format!("Saved template \"{}\"", template.name));
+                    self.refresh()
+                }
+                Err(e) => {
+                    self.status.show(format!("Failed to save template: {}", e));
+                    Task::none()
+                }
+            },
             // Loader handling
             Message::LoaderSelected(instance_id, loader) => {
                 self.pending_loader
@@ -212,24 +740,28 @@ impl InstancesScreen {
                     async move {
                         let versions = match loader {
                             ModLoader::Fabric => {
-                                version_manager::fabric::fetch_compatible_loaders(&game_version)
-                                    .await
-                                    .map(|v| v.into_iter().map(|l| l.version).collect())
-                                    .map_err(|e| e.to_string())
+                                fabric_loader_versions(&fabric::FABRIC, &game_version).await
+                            }
+                            ModLoader::LegacyFabric => {
+                                fabric_loader_versions(&fabric::LEGACY_FABRIC, &game_version).await
                             }
                             ModLoader::Quilt => {
-                                version_manager::quilt::fetch_quilt_loaders()
+                                version_manager::quilt::fetch_compatible_loaders(&game_version)
                                     .await
-                                    .map(|v| v.into_iter().map(|l| l.version).collect())
+                                    .map(|v| v.into_iter().map(|l| l.version).collect::<Vec<_>>())
+                                    .map(LoaderVersionList::from)
                             }
                             ModLoader::Forge => {
-                                version_manager::forge::fetch_forge_versions(&game_version).await
+                                version_manager::forge::fetch_forge_versions(&game_version)
+                                    .await
+                                    .map(LoaderVersionList::from)
                             }
                             ModLoader::NeoForge => {
                                 version_manager::neoforge::fetch_neoforge_versions(&game_version)
                                     .await
+                                    .map(LoaderVersionList::from)
                             }
-                            ModLoader::Vanilla => Ok(vec![]),
+                            ModLoader::Vanilla => Ok(LoaderVersionList::default()),
                         };
                         (id, versions)
                     },
@@ -239,12 +771,11 @@ impl InstancesScreen {
             Message::LoaderVersionsLoaded(instance_id, result) => {
                 match result {
                     Ok(versions) => {
-                        self.available_loader_versions
-                            .insert(instance_id, versions);
+                        self.available_loader_versions.insert(instance_id, versions);
                     }
                     Err(e) => {
-                        self.status_msg =
-                            Some(format!("Failed to fetch loader versions: {}", e));
+                        self.status
+                            .show(format!("Failed to fetch loader versions: {}", e));
                     }
                 }
                 Task::none()
@@ -258,21 +789,44 @@ impl InstancesScreen {
                 // Handled by parent (main.rs)
                 Task::none()
             }
-            Message::LoaderInstalled(result) => {
+            Message::ReinstallLoader(id) => {
+                let Some(inst) = self.instances.iter().find(|i| i.id == id) else {
+                    return Task::none();
+                };
+                let loader = inst.loader.clone();
+                match inst.loader_version.clone() {
+                    Some(version) => {
+                        self.pending_loader.insert(id.clone(), loader);
+                        self.pending_loader_version
+                            .insert(id.clone(), Some(version));
+                        Task::done(Message::InstallLoader(id))
+                    }
+                    None => Task::done(Message::LoaderSelected(id, loader)),
+                }
+            }
+            Message::LoaderInstallProgress(id, event) => {
+                if let Some(step) = self.installing.get_mut(&id) {
+                    *step = Some(event);
+                }
+                Task::none()
+            }
+            Message::CancelLoaderInstall(_instance_id) => {
+                // Handled by parent (main.rs)
+                Task::none()
+            }
+            Message::LoaderInstalled(id, result) => {
+                self.installing.remove(&id);
                 match result {
-                    Ok(ref id) => {
-                        self.installing.remove(id);
-                        self.pending_loader.remove(id);
-                        self.pending_loader_version.remove(id);
-                        self.available_loader_versions.remove(id);
-                        self.status_msg = Some("Loader installed successfully!".to_string());
+                    Ok(()) => {
+                        self.pending_loader.remove(&id);
+                        self.pending_loader_version.remove(&id);
+                        self.available_loader_versions.remove(&id);
+                        self.status
+                            .show("Loader installed successfully!".to_string());
                         return self.refresh();
                     }
-                    Err(ref e) => {
-                        self.status_msg = Some(format!("Loader install failed: {}", e));
-                        // Try to find which instance was installing and remove from set
-                        // We can't easily know, so just clear all
-                        self.installing.clear();
+                    Err(e) => {
+                        self.status.show(format!("Loader install failed: {}", e));
                     }
                 }
                 Task::none()
@@ -280,8 +834,21 @@ impl InstancesScreen {
         }
     }
 
+    pub fn status_expiring(&self) -> bool {
+        self.status.expiring()
+    }
+
+    pub fn expire_status(&mut self, now: std::time::Instant) {
+        self.status.expire(now);
+    }
+
     pub fn mark_installing(&mut self, id: &str) {
-        self.installing.insert(id.to_string());
+        self.installing.insert(id.to_string(), None);
+    }
+
+    pub fn mark_launching(&mut self, id: &str) {
+        self.launches
+            .insert(id.to_string(), LaunchStatus::default());
     }
 
     pub fn get_pending_loader(&self, id: &str) -> Option<&ModLoader> {
@@ -310,7 +877,12 @@ impl InstancesScreen {
         let version_list: Vec<String> = self
             .available_versions
             .iter()
-            .filter(|v| self.show_snapshots || v.type_ == version_manager::VersionType::Release)
+            .filter(|v| match v.type_ {
+                VersionType::Release => true,
+                VersionType::Snapshot => self.show_snapshots,
+                VersionType::OldAlpha | VersionType::OldBeta => self.show_old_versions,
+                VersionType::Unknown => false,
+            })
             .map(|v| v.id.clone())
             .collect();
 
@@ -336,12 +908,62 @@ impl InstancesScreen {
         .spacing(8)
         .align_y(Alignment::Center);
 
-        let create_row = row![create_input, version_picker, snapshot_toggle, create_btn]
-            .spacing(10)
-            .align_y(Alignment::Center);
+        let old_versions_toggle = row![
+            checkbox(self.show_old_versions)
+                .on_toggle(Message::ToggleOldVersions)
+                .size(16),
+            text("Show Alpha/Beta").size(14).color(Color::WHITE)
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        let template_choices: Vec<TemplateChoice> = std::iter::once(TemplateChoice::blank())
+            .chain(self.templates.iter().map(|t| TemplateChoice {
+                id: Some(t.id.clone()),
+                name: t.name.clone(),
+            }))
+            .collect();
+        let selected_choice = template_choices
+            .iter()
+            .find(|choice| choice.id == self.selected_template)
+            .cloned();
+        let template_picker =
+            pick_list(template_choices, selected_choice, Message::TemplateSelected)
+                .width(Length::Fixed(180.0));
+
+        // A template brings its own game version
+        let create_row = if self.selected_template.is_some() {
+            row![create_input, template_picker, create_btn]
+        } else {
+            row![
+                create_input,
+                template_picker,
+                version_picker,
+                snapshot_toggle,
+                old_versions_toggle,
+                create_btn
+            ]
+        }
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let import_btn = button(text("Import .minecraft backup"))
+            .on_press_maybe(self.importing.is_none().then_some(Message::ImportBackup))
+            .padding(10);
+        let import_row = match self.importing {
+            Some((done, total)) if total > 0 => row![
+                import_btn,
+                text(format!("Extracting {}/{} files", done, total))
+                    .size(14)
+                    .color(Color::WHITE)
+            ],
+            _ => row![import_btn],
+        }
+        .spacing(10)
+        .align_y(Alignment::Center);
 
         // Status
-        let status = if let Some(msg) = &self.status_msg {
+        let status = if let Some(msg) = self.status.get() {
             text(msg).size(14).color(Color::from_rgb(0.8, 0.8, 0.8))
         } else {
             text("")
@@ -366,28 +988,62 @@ impl InstancesScreen {
             .spacing(10)
         };
 
-        let content = column![title, create_row, status, scrollable(list_content)]
-            .spacing(20)
-            .padding(20);
+        let content = column![
+            title,
+            create_row,
+            import_row,
+            status,
+            scrollable(list_content)
+        ]
+        .spacing(20)
+        .padding(20);
 
         content.into()
     }
 
     fn instance_card<'a>(&'a self, inst: &'a InstanceMetadata) -> Element<'a, Message> {
-        let loader_label = if inst.loader_installed && inst.loader != ModLoader::Vanilla {
-            format!("{} (installed)", inst.loader)
-        } else {
-            format!("{:?}", inst.loader)
-        };
+        let mut badges = row![].spacing(4);
+        if inst.loader != ModLoader::Vanilla {
+            let label = match &inst.loader_version {
+                Some(version) if inst.loader_installed => format!("{} {}", inst.loader, version),
+                _ => inst.loader.to_string(),
+            };
+            badges = badges.push(badge(label));
+        }
+        badges = badges.push(badge(format!("MC {}", inst.game_version)));
+        if let Some(stats) = &inst.stats {
+            if stats.mod_count > 0 {
+                let noun = if stats.mod_count == 1 { "mod" } else { "mods" };
+                badges = badges.push(badge(format!("{} {}", stats.mod_count, noun)));
+            }
+            badges = badges.push(badge(format::size(stats.size_bytes)));
+        } else if self.scanning.contains(&inst.id) {
+            badges = badges.push(badge("Scanning…".to_string()));
+        }
 
-        let info = column![
-            text(&inst.name).size(18).color(Color::WHITE),
-            text(format!("{} • {}", inst.game_version, loader_label))
-                .size(12)
-                .color(Color::from_rgb(0.6, 0.6, 0.6))
-        ];
+        if inst.missing_assets > 0 {
+            badges = badges.push(
+                container(
+                    text(format!(
+                        "Incomplete: {} assets missing",
+                        inst.missing_assets
+                    ))
+                    .size(11)
+                    .color(Color::from_rgb(0.96, 0.47, 0.47)),
+                )
+                .padding([2, 6]),
+            );
+        }
+        if inst.last_played > 0 {
+            badges = badges.push(badge(format!(
+                "Played {}",
+                format::relative_time(inst.last_played).to_lowercase()
+            )));
+        }
+
+        let info = column![text(&inst.name).size(18).color(Color::WHITE), badges].spacing(4);
 
-        let is_installing = self.installing.contains(&inst.id);
+        let install_step = self.installing.get(&inst.id);
 
         // Loader picker row
         let loader_options: Vec<ModLoader> = ALL_LOADERS.to_vec();
@@ -409,9 +1065,12 @@ impl InstancesScreen {
 
         // Loader version picker
         let loader_version_picker: Element<'_, Message> =
-            if let Some(versions) = self.available_loader_versions.get(&inst.id) {
-                if versions.is_empty() {
-                    text("No versions").size(12).color(Color::from_rgb(0.5, 0.5, 0.5)).into()
+            if let Some(list) = self.available_loader_versions.get(&inst.id) {
+                if list.versions.is_empty() {
+                    text("No versions")
+                        .size(12)
+                        .color(Color::from_rgb(0.5, 0.5, 0.5))
+                        .into()
                 } else {
                     let selected = self
                         .pending_loader_version
@@ -419,7 +1078,7 @@ impl InstancesScreen {
                         .and_then(|v| v.clone());
                     let id = inst.id.clone();
                     pick_list(
-                        std::borrow::Cow::Owned(versions.clone()),
+                        std::borrow::Cow::Owned(list.versions.clone()),
                         selected,
                         move |v| Message::LoaderVersionSelected(id.clone(), v),
                     )
@@ -436,11 +1095,28 @@ impl InstancesScreen {
             };
 
         // Install button
-        let install_btn: Element<'_, Message> = if is_installing {
-            text("Installing...")
-                .size(12)
-                .color(Color::from_rgb(0.9, 0.7, 0.2))
-                .into()
+        let install_btn: Element<'_, Message> = if let Some(step) = install_step {
+            let status = match step {
+                None | Some(InstallEvent::Done) => "Installing...".to_string(),
+                Some(InstallEvent::DownloadingInstaller) => "Downloading installer...".to_string(),
+                Some(InstallEvent::DownloadingLibraries) => "Downloading libraries...".to_string(),
+                Some(InstallEvent::Extracting { done, total }) => {
+                    format!("Extracting installer {}/{}...", done, total)
+                }
+                Some(InstallEvent::RunningProcessor { index, total }) => {
+                    format!("Running processor {}/{}...", index, total)
+                }
+            };
+            row![
+                text(status).size(12).color(Color::from_rgb(0.9, 0.7, 0.2)),
+                button(text("Cancel").size(12))
+                    .on_press(Message::CancelLoaderInstall(inst.id.clone()))
+                    .padding([5, 10])
+                    .style(iced::widget::button::secondary),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .into()
         } else if inst.loader_installed && inst.loader != ModLoader::Vanilla {
             text("Installed")
                 .size(12)
@@ -470,42 +1146,470 @@ impl InstancesScreen {
             .padding([5, 10])
             .style(iced::widget::button::secondary);
 
+        let console_btn = button(text("Console").size(12))
+            .on_press(Message::OpenConsole(inst.id.clone(), inst.name.clone()))
+            .padding([5, 10])
+            .style(iced::widget::button::secondary);
+
         let delete_btn = button(text("Delete").size(12))
             .on_press(Message::DeleteInstance(inst.id.clone()))
             .padding([5, 10])
             .style(iced::widget::button::danger);
 
-        let launch_btn = button(text("Launch").size(12))
-            .on_press(Message::LaunchInstance(inst.id.clone()))
-            .padding([5, 10])
-            .style(iced::widget::button::success);
+        let launch_label = if inst.missing_assets > 0 {
+            "Repair"
+        } else {
+            "Launch"
+        };
+        let launch_status = self.launches.get(&inst.id);
+        let launch_btn = play_button(
+            launch_status.map_or(PlayState::Idle, PlayState::from),
+            launch_label,
+            PlayButtonSize::Card,
+            launch_status
+                .is_none()
+                .then(|| Message::LaunchInstance(inst.id.clone())),
+            Message::CancelLaunch(inst.id.clone()),
+        );
 
-        let loader_row = row![loader_picker, loader_version_picker, install_btn]
-            .spacing(6)
-            .align_y(Alignment::Center);
+        // Games started from another screen are only known to the registry
+        let force_stop_btn = processes::running_since(&inst.id)
+            .filter(|_| launch_status.is_none())
+            .map(|since| {
+                let mut label = format!("Running {}", format::duration(since.elapsed()));
+                if let Some(pid) = processes::pid(&inst.id) {
+                    label.push_str(&format!(" · PID {}", pid));
+                }
+                row![
+                    badge(label),
+                    button(text("Force stop").size(12))
+                        .on_press(Message::ForceStop(inst.id.clone()))
+                        .padding([5, 10])
+                        .style(iced::widget::button::danger)
+                ]
+                .spacing(6)
+                .align_y(Alignment::Center)
+            });
 
-        let left = column![info, loader_row].spacing(6);
+        let offline_toggle = row![
+            checkbox(inst.offline_mode)
+                .on_toggle({
+                    let id = inst.id.clone();
+                    move |enabled| Message::ToggleOfflineMode(id.clone(), enabled)
+                })
+                .size(14),
+            text("Offline")
+                .size(12)
+                .color(Color::from_rgb(0.6, 0.6, 0.6))
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
 
-        container(
-            row![
-                left,
-                iced::widget::Space::new().width(Length::Fill),
-                java_btn,
-                launch_btn,
-                delete_btn
-            ]
-            .spacing(10)
-            .align_y(Alignment::Center),
+        let telemetry_toggle = row![
+            checkbox(inst.disable_telemetry)
+                .on_toggle({
+                    let id = inst.id.clone();
+                    move |disabled| Message::ToggleTelemetry(id.clone(), disabled)
+                })
+                .size(14),
+            text("No telemetry")
+                .size(12)
+                .color(Color::from_rgb(0.6, 0.6, 0.6))
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        let heap_dump_toggle = row![
+            checkbox(inst.heap_dump_on_oom)
+                .on_toggle({
+                    let id = inst.id.clone();
+                    move |enabled| Message::ToggleHeapDump(id.clone(), enabled)
+                })
+                .size(14),
+            text("Heap dump on OOM")
+                .size(12)
+                .color(Color::from_rgb(0.6, 0.6, 0.6))
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        let mut restart_toggle = row![
+            checkbox(inst.restart_on_crash > 0)
+                .on_toggle({
+                    let id = inst.id.clone();
+                    move |enabled| {
+                        Message::RestartOnCrashChanged(
+                            id.clone(),
+                            if enabled { DEFAULT_RESTARTS } else { 0 },
+                        )
+                    }
+                })
+                .size(14),
+            text("Restart on crash")
+                .size(12)
+                .color(Color::from_rgb(0.6, 0.6, 0.6))
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+        if inst.restart_on_crash > 0 {
+            restart_toggle = restart_toggle.push(
+                pick_list(RESTART_LIMITS, Some(inst.restart_on_crash), {
+                    let id = inst.id.clone();
+                    move |restarts| Message::RestartOnCrashChanged(id.clone(), restarts)
+                })
+                .text_size(12)
+                .padding([2, 6]),
+            );
+        }
+
+        let mut loader_row = row![
+            loader_picker,
+            loader_version_picker,
+            install_btn,
+            offline_toggle,
+            telemetry_toggle,
+            heap_dump_toggle,
+            restart_toggle
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center);
+        // Only Apple Silicon and ARM64 Windows/Linux can choose an emulated runtime
+        if crate::game::host_arch() == "aarch64" {
+            loader_row = loader_row.push(
+                pick_list(ALL_LAUNCH_ARCHES, Some(inst.launch_arch), {
+                    let id = inst.id.clone();
+                    move |arch| Message::LaunchArchSelected(id.clone(), arch)
+                })
+                .text_size(12)
+                .padding([4, 8]),
+            );
+        }
+        if cfg!(target_os = "linux") {
+            loader_row = loader_row.push(
+                pick_list(ALL_DISPLAY_SERVERS, Some(inst.display_server), {
+                    let id = inst.id.clone();
+                    move |server| Message::DisplayServerSelected(id.clone(), server)
+                })
+                .text_size(12)
+                .padding([4, 8]),
+            );
+        }
+
+        // Unknown versions (e.g. the list hasn't loaded) aren't restricted
+        let capabilities = self
+            .available_versions
+            .iter()
+            .find(|v| v.id == inst.game_version)
+            .map_or(VersionCapabilities::ALL, |v| {
+                VersionCapabilities::from_release_time(&v.release_time)
+            });
+
+        let join_server = text_input(
+            "Join server on launch",
+            inst.join_server.as_deref().unwrap_or_default(),
         )
-        .padding(10)
-        .style(|_| iced::widget::container::Style {
-            background: Some(Color::from_rgb(0.18, 0.18, 0.20).into()),
-            border: iced::Border {
-                radius: 6.0.into(),
-                ..iced::Border::default()
-            },
-            ..iced::widget::container::Style::default()
-        })
-        .into()
+        .on_input_maybe(capabilities.join_server.then(|| {
+            let id = inst.id.clone();
+            move |address| Message::JoinServerChanged(id.clone(), address)
+        }))
+        .size(12)
+        .padding([4, 8])
+        .width(Length::Fixed(220.0));
+
+        let template_btn = button(text("Save as template").size(12))
+            .on_press(Message::SaveAsTemplate(inst.id.clone()))
+            .padding([4, 8])
+            .style(iced::widget::button::secondary);
+
+        let mods_open = self.open_mods.contains_key(&inst.id);
+        let mods_btn = button(text(if mods_open { "Hide mods" } else { "Mods" }).size(12))
+            .on_press(Message::ToggleMods(inst.id.clone()))
+            .padding([4, 8])
+            .style(iced::widget::button::secondary);
+
+        let activity_open = self.open_activity.contains_key(&inst.id);
+        let activity_btn = button(
+            text(if activity_open {
+                "Hide activity"
+            } else {
+                "Activity"
+            })
+            .size(12),
+        )
+        .on_press(Message::ToggleActivity(inst.id.clone()))
+        .padding([4, 8])
+        .style(iced::widget::button::secondary);
+
+        let ephemeral_btn = button(text("Ephemeral run").size(12))
+            .on_press_maybe(
+                launch_status
+                    .is_none()
+                    .then(|| Message::LaunchEphemeral(inst.id.clone())),
+            )
+            .padding([4, 8])
+            .style(iced::widget::button::secondary);
+
+        let server_row = row![
+            join_server,
+            template_btn,
+            mods_btn,
+            activity_btn,
+            ephemeral_btn
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center);
+
+        let mut left = column![info, loader_row, server_row].spacing(6);
+        if self
+            .available_loader_versions
+            .get(&inst.id)
+            .is_some_and(|list| list.experimental)
+        {
+            left = left.push(
+                text(format!(
+                    "Experimental: Fabric doesn't list {} yet, so a generic loader will be used. Mods may not work.",
+                    inst.game_version
+                ))
+                .size(11)
+                .color(Color::from_rgb(0.9, 0.7, 0.2)),
+            );
+        }
+
+        if !capabilities.join_server {
+            left = left.push(
+                text(format!(
+                    "{} can't join a server on launch; that needs 1.6 or newer.",
+                    inst.game_version
+                ))
+                .size(11)
+                .color(Color::from_rgb(0.6, 0.6, 0.6)),
+            );
+        }
+        if inst.join_world.is_some() && !capabilities.quick_play {
+            left = left.push(
+                text(format!(
+                    "{} has no Quick Play, so the world won't open on launch. It needs 23w14a or newer.",
+                    inst.game_version
+                ))
+                .size(11)
+                .color(Color::from_rgb(0.9, 0.7, 0.2)),
+            );
+        }
+
+        for issue in self.health.get(&inst.id).into_iter().flatten() {
+            if *issue == HealthIssue::LoaderNotInstalled && install_step.is_some() {
+                continue;
+            }
+            left = left.push(health_warning(inst, issue, mods_open));
+        }
+
+        if let Some(mods) = self.open_mods.get(&inst.id) {
+            left = left.push(mod_list(&inst.id, mods.as_ref()));
+        }
+        if let Some(entries) = self.open_activity.get(&inst.id) {
+            left = left.push(activity_list(entries));
+        }
+
+        let mut card = row![
+            left,
+            iced::widget::Space::new().width(Length::Fill),
+            java_btn,
+            console_btn
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+        if let Some(force_stop_btn) = force_stop_btn {
+            card = card.push(force_stop_btn);
+        }
+
+        container(card.push(launch_btn).push(delete_btn))
+            .padding(10)
+            .style(|_| iced::widget::container::Style {
+                background: Some(Color::from_rgb(0.18, 0.18, 0.20).into()),
+                border: iced::Border {
+                    radius: 6.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            })
+            .into()
     }
 }
+
+/// Fabric loaders compatible with `game_version`. When meta hasn't caught up
+/// with a new release yet but intermediary is already out, falls back to the
+/// stable generic loaders and flags the list as experimental.
+async fn fabric_loader_versions(
+    meta: &fabric::FabricMeta,
+    game_version: &str,
+) -> Result<LoaderVersionList, String> {
+    let compatible = fabric::fetch_compatible_loaders(meta, game_version)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !compatible.is_empty() {
+        return Ok(compatible
+            .into_iter()
+            .map(|l| l.version)
+            .collect::<Vec<_>>()
+            .into());
+    }
+
+    let intermediary = fabric::intermediary_available(meta, game_version)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !intermediary {
+        return Ok(LoaderVersionList::default());
+    }
+
+    let versions = fabric::fetch_fabric_loaders(meta)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|l| l.stable)
+        .map(|l| l.version)
+        .collect();
+    Ok(LoaderVersionList {
+        versions,
+        experimental: true,
+    })
+}
+
+/// One health-check warning with the button that fixes it.
+fn health_warning<'a>(
+    inst: &InstanceMetadata,
+    issue: &HealthIssue,
+    mods_open: bool,
+) -> Element<'a, Message> {
+    let fix = match issue {
+        HealthIssue::MissingJava(_) => Some((
+            "Java settings",
+            Message::OpenJavaSettings(inst.id.clone(), inst.name.clone()),
+        )),
+        HealthIssue::LoaderNotInstalled => {
+            Some(("Install", Message::ReinstallLoader(inst.id.clone())))
+        }
+        HealthIssue::ModsWithoutLoader(_) | HealthIssue::ForeignMods(_) => {
+            (!mods_open).then(|| ("Show mods", Message::ToggleMods(inst.id.clone())))
+        }
+        HealthIssue::IncompleteInstall => {
+            Some(("Repair", Message::LaunchInstance(inst.id.clone())))
+        }
+    };
+
+    let mut warning = row![
+        text(issue.to_string())
+            .size(11)
+            .color(Color::from_rgb(0.9, 0.7, 0.2))
+    ]
+    .spacing(6)
+    .align_y(Alignment::Center);
+    if let Some((label, message)) = fix {
+        warning = warning.push(
+            button(text(label).size(11))
+                .on_press(message)
+                .padding([2, 8])
+                .style(iced::widget::button::secondary),
+        );
+    }
+    warning.into()
+}
+
+fn mod_list<'a>(
+    id: &str,
+    mods: Option<&'a Result<Vec<InstalledMod>, String>>,
+) -> Element<'a, Message> {
+    let muted = Color::from_rgb(0.6, 0.6, 0.6);
+    match mods {
+        None => text("Reading mods…").size(12).color(muted).into(),
+        Some(Err(e)) => text(format!("Failed to read mods: {}", e))
+            .size(12)
+            .color(Color::from_rgb(0.9, 0.4, 0.4))
+            .into(),
+        Some(Ok(mods)) if mods.is_empty() => {
+            text("No mods installed.").size(12).color(muted).into()
+        }
+        Some(Ok(mods)) => {
+            let export = |label: &'static str, format: ExportFormat| {
+                button(text(label).size(11))
+                    .on_press(Message::ExportMods(id.to_string(), format))
+                    .padding([3, 8])
+                    .style(iced::widget::button::secondary)
+            };
+            let actions = row![
+                export("Export Markdown", ExportFormat::Markdown),
+                export("Export CSV", ExportFormat::Csv)
+            ]
+            .spacing(6);
+            let list = column(mods.iter().map(|m| {
+                let icon: Element<'a, Message> = match &m.info.icon {
+                    Some(path) => iced::widget::image(path.clone())
+                        .width(Length::Fixed(20.0))
+                        .height(Length::Fixed(20.0))
+                        .into(),
+                    None => iced::widget::Space::new()
+                        .width(Length::Fixed(20.0))
+                        .height(Length::Fixed(20.0))
+                        .into(),
+                };
+                row![
+                    icon,
+                    text(&m.info.name).size(12).color(Color::WHITE),
+                    text(m.info.version.as_deref().unwrap_or_default())
+                        .size(12)
+                        .color(muted),
+                    text(&m.file_name).size(11).color(muted)
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center)
+                .into()
+            }))
+            .spacing(4);
+            column![actions, list].spacing(6).into()
+        }
+    }
+}
+
+/// Most recent entries of an activity log, newest first.
+fn activity_list<'a>(entries: &[activity::Entry]) -> Element<'a, Message> {
+    const SHOWN: usize = 30;
+    let muted = Color::from_rgb(0.6, 0.6, 0.6);
+    if entries.is_empty() {
+        return text("No activity recorded yet.")
+            .size(12)
+            .color(muted)
+            .into();
+    }
+    column(entries.iter().rev().take(SHOWN).map(|entry| {
+        row![
+            text(format::relative_time(entry.at))
+                .size(11)
+                .color(muted)
+                .width(Length::Fixed(90.0)),
+            text(entry.activity.to_string())
+                .size(12)
+                .color(Color::WHITE)
+        ]
+        .spacing(8)
+        .into()
+    }))
+    .spacing(4)
+    .into()
+}
+
+fn badge<'a>(label: String) -> Element<'a, Message> {
+    container(
+        text(label)
+            .size(11)
+            .color(Color::from_rgb(0.75, 0.75, 0.78)),
+    )
+    .padding([2, 6])
+    .style(|_| iced::widget::container::Style {
+        background: Some(Color::from_rgb(0.24, 0.24, 0.27).into()),
+        border: iced::Border {
+            radius: 4.0.into(),
+            ..iced::Border::default()
+        },
+        ..iced::widget::container::Style::default()
+    })
+    .into()
+}
@@ -1,20 +1,26 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
 use config_manager::{FastmcConfig, JavaInstallationRecord};
+use downloader::CancellationToken;
 use iced::widget::{
     Space, button, column, container, pick_list, row, scrollable, slider, text, text_editor,
     text_input,
 };
 use iced::{Alignment, Color, Element, Length, Task};
 use java_manager::{
-    DetectionSummary, InstallSource, JavaDetectionConfig, JavaInstallation, JavaLaunchSettings,
-    detect_installations,
+    ALL_PRESETS, DetectionSummary, InstallSource, InstalledRuntime, JavaDetectionConfig,
+    JavaInstallation, JavaLaunchSettings, JvmPreset, MemoryCheck, Workload, detect_installations,
 };
-use std::time::{Duration, Instant};
+use std::time::Instant;
 use uuid::Uuid;
 
-use crate::instance_manager::{InstanceManager, InstanceMetadata};
+use crate::format;
+use crate::game::{self, downloader};
+use crate::instance_manager::{InstanceManager, InstanceMetadata, ModLoader};
+use crate::processes;
+use crate::status::Status;
 
 const MIN_MEMORY_BOUND: u32 = 512;
 const MAX_MEMORY_BOUND: u32 = 16384;
@@ -51,14 +57,17 @@ pub enum Message {
     DetectionFinished(DetectionSummary),
     Tick,
     Resized(f32),
-    ClearStatus(Instant),
     SelectInstallation(Uuid),
     RemoveInstallation(Uuid),
+    TestInstallation(Uuid),
+    TestFinished(Uuid, Result<JavaInstallation, String>),
     ToggleCustomForm,
     MinMemoryChanged(f32),
     MaxMemoryChanged(f32),
+    RecommendMemory,
     ExtraArgsEdited(text_editor::Action),
     SaveArgs,
+    PresetSelected(JvmPreset),
     CustomPathChanged(String),
     BrowseForJava,
     BrowseFinished(Option<PathBuf>),
@@ -68,6 +77,12 @@ pub enum Message {
     InstancesLoaded(Vec<InstanceMetadata>),
     ScopeToInstance(String, String),
     ClearOverride(OverrideField),
+    // Runtimes installed from Mojang's manifests
+    RuntimesLoaded(Vec<InstalledRuntime>),
+    UpdateRuntime(PathBuf),
+    RuntimeUpdated(Result<String, String>),
+    RemoveRuntime(PathBuf),
+    RuntimeRemoved(Result<String, String>),
 }
 
 pub struct JavaManagerScreen {
@@ -79,13 +94,19 @@ pub struct JavaManagerScreen {
     custom_path_input: String,
     show_custom_form: bool,
     is_wide: bool,
-    status: Option<(String, Color, Instant)>,
+    status: Status<(String, Color)>,
     // Instance-awareness
     target: JavaTarget,
     available_targets: Vec<TargetOption>,
     global_settings: JavaLaunchSettings,
     instance_metadata: Option<InstanceMetadata>,
     instance_manager: InstanceManager,
+    total_memory_mb: Option<u64>,
+    /// Outcome of "Test" per installation; `None` while it runs.
+    java_tests: HashMap<Uuid, Option<Result<String, String>>>,
+    managed_runtimes: Vec<InstalledRuntime>,
+    /// Folder of the managed runtime being updated or removed.
+    runtime_busy: Option<PathBuf>,
 }
 
 impl Default for JavaManagerScreen {
@@ -124,12 +145,16 @@ impl JavaManagerScreen {
             custom_path_input,
             show_custom_form: false,
             is_wide: false,
-            status: None,
+            status: Status::default(),
             target: JavaTarget::Global,
             available_targets,
             global_settings,
             instance_metadata: None,
             instance_manager,
+            total_memory_mb: java_manager::total_memory_mb(),
+            java_tests: HashMap::new(),
+            managed_runtimes: Vec::new(),
+            runtime_busy: None,
         };
         screen.installations.append(&mut installations);
         screen.ensure_selected_entry();
@@ -159,6 +184,7 @@ impl JavaManagerScreen {
                         auto_discover: meta
                             .auto_discover
                             .unwrap_or(self.global_settings.auto_discover),
+                        mojang_runtimes: self.global_settings.mojang_runtimes,
                         min_memory_mb: meta
                             .min_memory_mb
                             .unwrap_or(self.global_settings.min_memory_mb),
@@ -372,7 +398,7 @@ impl JavaManagerScreen {
             ..iced::widget::container::Style::default()
         });
 
-        let status_banner = self.status.as_ref().map(|(msg, tone, _)| {
+        let status_banner = self.status.get().map(|(msg, tone)| {
             container(
                 row![
                     text(msg.as_str())
@@ -757,13 +783,54 @@ impl JavaManagerScreen {
             text_primary
         };
 
+        // Same check as at launch, against the selected Java when it is known
+        let java_64_bit = self.settings.java_path.as_ref().and_then(|path| {
+            let normalized = normalize_path(path);
+            self.installations
+                .iter()
+                .find(|inst| normalize_path(&inst.path) == normalized)
+                .and_then(|inst| inst.is_64_bit)
+        });
+        let memory_check = self
+            .settings
+            .memory_check(self.total_memory_mb, java_64_bit);
+        let memory_verdict: Element<'_, Message> = match memory_check {
+            MemoryCheck::Ok => match self.total_memory_mb {
+                Some(total) => text(format!("System memory: {} MB", total))
+                    .size(13)
+                    .style(move |_| iced::widget::text::Style {
+                        color: Some(text_muted),
+                    })
+                    .into(),
+                None => Space::new().into(),
+            },
+            MemoryCheck::Warning(reason) => text(reason)
+                .size(13)
+                .color(Color::from_rgb(0.98, 0.75, 0.30))
+                .into(),
+            MemoryCheck::Denied(reason) => text(format!("{} — launching is blocked", reason))
+                .size(13)
+                .color(Color::from_rgb(0.96, 0.47, 0.47))
+                .into(),
+        };
+
+        let recommend_button = button(text("Recommend for me").size(14))
+            .padding([8, 12])
+            .style(iced::widget::button::secondary)
+            .on_press(Message::RecommendMemory);
+
         let memory_controls = container(
             column![
-                text("Memory Allocation")
-                    .size(20)
-                    .style(move |_| iced::widget::text::Style {
-                        color: Some(text_primary),
-                    }),
+                row![
+                    text("Memory Allocation")
+                        .size(20)
+                        .style(move |_| iced::widget::text::Style {
+                            color: Some(text_primary),
+                        }),
+                    Space::new().width(Length::Fill),
+                    recommend_button,
+                ]
+                .align_y(Alignment::Center),
                 text("Adjust how much RAM Java can use for the launcher.")
                     .size(14)
                     .style(move |_| iced::widget::text::Style {
@@ -809,7 +876,8 @@ impl JavaManagerScreen {
                         .size(13)
                         .style(move |_| iced::widget::text::Style {
                             color: Some(text_muted),
-                        })
+                        }),
+                    memory_verdict
                 ]
                 .spacing(12)
             ]
@@ -859,8 +927,17 @@ impl JavaManagerScreen {
         })
         .on_press(Message::SaveArgs);
 
+        let preset_picker = pick_list(
+            ALL_PRESETS,
+            self.settings.active_preset(),
+            Message::PresetSelected,
+        )
+        .placeholder("Apply GC preset")
+        .width(Length::Fixed(220.0));
+
         let jvm_args_overridden = self.is_field_overridden(&OverrideField::JvmArgs);
-        let jvm_args_indicator = self.inherited_indicator(OverrideField::JvmArgs, jvm_args_overridden);
+        let jvm_args_indicator =
+            self.inherited_indicator(OverrideField::JvmArgs, jvm_args_overridden);
 
         let args_title_color = if !matches!(self.target, JavaTarget::Global) && !jvm_args_overridden
         {
@@ -887,7 +964,9 @@ impl JavaManagerScreen {
                         color: Some(text_muted),
                     }),
                 args_editor,
-                row![save_args].align_y(Alignment::Center)
+                row![save_args, preset_picker]
+                    .spacing(10)
+                    .align_y(Alignment::Center)
             ]
             .spacing(10),
         )
@@ -902,7 +981,7 @@ impl JavaManagerScreen {
             ..iced::widget::container::Style::default()
         });
 
-        let layout = column![
+        let mut layout = column![
             heading,
             target_section,
             info,
@@ -923,6 +1002,9 @@ impl JavaManagerScreen {
         .spacing(14)
         .align_x(Alignment::Center)
         .max_width(1280);
+        if matches!(self.target, JavaTarget::Global) {
+            layout = layout.push(self.runtimes_section(text_primary, text_muted, surface));
+        }
 
         let mut content = column![layout]
             .spacing(10)
@@ -1014,8 +1096,62 @@ impl JavaManagerScreen {
             }
             Message::InstancesLoaded(instances) => {
                 self.rebuild_target_options(&instances);
+                load_runtimes()
+            }
+            Message::RuntimesLoaded(runtimes) => {
+                self.managed_runtimes = runtimes;
                 Task::none()
             }
+            Message::UpdateRuntime(path) => {
+                let Some(runtime) = self.managed_runtime(&path) else {
+                    return Task::none();
+                };
+                self.runtime_busy = Some(path);
+                Task::perform(
+                    async move {
+                        java_manager::update_runtime(
+                            &downloader(),
+                            &runtime,
+                            &CancellationToken::new(),
+                        )
+                        .await
+                        .map(|updated| {
+                            format!("{} is up to date ({})", updated.component, updated.version)
+                        })
+                        .map_err(|e| format!("Couldn't update {}: {}", runtime.component, e))
+                    },
+                    Message::RuntimeUpdated,
+                )
+            }
+            Message::RemoveRuntime(path) => {
+                let Some(runtime) = self.managed_runtime(&path) else {
+                    return Task::none();
+                };
+                if processes::any_running() {
+                    self.push_status(
+                        "Close running games before removing a Java runtime",
+                        Color::from_rgb(0.96, 0.47, 0.47),
+                    );
+                    return Task::none();
+                }
+                self.runtime_busy = Some(path);
+                Task::perform(
+                    async move {
+                        java_manager::remove_runtime(&runtime)
+                            .map(|_| format!("Removed {}", runtime.component))
+                            .map_err(|e| format!("Couldn't remove {}: {}", runtime.component, e))
+                    },
+                    Message::RuntimeRemoved,
+                )
+            }
+            Message::RuntimeUpdated(result) | Message::RuntimeRemoved(result) => {
+                self.runtime_busy = None;
+                match result {
+                    Ok(summary) => self.push_status(&summary, Color::from_rgb(0.13, 0.77, 0.36)),
+                    Err(err) => self.push_status(&err, Color::from_rgb(0.96, 0.47, 0.47)),
+                }
+                load_runtimes()
+            }
             Message::ClearOverride(field) => {
                 if let Some(meta) = &mut self.instance_metadata {
                     match field {
@@ -1046,7 +1182,8 @@ impl JavaManagerScreen {
                             );
                         }
                     }
-                    self.persist_settings("Override cleared — using global default")
+                    self.persist_settings("Override cleared — using global default");
+                    Task::none()
                 } else {
                     Task::none()
                 }
@@ -1054,34 +1191,16 @@ impl JavaManagerScreen {
             Message::DetectJava => {
                 self.detection_in_progress = true;
                 self.detection_errors.clear();
-                self.status = None;
+                self.status = Status::default();
                 let detection_config = self.settings.detection_config();
                 Task::perform(
-                    async move { detect_installations(&detection_config) },
+                    async move { detect_installations(&detection_config).await },
                     Message::DetectionFinished,
                 )
             }
             Message::Tick => Task::none(),
-            Message::ClearStatus(at) => {
-                if let Some((_, _, stored_at)) = &self.status
-                    && *stored_at == at
-                {
-                    self.status = None;
-                }
-                Task::none()
-            }
             Message::Resized(width) => {
                 self.is_wide = width >= 1200.0;
-
-                if let Some((msg, tone, at)) = &self.status {
-                    let elapsed = Instant::now() - *at;
-                    if elapsed.as_secs_f32() > 5.0 {
-                        self.status = None;
-                    } else {
-                        self.status = Some((msg.clone(), *tone, *at));
-                    }
-                }
-
                 Task::none()
             }
             Message::DetectionFinished(summary) => {
@@ -1121,7 +1240,7 @@ impl JavaManagerScreen {
                 self.sync_detected_records();
                 self.ensure_selected_entry();
                 if self.installations.is_empty() && !self.detection_errors.is_empty() {
-                    return self.push_status("No Java found", Color::from_rgb(0.24, 0.12, 0.12));
+                    self.push_status("No Java found", Color::from_rgb(0.24, 0.12, 0.12));
                 }
                 Task::none()
             }
@@ -1132,8 +1251,48 @@ impl JavaManagerScreen {
                     if matches!(self.target, JavaTarget::Instance(_)) {
                         self.mark_field_overridden(&OverrideField::JavaPath);
                     }
-                    return self.persist_settings("Java selection saved");
+                    self.persist_settings("Java selection saved");
+                    return Task::none();
+                }
+                Task::none()
+            }
+            Message::TestInstallation(id) => {
+                let Some(install) = self.installations.iter().find(|inst| inst.id == id) else {
+                    return Task::none();
+                };
+                let (path, source) = (install.path.clone(), install.source);
+                self.java_tests.insert(id, None);
+                Task::perform(
+                    async move {
+                        java_manager::test_java(&path, source)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    move |result| Message::TestFinished(id, result),
+                )
+            }
+            Message::TestFinished(id, result) => {
+                let outcome = result.map(|found| {
+                    let mut summary = format!(
+                        "Works: Java {}",
+                        found.version.as_deref().unwrap_or_default()
+                    );
+                    for detail in [&found.vendor, &found.arch].into_iter().flatten() {
+                        summary = format!("{} · {}", summary, detail);
+                    }
+                    if let Some(install) = self.installations.iter_mut().find(|inst| inst.id == id)
+                    {
+                        install.version = found.version;
+                        install.vendor = found.vendor.or(install.vendor.take());
+                        install.is_64_bit = found.is_64_bit;
+                        install.arch = found.arch;
+                    }
+                    summary
+                });
+                if outcome.is_ok() {
+                    self.sync_detected_records();
                 }
+                self.java_tests.insert(id, Some(outcome));
                 Task::none()
             }
             Message::RemoveInstallation(id) => {
@@ -1154,7 +1313,8 @@ impl JavaManagerScreen {
                     {
                         self.settings.java_path = None;
                         self.sync_detected_records();
-                        return self.persist_settings("Cleared Java selection");
+                        self.persist_settings("Cleared Java selection");
+                        return Task::none();
                     }
                     self.sync_detected_records();
                 }
@@ -1175,7 +1335,8 @@ impl JavaManagerScreen {
                 if matches!(self.target, JavaTarget::Instance(_)) {
                     self.mark_field_overridden(&OverrideField::MinMemory);
                 }
-                self.persist_settings("Memory settings updated")
+                self.persist_settings("Memory settings updated");
+                Task::none()
             }
             Message::MaxMemoryChanged(value) => {
                 let mut max = clamp_memory_value(value);
@@ -1192,7 +1353,27 @@ impl JavaManagerScreen {
                 if matches!(self.target, JavaTarget::Instance(_)) {
                     self.mark_field_overridden(&OverrideField::MaxMemory);
                 }
-                self.persist_settings("Memory settings updated")
+                self.persist_settings("Memory settings updated");
+                Task::none()
+            }
+            Message::RecommendMemory => {
+                let workload = self.workload();
+                let (min, max) = java_manager::recommend_memory(self.total_memory_mb, workload);
+                self.settings.min_memory_mb = min;
+                self.settings.max_memory_mb = max;
+                if matches!(self.target, JavaTarget::Instance(_)) {
+                    self.mark_field_overridden(&OverrideField::MinMemory);
+                    self.mark_field_overridden(&OverrideField::MaxMemory);
+                }
+                let kind = match workload {
+                    Workload { modded: false, .. } => "vanilla".to_string(),
+                    Workload { mod_count, .. } => format!("{} mods", mod_count),
+                };
+                self.persist_settings(&format!(
+                    "Memory set to {} - {} MB, recommended for {}",
+                    min, max, kind
+                ));
+                Task::none()
             }
             Message::ExtraArgsEdited(action) => {
                 self.args_content.perform(action);
@@ -1211,7 +1392,20 @@ impl JavaManagerScreen {
                 if matches!(self.target, JavaTarget::Instance(_)) {
                     self.mark_field_overridden(&OverrideField::JvmArgs);
                 }
-                self.persist_settings("JVM arguments saved")
+                self.persist_settings("JVM arguments saved");
+                Task::none()
+            }
+            Message::PresetSelected(preset) => {
+                // Keep unsaved edits in the editor; only the GC flags change.
+                self.settings.extra_jvm_args = parse_args(&self.args_content.text());
+                self.settings.apply_preset(preset);
+                self.args_content =
+                    text_editor::Content::with_text(&self.settings.extra_jvm_args.join(" "));
+                if matches!(self.target, JavaTarget::Instance(_)) {
+                    self.mark_field_overridden(&OverrideField::JvmArgs);
+                }
+                self.persist_settings(&format!("Applied {}", preset));
+                Task::none()
             }
             Message::CustomPathChanged(input) => {
                 self.custom_path_input = input;
@@ -1229,17 +1423,16 @@ impl JavaManagerScreen {
                     if matches!(self.target, JavaTarget::Instance(_)) {
                         self.mark_field_overridden(&OverrideField::JavaPath);
                     }
-                    let status = self.persist_settings("Custom Java selected");
+                    self.persist_settings("Custom Java selected");
                     let cfg = JavaDetectionConfig {
                         auto_discover: false,
                         preferred_path: Some(path),
                     };
                     self.detection_in_progress = true;
-                    let detection = Task::perform(
-                        async move { detect_installations(&cfg) },
+                    return Task::perform(
+                        async move { detect_installations(&cfg).await },
                         Message::DetectionFinished,
                     );
-                    return Task::batch([status, detection]);
                 }
                 Task::none()
             }
@@ -1251,22 +1444,22 @@ impl JavaManagerScreen {
                     if matches!(self.target, JavaTarget::Instance(_)) {
                         self.mark_field_overridden(&OverrideField::JavaPath);
                     }
-                    let status = self.persist_settings("Custom Java selected");
+                    self.persist_settings("Custom Java selected");
                     let cfg = JavaDetectionConfig {
                         auto_discover: false,
                         preferred_path: self.settings.java_path.clone(),
                     };
                     self.detection_in_progress = true;
-                    let detection = Task::perform(
-                        async move { detect_installations(&cfg) },
+                    Task::perform(
+                        async move { detect_installations(&cfg).await },
                         Message::DetectionFinished,
-                    );
-                    Task::batch([status, detection])
+                    )
                 } else {
                     self.push_status(
                         "Enter a valid Java path first.",
                         Color::from_rgb(0.24, 0.12, 0.12),
-                    )
+                    );
+                    Task::none()
                 }
             }
             Message::ToggleCustomForm => {
@@ -1297,10 +1490,18 @@ impl JavaManagerScreen {
             .map(|v| format!("Java {}", v))
             .unwrap_or_else(|| "Java (unknown version)".to_string());
 
-        let vendor = install
+        let mut vendor = install
             .vendor
             .clone()
             .unwrap_or_else(|| "Unknown vendor".to_string());
+        if let Some(arch) = &install.arch {
+            vendor = format!("{} · {}", vendor, arch);
+        }
+        match install.is_64_bit {
+            Some(true) => vendor.push_str(" · 64-bit"),
+            Some(false) => vendor.push_str(" · 32-bit"),
+            None => {}
+        }
 
         let path = install.path.display().to_string();
 
@@ -1357,6 +1558,34 @@ impl JavaManagerScreen {
                 }),
         ]
         .spacing(6);
+        let info = if install.is_emulated_on(game::host_arch()) {
+            info.push(
+                text("x86 build: runs under emulation on this ARM machine and is much slower")
+                    .size(13)
+                    .color(Color::from_rgb(0.98, 0.75, 0.30)),
+            )
+        } else {
+            info
+        };
+        let test = self.java_tests.get(&install.id);
+        let info = match test {
+            Some(Some(Ok(summary))) => info.push(
+                text(summary.as_str())
+                    .size(13)
+                    .color(Color::from_rgb(0.13, 0.77, 0.36)),
+            ),
+            Some(Some(Err(err))) => info.push(
+                text(err.as_str())
+                    .size(13)
+                    .color(Color::from_rgb(0.96, 0.47, 0.47)),
+            ),
+            _ => info,
+        };
+        let testing = matches!(test, Some(None));
+        let test_button = button(text(if testing { "Testing..." } else { "Test" }))
+            .padding([10, 14])
+            .style(iced::widget::button::secondary)
+            .on_press_maybe((!testing).then_some(Message::TestInstallation(install.id)));
 
         let select_button = button(
             text(if selected {
@@ -1432,7 +1661,7 @@ impl JavaManagerScreen {
                 badge,
                 info,
                 Space::new().width(Length::Fill),
-                column![select_button, remove_button]
+                column![select_button, test_button, remove_button]
                     .spacing(8)
                     .align_x(Alignment::End)
             ]
@@ -1453,6 +1682,8 @@ impl JavaManagerScreen {
         .into()
     }
 
+    /// Lists the configured Java path even when detection hasn't seen it. Its
+    /// details fill in once the next detection run inspects it.
     fn ensure_selected_entry(&mut self) {
         if let Some(path) = &self.settings.java_path {
             let normalized = normalize_path(path);
@@ -1465,28 +1696,16 @@ impl JavaManagerScreen {
                     &Uuid::NAMESPACE_OID,
                     normalized.to_string_lossy().as_bytes(),
                 );
-                let mut install = JavaInstallation {
+                let install = JavaInstallation {
                     id,
-                    path: normalized.clone(),
+                    path: normalized,
                     version: None,
                     vendor: Some("Configured path".to_string()),
                     source: java_manager::InstallSource::UserProvided,
+                    is_64_bit: None,
+                    arch: None,
                 };
 
-                let detection = detect_installations(&JavaDetectionConfig {
-                    auto_discover: false,
-                    preferred_path: Some(normalized.clone()),
-                });
-
-                if let Some(found) = detection
-                    .installations
-                    .into_iter()
-                    .find(|inst| normalize_path(&inst.path) == normalized)
-                {
-                    install.version = found.version.or(install.version);
-                    install.vendor = found.vendor.or(install.vendor);
-                }
-
                 self.installations.push(install);
             }
         }
@@ -1501,7 +1720,7 @@ impl JavaManagerScreen {
             .collect();
     }
 
-    fn persist_settings(&mut self, success: &str) -> Task<Message> {
+    fn persist_settings(&mut self, success: &str) {
         match self.save_settings() {
             Ok(_) => self.push_status(success, Color::from_rgb(0.12, 0.61, 0.30)),
             Err(err) => {
@@ -1530,19 +1749,141 @@ impl JavaManagerScreen {
         }
     }
 
-    fn push_status(&mut self, message: &str, tone: Color) -> Task<Message> {
-        let at = Instant::now();
-        self.status = Some((message.to_string(), tone, at));
-        Task::perform(
-            async move {
-                std::thread::sleep(Duration::from_secs(5));
-                Message::ClearStatus(at)
-            },
-            |msg| msg,
-        )
+    /// What the configured target runs, for the memory recommendation. The
+    /// global defaults are sized for vanilla.
+    fn workload(&self) -> Workload {
+        let Some(meta) = &self.instance_metadata else {
+            return Workload::default();
+        };
+        let mods_dir = self.instance_manager.game_dir(&meta.id).join("mods");
+        let mod_count = fs::read_dir(mods_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| e.path().extension().is_some_and(|ext| ext == "jar"))
+                    .count() as u32
+            })
+            .unwrap_or(0);
+        Workload {
+            modded: meta.loader != ModLoader::Vanilla,
+            mod_count,
+        }
+    }
+
+    fn managed_runtime(&self, path: &PathBuf) -> Option<InstalledRuntime> {
+        self.managed_runtimes
+            .iter()
+            .find(|runtime| &runtime.path == path)
+            .cloned()
+    }
+
+    fn runtimes_section(
+        &self,
+        text_primary: Color,
+        text_muted: Color,
+        surface: Color,
+    ) -> Element<'_, Message> {
+        let total: u64 = self.managed_runtimes.iter().map(|r| r.size_bytes).sum();
+        let mut list = column![
+            text("Managed runtimes")
+                .size(20)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_primary),
+                }),
+            text(if self.managed_runtimes.is_empty() {
+                "Java runtimes downloaded from Mojang for versions that need them appear here."
+                    .to_string()
+            } else {
+                format!("Downloaded from Mojang, {} in total.", format::size(total))
+            })
+            .size(14)
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_muted),
+            }),
+        ]
+        .spacing(10);
+
+        for runtime in &self.managed_runtimes {
+            let busy = self.runtime_busy.is_some();
+            let version = if runtime.version.is_empty() {
+                runtime.platform.clone()
+            } else {
+                format!("{} · {}", runtime.version, runtime.platform)
+            };
+            list = list.push(
+                row![
+                    column![
+                        text(runtime.component.as_str()).size(16).style(move |_| {
+                            iced::widget::text::Style {
+                                color: Some(text_primary),
+                            }
+                        }),
+                        text(format!(
+                            "{} · {}",
+                            version,
+                            format::size(runtime.size_bytes)
+                        ))
+                        .size(13)
+                        .style(move |_| iced::widget::text::Style {
+                            color: Some(text_muted),
+                        }),
+                    ]
+                    .spacing(4),
+                    Space::new().width(Length::Fill),
+                    button(text(if self.runtime_busy.as_ref() == Some(&runtime.path) {
+                        "Working..."
+                    } else {
+                        "Update"
+                    }))
+                    .padding([8, 12])
+                    .style(iced::widget::button::secondary)
+                    .on_press_maybe((!busy).then(|| Message::UpdateRuntime(runtime.path.clone()))),
+                    button(text("Remove"))
+                        .padding([8, 12])
+                        .style(iced::widget::button::danger)
+                        .on_press_maybe(
+                            (!busy).then(|| Message::RemoveRuntime(runtime.path.clone()))
+                        ),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            );
+        }
+
+        container(list)
+            .padding(16)
+            .width(Length::Fill)
+            .style(move |_| iced::widget::container::Style {
+                background: Some(surface.into()),
+                border: iced::Border {
+                    radius: 12.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            })
+            .into()
+    }
+
+    fn push_status(&mut self, message: &str, tone: Color) {
+        self.status.show((message.to_string(), tone));
+    }
+
+    pub fn status_expiring(&self) -> bool {
+        self.status.expiring()
+    }
+
+    pub fn expire_status(&mut self, now: Instant) {
+        self.status.expire(now);
     }
 }
 
+fn load_runtimes() -> Task<Message> {
+    Task::perform(
+        async { java_manager::installed_runtimes() },
+        Message::RuntimesLoaded,
+    )
+}
+
 fn parse_args(input: &str) -> Vec<String> {
     input.split_whitespace().map(|s| s.to_string()).collect()
 }
@@ -1591,6 +1932,8 @@ fn map_records_to_installations(records: &[JavaInstallationRecord]) -> Vec<JavaI
                 version: rec.version.clone(),
                 vendor: rec.vendor.clone(),
                 source,
+                is_64_bit: None,
+                arch: None,
             }
         })
         .collect()
@@ -1,4 +1,5 @@
-use iced::widget::{Space, column, container, text};
+use crate::data_migration::MigrationProgress;
+use iced::widget::{Space, column, container, progress_bar, text};
 use iced::{Color, Element, Length};
 
 #[derive(Debug, Clone)]
@@ -6,9 +7,25 @@ pub enum Message {
     // No interaction needed for loading screen
 }
 
-pub struct LoadingScreen;
+#[derive(Default)]
+pub struct LoadingScreen {
+    /// Set while a data layout migration runs on the first start after an
+    /// upgrade.
+    migration: Option<MigrationProgress>,
+    /// Why the migration failed. The launcher stops here rather than start
+    /// on half-migrated data.
+    error: Option<String>,
+}
 
 impl LoadingScreen {
+    pub fn set_migration(&mut self, progress: Option<MigrationProgress>) {
+        self.migration = progress;
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
     pub fn view(&self) -> Element<'_, Message> {
         let title = text("FastMC")
             .size(40)
@@ -28,7 +45,7 @@ impl LoadingScreen {
             color: Some(Color::from_rgb(0.13, 0.77, 0.36)), // Accent green
         });
 
-        let content = column![
+        let mut content = column![
             title,
             Space::new().height(10),
             subtitle,
@@ -37,6 +54,44 @@ impl LoadingScreen {
         ]
         .align_x(iced::Alignment::Center);
 
+        if let Some(migration) = &self.migration {
+            content = content
+                .push(Space::new().height(20))
+                .push(
+                    text(format!(
+                        "Upgrading launcher data ({}/{}): {}",
+                        migration.step, migration.total, migration.description
+                    ))
+                    .size(14)
+                    .style(|_| iced::widget::text::Style {
+                        color: Some(Color::from_rgb(0.7, 0.7, 0.7)),
+                    }),
+                )
+                .push(Space::new().height(8))
+                .push(
+                    container(progress_bar(
+                        0.0..=migration.total as f32,
+                        migration.step as f32 - 0.5,
+                    ))
+                    .width(Length::Fixed(320.0)),
+                );
+        }
+
+        if let Some(error) = &self.error {
+            content = content
+                .push(Space::new().height(20))
+                .push(
+                    text(format!(
+                        "Upgrading launcher data failed: {}\nFix the problem and restart fastMC; the upgrade resumes where it stopped.",
+                        error
+                    ))
+                    .size(14)
+                    .style(|_| iced::widget::text::Style {
+                        color: Some(Color::from_rgb(0.96, 0.47, 0.47)),
+                    }),
+                );
+        }
+
         container(content)
             .width(Length::Fill)
             .height(Length::Fill)
@@ -1,4 +1,5 @@
 pub mod account;
+pub mod console;
 pub mod java_manager;
 pub mod modpacks;
 pub mod play;
@@ -6,6 +7,7 @@ pub mod server;
 pub mod settings;
 
 pub use account::{AccountScreen, AccountUpdate, Message as AccountMessage};
+pub use console::{ConsoleScreen, Message as ConsoleMessage};
 pub use java_manager::{JavaManagerScreen, Message as JavaManagerMessage};
 pub use modpacks::{Message as ModpacksMessage, ModpacksScreen};
 pub use play::{Message as PlayMessage, PlayScreen};
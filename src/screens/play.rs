@@ -1,6 +1,9 @@
+use crate::game::{GameExit, LaunchPhase, LaunchProgress, LaunchStatus};
 use crate::instance_manager::{InstanceManager, InstanceMetadata};
-use iced::widget::{button, column, container, row, scrollable, text};
+use crate::play_button::{PlayButtonSize, PlayState, play_button};
+use iced::widget::{button, column, container, progress_bar, row, scrollable, text};
 use iced::{Alignment, Color, Element, Length, Task};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -9,8 +12,12 @@ pub enum Message {
     SelectInstance(String),
     Launch,
     LaunchInstance(String),
-    LaunchStarted,
-    LaunchFinished(Result<(), String>),
+    LaunchStarted(String),
+    LaunchProgress(String, LaunchProgress),
+    CancelLaunch(String),
+    LaunchFinished(String, Result<GameExit, String>),
+    /// The instance is already running or being launched.
+    LaunchRefused(String, String),
     OpenSettings(String), // Instance ID
 }
 
@@ -18,7 +25,8 @@ pub struct PlayScreen {
     instances: Vec<InstanceMetadata>,
     manager: InstanceManager,
     active_instance_id: Option<String>,
-    is_launching: bool,
+    /// Launches in progress, by instance.
+    launches: HashMap<String, LaunchStatus>,
     error: Option<String>,
 }
 
@@ -38,14 +46,21 @@ impl PlayScreen {
             instances: Vec::new(),
             manager,
             active_instance_id: None,
-            is_launching: false,
+            launches: HashMap::new(),
             error: None,
         }
     }
 
     pub fn refresh(&self) -> Task<Message> {
         let manager = self.manager.clone();
-        Task::perform(async move { manager.list_instances() }, Message::Loaded)
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || manager.list_instances())
+                    .await
+                    .unwrap_or_default()
+            },
+            Message::Loaded,
+        )
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
@@ -63,29 +78,35 @@ impl PlayScreen {
                 self.active_instance_id = Some(id);
                 Task::none()
             }
-            Message::Launch => {
-                if let Some(_id) = &self.active_instance_id {
-                    self.is_launching = true;
-                    self.error = None;
-                    Task::done(Message::LaunchStarted)
-                } else {
-                    Task::none()
-                }
-            }
+            Message::Launch => match self.active_instance_id.clone() {
+                Some(id) => self.start_launch(id),
+                None => Task::none(),
+            },
             Message::LaunchInstance(id) => {
-                self.active_instance_id = Some(id);
-                self.is_launching = true;
-                self.error = None;
-                Task::done(Message::LaunchStarted)
+                self.active_instance_id = Some(id.clone());
+                self.start_launch(id)
+            }
+            Message::LaunchStarted(_) => Task::none(),
+            Message::LaunchProgress(id, event) => {
+                if let Some(status) = self.launches.get_mut(&id) {
+                    status.apply(event);
+                }
+                Task::none()
             }
-            Message::LaunchStarted => Task::none(),
-            Message::LaunchFinished(result) => {
-                self.is_launching = false;
+            // The token itself is cancelled by the parent (main.rs)
+            Message::CancelLaunch(_) => Task::none(),
+            Message::LaunchFinished(id, result) => {
+                self.launches.remove(&id);
                 if let Err(e) = result {
                     self.error = Some(e);
                 }
                 Task::none()
             }
+            Message::LaunchRefused(id, e) => {
+                self.launches.remove(&id);
+                self.error = Some(e);
+                Task::none()
+            }
             Message::OpenSettings(_id) => {
                 // Placeholder for now
                 Task::none()
@@ -93,6 +114,15 @@ impl PlayScreen {
         }
     }
 
+    fn start_launch(&mut self, id: String) -> Task<Message> {
+        if self.launches.contains_key(&id) {
+            return Task::none();
+        }
+        self.launches.insert(id.clone(), LaunchStatus::default());
+        self.error = None;
+        Task::done(Message::LaunchStarted(id))
+    }
+
     pub fn active_instance(&self) -> Option<&InstanceMetadata> {
         self.active_instance_id
             .as_ref()
@@ -134,22 +164,38 @@ impl PlayScreen {
     }
 
     fn view_hero(&self, assets: Option<&crate::assets::AssetStore>) -> Element<'_, Message> {
-        let (name, version, _last_played) = if let Some(instance) = self.active_instance() {
+        let (name, version, last_played) = if let Some(instance) = self.active_instance() {
             (
                 instance.name.clone(),
                 format!("{} • {:?}", instance.game_version, instance.loader),
-                "Last played: Never", // Placeholder
+                format!(
+                    "Last played: {}",
+                    crate::format::relative_time(instance.last_played)
+                ),
             )
         } else {
             (
                 "No Profile Selected".to_string(),
                 "Create a profile to play".to_string(),
-                "",
+                String::new(),
             )
         };
 
+        let launch_status = self
+            .active_instance_id
+            .as_ref()
+            .and_then(|id| self.launches.get(id));
+        let play_state = launch_status.map_or(PlayState::Idle, PlayState::from);
+        // Once the game process is up, the launch future just waits for it to exit.
+        let preparing = launch_status.filter(|status| {
+            matches!(play_state, PlayState::Preparing(_))
+                && status.phase != Some(LaunchPhase::Launching)
+        });
+
         // Hero Content (Text & Buttons)
-        let status_badge = container(text("Ready to Play").size(12).color(Color::WHITE))
+        let status_label =
+            launch_status.map_or_else(|| "Ready to Play".to_string(), LaunchStatus::label);
+        let status_badge = container(text(status_label).size(12).color(Color::WHITE))
             .padding([4, 8])
             .style(|_| container::Style {
                 background: Some(Color::from_rgb(0.13, 0.77, 0.36).into()), // Green
@@ -168,49 +214,21 @@ impl PlayScreen {
             color: Some(Color::from_rgb(0.9, 0.9, 0.9)),
         });
 
-        // Launch Button
-        let launch_btn = button(
-            row![
-                text(if self.is_launching {
-                    "Playing"
-                } else {
-                    "Launch Game"
-                })
-                .size(16)
-                .color(Color::WHITE)
-            ]
-            .spacing(8)
-            .align_y(Alignment::Center),
-        )
-        .padding([12, 24])
-        .on_press_maybe(if self.is_launching || self.active_instance().is_none() {
-            None
-        } else {
-            Some(Message::Launch)
-        })
-        .style(|_theme, status| {
-            let base = Color::from_rgb(0.13, 0.77, 0.36);
-            let hover = Color::from_rgb(0.15, 0.85, 0.40);
-            let disabled = Color::from_rgb(0.3, 0.3, 0.3);
-
-            let bg = match status {
-                _ if self.is_launching => disabled,
-                iced::widget::button::Status::Hovered | iced::widget::button::Status::Pressed => {
-                    hover
-                }
-                _ => base,
-            };
+        let last_played = text(last_played)
+            .size(12)
+            .style(|_| iced::widget::text::Style {
+                color: Some(Color::from_rgb(0.7, 0.7, 0.7)),
+            });
 
-            iced::widget::button::Style {
-                background: Some(bg.into()),
-                border: iced::Border {
-                    radius: 6.0.into(),
-                    ..iced::Border::default()
-                },
-                text_color: Color::WHITE,
-                ..iced::widget::button::Style::default()
-            }
-        });
+        // Launch Button
+        let launch_btn = play_button(
+            play_state,
+            "Launch Game",
+            PlayButtonSize::Hero,
+            (launch_status.is_none() && self.active_instance().is_some())
+                .then_some(Message::Launch),
+            Message::CancelLaunch(self.active_instance_id.clone().unwrap_or_default()),
+        );
 
         // Change Profile Button (Placeholder logic for now)
         let profile_btn = button(text("Edit Profile").size(14))
@@ -219,16 +237,23 @@ impl PlayScreen {
 
         let actions = row![launch_btn, profile_btn].spacing(12);
 
-        let hero_content = column![
+        let mut hero_content = column![
             status_badge,
             title,
             subtitle,
+            last_played,
             iced::widget::Space::new().height(20),
             actions
         ]
         .spacing(10)
         .padding(40);
 
+        if let Some(status) = preparing {
+            hero_content = hero_content.push(
+                container(progress_bar(0.0..=1.0, status.fraction())).width(Length::Fixed(360.0)),
+            );
+        }
+
         // Background Image
         let bg_image = if let Some(assets) = assets {
             if let Some(handle) = assets.get_image("instances_images/default.jpg") {
@@ -353,47 +378,17 @@ impl PlayScreen {
                             ]
                             .spacing(4);
 
-                            let (btn_text, btn_action) = if self.is_launching {
-                                if is_active {
-                                    ("Playing", None)
-                                } else {
-                                    ("▶", None)
-                                }
-                            } else {
-                                ("▶", Some(Message::LaunchInstance(inst.id.clone())))
-                            };
-
-                            let is_launching = self.is_launching;
-
-                            let play_btn = button(text(btn_text).size(14).color(Color::WHITE))
-                                .padding(10)
-                                .on_press_maybe(btn_action)
-                                .style(move |_theme, status| {
-                                    let base = Color::from_rgb(0.13, 0.77, 0.36);
-                                    let hover = Color::from_rgb(0.15, 0.85, 0.40);
-                                    let disabled = Color::from_rgb(0.3, 0.3, 0.3);
+                            let launch_status = self.launches.get(&inst.id);
 
-                                    iced::widget::button::Style {
-                                        background: Some(
-                                            match status {
-                                                iced::widget::button::Status::Hovered => hover,
-                                                _ => {
-                                                    if is_launching && !is_active {
-                                                        disabled
-                                                    } else {
-                                                        base
-                                                    }
-                                                }
-                                            }
-                                            .into(),
-                                        ),
-                                        border: iced::Border {
-                                            radius: 20.0.into(), // Round circleish
-                                            ..iced::Border::default()
-                                        },
-                                        ..iced::widget::button::Style::default()
-                                    }
-                                });
+                            let play_btn = play_button(
+                                launch_status.map_or(PlayState::Idle, PlayState::from),
+                                "▶",
+                                PlayButtonSize::Icon,
+                                launch_status
+                                    .is_none()
+                                    .then(|| Message::LaunchInstance(inst.id.clone())),
+                                Message::CancelLaunch(inst.id.clone()),
+                            );
 
                             // Settings cog
                             let settings_btn =
@@ -419,11 +414,7 @@ impl PlayScreen {
                             };
 
                             button(content)
-                                .on_press_maybe(if is_launching {
-                                    None
-                                } else {
-                                    Some(Message::SelectInstance(inst.id.clone()))
-                                })
+                                .on_press(Message::SelectInstance(inst.id.clone()))
                                 .padding(12)
                                 .width(Length::Fill)
                                 .style(move |_theme, status| {
@@ -434,17 +425,13 @@ impl PlayScreen {
                                         _ => bg_color,
                                     };
 
-                                    // If launching and not active, dim it
                                     let border_color = if is_active {
                                         Color::from_rgb(0.13, 0.77, 0.36)
                                     } else {
                                         Color::TRANSPARENT
                                     };
 
-                                    let opacity_factor =
-                                        if is_launching && !is_active { 0.5 } else { 1.0 };
-
-                                    let mut style = iced::widget::button::Style {
+                                    iced::widget::button::Style {
                                         background: Some(bg.into()),
                                         border: iced::Border {
                                             radius: 12.0.into(),
@@ -452,25 +439,9 @@ impl PlayScreen {
                                             color: border_color,
                                             ..iced::Border::default()
                                         },
-                                        text_color: Color {
-                                            a: opacity_factor,
-                                            ..Color::WHITE
-                                        },
+                                        text_color: Color::WHITE,
                                         ..iced::widget::button::Style::default()
-                                    };
-
-                                    // Apply opacity to background if possible, or just imply it by logic
-                                    if is_launching && !is_active {
-                                        if let Some(iced::Background::Color(c)) = style.background {
-                                            style.background =
-                                                Some(iced::Background::Color(Color {
-                                                    a: 0.5,
-                                                    ..c
-                                                }));
-                                        }
                                     }
-
-                                    style
                                 })
                                 .into() // Cast button to Element
                         })
@@ -1,22 +1,914 @@
+use crate::changelog::{self, Release};
+use crate::database;
+use crate::format;
+use crate::instance_manager::{self, InstancesMove};
+use crate::log_upload;
+use crate::maintenance::{self, ALL_JOBS, Job, Schedule};
+use crate::processes;
+use account_manager::{AccountStore, KeyringReport, keyring_health_check};
+use config_manager::{ALL_CLOSE_BEHAVIORS, CloseBehavior, FastmcConfig, MaintenanceConfig};
+use iced::widget::{Space, button, checkbox, column, container, pick_list, row, scrollable, text};
+use iced::{Alignment, Color, Element, Length, Task};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Outcome of a maintenance job during this session.
+#[derive(Debug, Clone)]
+enum JobStatus {
+    Running,
+    Done(String),
+    Failed(String),
+}
+
 #[derive(Default)]
-pub struct SettingsScreen;
+pub struct SettingsScreen {
+    close_behavior: CloseBehavior,
+    maintenance: MaintenanceConfig,
+    use_database: bool,
+    database_error: Option<String>,
+    job_status: HashMap<Job, JobStatus>,
+    /// Last successful run of each job before this session.
+    last_runs: HashMap<Job, u64>,
+    save_error: Option<String>,
+    keyring_check_running: bool,
+    keyring_report: Option<KeyringReport>,
+    /// Most recent launch/install error, attached to bug reports.
+    last_error: Option<String>,
+    report_error: Option<String>,
+    releases: Vec<Release>,
+    show_release_notes: bool,
+    instances_dir: PathBuf,
+    moving_instances: bool,
+    /// Outcome of the last instances folder change.
+    instances_move: Option<Result<String, String>>,
+    /// "Reset to defaults" was pressed once and waits for confirmation.
+    confirm_reset: bool,
+    /// Outcome of the last settings export, import or reset.
+    transfer: Option<Result<String, String>>,
+}
 
-#[derive(Debug, Clone, Copy)]
-pub enum Message {}
+#[derive(Debug, Clone)]
+pub enum Message {
+    CloseBehaviorSelected(CloseBehavior),
+    ToggleMaintenanceJob(Job, bool),
+    RunMaintenanceJob(Job),
+    MaintenanceJobFinished(Job, Result<String, String>),
+    ToggleDatabase(bool),
+    DatabaseReady(Result<(), String>),
+    RunKeyringCheck,
+    KeyringCheckFinished(KeyringReport),
+    ReportBug,
+    ToggleReleaseNotes,
+    ChooseInstancesDir,
+    /// `None` when the dialog was dismissed.
+    InstancesDirChosen(Option<PathBuf>),
+    ResetInstancesDir,
+    /// The new `profiles.instances_dir` and how many instances moved there.
+    InstancesMoved(Option<PathBuf>, Result<InstancesMove, String>),
+    ExportSettings,
+    /// `None` when the dialog was dismissed.
+    ExportPathChosen(Option<PathBuf>),
+    ImportSettings,
+    ImportPathChosen(Option<PathBuf>),
+    ResetSettings,
+    ConfirmReset,
+    CancelReset,
+}
+
+impl Message {
+    /// Whether handling this message may replace the whole config, so
+    /// screens holding on to settings should read them again.
+    pub fn replaces_config(&self) -> bool {
+        matches!(
+            self,
+            Message::ImportPathChosen(Some(_)) | Message::ConfirmReset
+        )
+    }
+}
+
+const ISSUES_URL: &str = "https://github.com/breraud/fastMC/issues/new";
 
 impl SettingsScreen {
-    pub fn view(&self) -> iced::Element<'_, Message> {
-        iced::widget::container(
-            iced::widget::column![iced::widget::text("Settings Screen")]
-                .align_x(iced::Alignment::Center)
-                .spacing(8),
+    pub fn new() -> Self {
+        let mut screen = Self {
+            last_runs: ALL_JOBS
+                .into_iter()
+                .map(|job| (job, maintenance::last_run(job)))
+                .collect(),
+            releases: changelog::releases(),
+            ..Self::default()
+        };
+        screen.read_config();
+        screen
+    }
+
+    /// Picks up the settings shown here from the saved config.
+    fn read_config(&mut self) {
+        let config = FastmcConfig::load().unwrap_or_default();
+        self.close_behavior = config.launcher.close_behavior;
+        self.maintenance = config.maintenance;
+        self.use_database = config.launcher.use_database;
+        self.instances_dir = instance_manager::instances_dir();
+    }
+
+    /// Saves `config` in place of the current one and shows it. Paths of this
+    /// machine are kept; the instances folder in particular only changes
+    /// through the folder setting, which moves the instances.
+    fn replace_config(&mut self, mut config: FastmcConfig) -> Result<(), String> {
+        config.keep_machine_specific(&FastmcConfig::load().unwrap_or_default());
+        config.save().map_err(|e| e.to_string())?;
+        self.read_config();
+        Ok(())
+    }
+
+    /// Starts the maintenance jobs that are due and not already running.
+    /// `at_start` also runs the ones scheduled for every launcher start.
+    pub fn run_due_maintenance(&mut self, at_start: bool) -> Task<Message> {
+        let mut jobs = maintenance::due_jobs(&self.maintenance, at_start);
+        jobs.retain(|job| !matches!(self.job_status.get(job), Some(JobStatus::Running)));
+        Task::batch(
+            jobs.into_iter()
+                .map(|job| self.run_job(job))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn run_job(&mut self, job: Job) -> Task<Message> {
+        self.job_status.insert(job, JobStatus::Running);
+        let config = self.maintenance.clone();
+        Task::perform(maintenance::run(job, config), move |result| {
+            Message::MaintenanceJobFinished(job, result)
+        })
+    }
+
+    /// Moves the instances into `dir`, or the default folder with `None`,
+    /// and points the config there once they are.
+    fn change_instances_dir(&mut self, dir: Option<PathBuf>) -> Task<Message> {
+        let target = dir
+            .clone()
+            .unwrap_or_else(instance_manager::default_instances_dir);
+        if target == self.instances_dir {
+            return Task::none();
+        }
+        if target.starts_with(&self.instances_dir) {
+            self.instances_move = Some(Err(
+                "Pick a folder outside the current instances folder.".to_string()
+            ));
+            return Task::none();
+        }
+        if processes::any_running() {
+            self.instances_move = Some(Err(
+                "Stop running games before moving the instances.".to_string()
+            ));
+            return Task::none();
+        }
+
+        self.moving_instances = true;
+        self.instances_move = None;
+        let from = self.instances_dir.clone();
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    instance_manager::move_instances(&from, &target)
+                })
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())
+            },
+            move |result| Message::InstancesMoved(dir.clone(), result),
+        )
+    }
+
+    pub fn record_error(&mut self, error: &str) {
+        self.last_error = Some(error.to_string());
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let text_primary = Color::from_rgb(0.88, 0.89, 0.91);
+        let text_muted = Color::from_rgb(0.63, 0.64, 0.67);
+        let ok = Color::from_rgb(0.13, 0.77, 0.36);
+        let failed = Color::from_rgb(0.96, 0.47, 0.47);
+        let surface = Color::from_rgb(0.14, 0.14, 0.17);
+
+        let heading = text("Settings")
+            .size(28)
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_primary),
+            });
+
+        let mut close_section = column![
+            text("Closing the launcher")
+                .size(18)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_primary),
+                }),
+            text("What happens when you close the launcher while a game is running.")
+                .size(13)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_muted),
+                }),
+            pick_list(
+                ALL_CLOSE_BEHAVIORS,
+                Some(self.close_behavior),
+                Message::CloseBehaviorSelected
+            )
+            .text_size(14),
+        ]
+        .spacing(8);
+
+        if let Some(err) = &self.save_error {
+            close_section = close_section.push(
+                text(format!("Couldn't save settings: {}", err))
+                    .size(13)
+                    .style(move |_| iced::widget::text::Style {
+                        color: Some(failed),
+                    }),
+            );
+        }
+
+        let close_card = container(close_section)
+            .padding([14, 16])
+            .width(Length::Fill)
+            .style(move |_| iced::widget::container::Style {
+                background: Some(surface.into()),
+                border: iced::Border {
+                    radius: 10.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            });
+
+        let mut maintenance_section = column![
+            text("Maintenance")
+                .size(18)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_primary),
+                }),
+            text("Housekeeping run in the background when the launcher starts or once a day.")
+                .size(13)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_muted),
+                }),
+        ]
+        .spacing(8);
+
+        for job in ALL_JOBS {
+            let schedule = match job.schedule() {
+                Schedule::EveryStart => "every start",
+                Schedule::Daily => "daily",
+            };
+            let (status, status_color) = match self.job_status.get(&job) {
+                Some(JobStatus::Running) => ("Running…".to_string(), text_muted),
+                Some(JobStatus::Done(summary)) => (summary.clone(), ok),
+                Some(JobStatus::Failed(err)) => (format!("Failed: {}", err), failed),
+                None => {
+                    let last_run = self.last_runs.get(&job).copied().unwrap_or_default();
+                    (
+                        format!("Last run: {}", format::relative_time(last_run)),
+                        text_muted,
+                    )
+                }
+            };
+            let running = matches!(self.job_status.get(&job), Some(JobStatus::Running));
+
+            maintenance_section = maintenance_section.push(
+                row![
+                    checkbox(job.is_enabled(&self.maintenance))
+                        .on_toggle(move |enabled| Message::ToggleMaintenanceJob(job, enabled))
+                        .size(16),
+                    column![
+                        text(format!("{} ({})", job.label(), schedule))
+                            .size(14)
+                            .color(text_primary),
+                        text(status).size(12).color(status_color),
+                    ]
+                    .spacing(2),
+                    Space::new().width(Length::Fill),
+                    button(text("Run now").size(12))
+                        .padding([4, 10])
+                        .style(iced::widget::button::secondary)
+                        .on_press_maybe((!running).then_some(Message::RunMaintenanceJob(job))),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            );
+        }
+
+        maintenance_section = maintenance_section.push(
+            row![
+                checkbox(self.use_database)
+                    .on_toggle(Message::ToggleDatabase)
+                    .size(16),
+                column![
+                    text("Keep stats, the mod cache and job history in a database")
+                        .size(14)
+                        .color(text_primary),
+                    text("Faster with many instances and mods. Existing data is imported.")
+                        .size(12)
+                        .color(text_muted),
+                ]
+                .spacing(2),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+        );
+        if let Some(err) = &self.database_error {
+            maintenance_section = maintenance_section.push(
+                text(format!("Couldn't open the database: {}", err))
+                    .size(13)
+                    .color(failed),
+            );
+        }
+
+        let maintenance_card = container(maintenance_section)
+            .padding([14, 16])
+            .width(Length::Fill)
+            .style(move |_| iced::widget::container::Style {
+                background: Some(surface.into()),
+                border: iced::Border {
+                    radius: 10.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            });
+
+        let overridden =
+            std::env::var_os("FASTMC_INSTANCES_DIR").is_some_and(|dir| !dir.is_empty());
+        let can_move = !self.moving_instances && !overridden;
+        let mut instances_section = column![
+            text("Instances folder")
+                .size(18)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_primary),
+                }),
+            text(self.instances_dir.display().to_string())
+                .size(13)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_muted),
+                }),
+            row![
+                button(
+                    text(if self.moving_instances {
+                        "Moving..."
+                    } else {
+                        "Change folder"
+                    })
+                    .size(14)
+                )
+                .padding([10, 14])
+                .style(iced::widget::button::secondary)
+                .on_press_maybe(can_move.then_some(Message::ChooseInstancesDir)),
+                button(text("Reset to default").size(14))
+                    .padding([10, 14])
+                    .style(iced::widget::button::secondary)
+                    .on_press_maybe(can_move.then_some(Message::ResetInstancesDir)),
+            ]
+            .spacing(8),
+        ]
+        .spacing(8);
+
+        if overridden {
+            instances_section = instances_section.push(
+                text("Set by the FASTMC_INSTANCES_DIR environment variable.")
+                    .size(12)
+                    .color(text_muted),
+            );
+        } else {
+            instances_section = instances_section.push(
+                text("Existing instances are moved to the new folder.")
+                    .size(12)
+                    .color(text_muted),
+            );
+        }
+        match &self.instances_move {
+            Some(Ok(summary)) => {
+                instances_section = instances_section.push(text(summary).size(13).color(ok));
+            }
+            Some(Err(err)) => {
+                instances_section = instances_section.push(text(err).size(13).color(failed));
+            }
+            None => {}
+        }
+
+        let instances_card = container(instances_section)
+            .padding([14, 16])
+            .width(Length::Fill)
+            .style(move |_| iced::widget::container::Style {
+                background: Some(surface.into()),
+                border: iced::Border {
+                    radius: 10.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            });
+
+        let reset_buttons = if self.confirm_reset {
+            row![
+                button(text("Confirm reset").size(14))
+                    .padding([10, 14])
+                    .style(iced::widget::button::danger)
+                    .on_press(Message::ConfirmReset),
+                button(text("Cancel").size(14))
+                    .padding([10, 14])
+                    .style(iced::widget::button::secondary)
+                    .on_press(Message::CancelReset),
+            ]
+        } else {
+            row![
+                button(text("Reset to defaults").size(14))
+                    .padding([10, 14])
+                    .style(iced::widget::button::secondary)
+                    .on_press(Message::ResetSettings),
+            ]
+        };
+        let mut transfer_section = column![
+            text("Backup and reset")
+                .size(18)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_primary),
+                }),
+            text("Move your settings to another install, or start over. Accounts and instances are not affected.")
+                .size(13)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_muted),
+                }),
+            row![
+                button(text("Export settings").size(14))
+                    .padding([10, 14])
+                    .style(iced::widget::button::secondary)
+                    .on_press(Message::ExportSettings),
+                button(text("Import settings").size(14))
+                    .padding([10, 14])
+                    .style(iced::widget::button::secondary)
+                    .on_press(Message::ImportSettings),
+                reset_buttons.spacing(8),
+            ]
+            .spacing(8),
+        ]
+        .spacing(8);
+
+        match &self.transfer {
+            Some(Ok(summary)) => {
+                transfer_section = transfer_section.push(text(summary).size(13).color(ok));
+            }
+            Some(Err(err)) => {
+                transfer_section = transfer_section.push(text(err).size(13).color(failed));
+            }
+            None => {}
+        }
+
+        let transfer_card = container(transfer_section)
+            .padding([14, 16])
+            .width(Length::Fill)
+            .style(move |_| iced::widget::container::Style {
+                background: Some(surface.into()),
+                border: iced::Border {
+                    radius: 10.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            });
+
+        let check_button = button(
+            text(if self.keyring_check_running {
+                "Checking..."
+            } else {
+                "Test keyring"
+            })
+            .style(move |_| iced::widget::text::Style {
+                color: Some(Color::WHITE),
+            }),
         )
-        .center(iced::Length::Fill)
+        .padding([10, 14])
+        .style(move |_theme, status| {
+            let base = Color::from_rgb(0.13, 0.77, 0.36);
+            let hover = Color::from_rgb(0.12, 0.61, 0.30);
+            iced::widget::button::Style {
+                background: Some(
+                    match status {
+                        iced::widget::button::Status::Hovered
+                        | iced::widget::button::Status::Pressed => hover,
+                        _ => base,
+                    }
+                    .into(),
+                ),
+                text_color: Color::WHITE,
+                border: iced::Border {
+                    radius: 10.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::button::Style::default()
+            }
+        })
+        .on_press_maybe((!self.keyring_check_running).then_some(Message::RunKeyringCheck));
+
+        let mut keyring_section = column![
+            text("Credential storage")
+                .size(18)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_primary),
+                }),
+            text("Microsoft sign-in tokens are kept in the system keyring.")
+                .size(13)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_muted),
+                }),
+            check_button,
+        ]
+        .spacing(8);
+
+        if let Some(report) = &self.keyring_report {
+            keyring_section =
+                keyring_section.push(text(format!("Backend: {}", report.backend)).size(13).style(
+                    move |_| iced::widget::text::Style {
+                        color: Some(text_primary),
+                    },
+                ));
+
+            for step in &report.steps {
+                let (label, color) = match &step.result {
+                    Ok(()) => (format!("{}: ok", step.name), ok),
+                    Err(err) => (format!("{}: failed ({})", step.name, err), failed),
+                };
+                keyring_section = keyring_section.push(
+                    text(label)
+                        .size(13)
+                        .style(move |_| iced::widget::text::Style { color: Some(color) }),
+                );
+            }
+
+            for hint in &report.hints {
+                keyring_section =
+                    keyring_section.push(text(format!("• {hint}")).size(12).style(move |_| {
+                        iced::widget::text::Style {
+                            color: Some(text_muted),
+                        }
+                    }));
+            }
+        }
+
+        let keyring_card = container(keyring_section)
+            .padding([14, 16])
+            .width(Length::Fill)
+            .style(move |_| iced::widget::container::Style {
+                background: Some(surface.into()),
+                border: iced::Border {
+                    radius: 10.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            });
+
+        let mut report_section = column![
+            text("Report a bug")
+                .size(18)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_primary),
+                }),
+            text("Opens a GitHub issue prefilled with your launcher version, system and Java setup, and the last error. Account tokens are never included.")
+                .size(13)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_muted),
+                }),
+            button(text("Report a bug").size(14))
+                .padding([10, 14])
+                .style(iced::widget::button::secondary)
+                .on_press(Message::ReportBug),
+        ]
+        .spacing(8);
+
+        if let Some(err) = &self.report_error {
+            report_section = report_section.push(
+                text(format!("Couldn't open the browser: {}", err))
+                    .size(13)
+                    .style(move |_| iced::widget::text::Style {
+                        color: Some(failed),
+                    }),
+            );
+        }
+
+        let report_card = container(report_section)
+            .padding([14, 16])
+            .width(Length::Fill)
+            .style(move |_| iced::widget::container::Style {
+                background: Some(surface.into()),
+                border: iced::Border {
+                    radius: 10.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            });
+
+        let mut release_section = column![
+            text("Release notes")
+                .size(18)
+                .style(move |_| iced::widget::text::Style {
+                    color: Some(text_primary),
+                }),
+            text(format!(
+                "You're running fastMC {}.",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .size(13)
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_muted),
+            }),
+            button(
+                text(if self.show_release_notes {
+                    "Hide history"
+                } else {
+                    "Show history"
+                })
+                .size(14)
+            )
+            .padding([10, 14])
+            .style(iced::widget::button::secondary)
+            .on_press(Message::ToggleReleaseNotes),
+        ]
+        .spacing(8);
+
+        if self.show_release_notes {
+            release_section = release_section
+                .push(scrollable(changelog::view(&self.releases)).height(Length::Fixed(240.0)));
+        }
+
+        let release_card = container(release_section)
+            .padding([14, 16])
+            .width(Length::Fill)
+            .style(move |_| iced::widget::container::Style {
+                background: Some(surface.into()),
+                border: iced::Border {
+                    radius: 10.0.into(),
+                    ..iced::Border::default()
+                },
+                ..iced::widget::container::Style::default()
+            });
+
+        container(
+            column![
+                heading,
+                close_card,
+                instances_card,
+                maintenance_card,
+                keyring_card,
+                report_card,
+                transfer_card,
+                release_card
+            ]
+            .align_x(Alignment::Start)
+            .spacing(16),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
         .padding(20)
         .into()
     }
 
-    pub fn update(&mut self, message: Message) {
-        match message {}
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::CloseBehaviorSelected(behavior) => {
+                self.close_behavior = behavior;
+                let mut config = FastmcConfig::load().unwrap_or_default();
+                config.launcher.close_behavior = behavior;
+                self.save_error = config.save().err().map(|e| e.to_string());
+                Task::none()
+            }
+            Message::ToggleMaintenanceJob(job, enabled) => {
+                job.set_enabled(&mut self.maintenance, enabled);
+                let mut config = FastmcConfig::load().unwrap_or_default();
+                config.maintenance = self.maintenance.clone();
+                self.save_error = config.save().err().map(|e| e.to_string());
+                Task::none()
+            }
+            Message::ToggleDatabase(enabled) => {
+                self.use_database = enabled;
+                self.database_error = None;
+                let mut config = FastmcConfig::load().unwrap_or_default();
+                config.launcher.use_database = enabled;
+                self.save_error = config.save().err().map(|e| e.to_string());
+                if !enabled {
+                    return Task::none();
+                }
+                // Creates the database and imports the JSON data right away
+                Task::perform(
+                    async {
+                        tokio::task::spawn_blocking(|| database::open().map(|_| ()))
+                            .await
+                            .map_err(|e| e.to_string())?
+                    },
+                    Message::DatabaseReady,
+                )
+            }
+            Message::DatabaseReady(result) => {
+                if let Err(e) = result {
+                    // Fall back to the JSON files
+                    self.use_database = false;
+                    self.database_error = Some(e);
+                    let mut config = FastmcConfig::load().unwrap_or_default();
+                    config.launcher.use_database = false;
+                    self.save_error = config.save().err().map(|e| e.to_string());
+                }
+                Task::none()
+            }
+            Message::RunMaintenanceJob(job) => self.run_job(job),
+            Message::MaintenanceJobFinished(job, result) => {
+                let status = match result {
+                    Ok(summary) => JobStatus::Done(summary),
+                    Err(e) => {
+                        println!("Maintenance job \"{}\" failed: {}", job.label(), e);
+                        JobStatus::Failed(e)
+                    }
+                };
+                self.job_status.insert(job, status);
+                Task::none()
+            }
+            Message::RunKeyringCheck => {
+                self.keyring_check_running = true;
+                Task::perform(
+                    async { keyring_health_check() },
+                    Message::KeyringCheckFinished,
+                )
+            }
+            Message::KeyringCheckFinished(report) => {
+                self.keyring_check_running = false;
+                self.keyring_report = Some(report);
+                Task::none()
+            }
+            Message::ReportBug => {
+                let secrets = AccountStore::load()
+                    .map(|store| log_upload::Secrets::of_active(&store))
+                    .unwrap_or_default();
+                let url = bug_report_url(self.last_error.as_deref(), &secrets);
+                self.report_error = open::that(url).err().map(|e| e.to_string());
+                Task::none()
+            }
+            Message::ToggleReleaseNotes => {
+                self.show_release_notes = !self.show_release_notes;
+                Task::none()
+            }
+            Message::ExportSettings => Task::perform(
+                async {
+                    rfd::FileDialog::new()
+                        .add_filter("fastMC settings", &["json"])
+                        .set_file_name("fastmc-settings.json")
+                        .save_file()
+                },
+                Message::ExportPathChosen,
+            ),
+            Message::ExportPathChosen(None) | Message::ImportPathChosen(None) => Task::none(),
+            Message::ExportPathChosen(Some(path)) => {
+                self.transfer = Some(
+                    FastmcConfig::load()
+                        .unwrap_or_default()
+                        .export(&path)
+                        .map(|_| format!("Settings exported to {}", path.display()))
+                        .map_err(|e| format!("Couldn't export settings: {}", e)),
+                );
+                Task::none()
+            }
+            Message::ImportSettings => Task::perform(
+                async {
+                    rfd::FileDialog::new()
+                        .add_filter("fastMC settings", &["json"])
+                        .pick_file()
+                },
+                Message::ImportPathChosen,
+            ),
+            Message::ImportPathChosen(Some(path)) => {
+                self.transfer = Some(
+                    FastmcConfig::import(&path)
+                        .map_err(|e| e.to_string())
+                        .and_then(|config| self.replace_config(config))
+                        .map(|_| "Settings imported".to_string())
+                        .map_err(|e| format!("Couldn't import settings: {}", e)),
+                );
+                Task::none()
+            }
+            Message::ResetSettings => {
+                self.confirm_reset = true;
+                Task::none()
+            }
+            Message::CancelReset => {
+                self.confirm_reset = false;
+                Task::none()
+            }
+            Message::ConfirmReset => {
+                self.confirm_reset = false;
+                let mut config = FastmcConfig::load().unwrap_or_default();
+                config.reset();
+                self.transfer = Some(
+                    self.replace_config(config)
+                        .map(|_| "Settings reset to defaults".to_string())
+                        .map_err(|e| format!("Couldn't reset settings: {}", e)),
+                );
+                Task::none()
+            }
+            Message::ChooseInstancesDir => Task::perform(
+                async { rfd::FileDialog::new().pick_folder() },
+                Message::InstancesDirChosen,
+            ),
+            Message::InstancesDirChosen(None) => Task::none(),
+            Message::InstancesDirChosen(Some(dir)) => self.change_instances_dir(Some(dir)),
+            Message::ResetInstancesDir => self.change_instances_dir(None),
+            Message::InstancesMoved(dir, result) => {
+                self.moving_instances = false;
+                self.instances_move = Some(match result {
+                    // Nothing moved yet, so the old folder is still the one in use
+                    Ok(report) if report.moved == 0 && !report.left_behind.is_empty() => {
+                        Err(format!(
+                            "Failed to move the instances: {}",
+                            describe_left_behind(&report)
+                        ))
+                    }
+                    Ok(report) => {
+                        let mut config = FastmcConfig::load().unwrap_or_default();
+                        config.profiles.instances_dir =
+                            dir.map(|dir| dir.to_string_lossy().into_owned());
+                        self.save_error = config.save().err().map(|e| e.to_string());
+                        self.instances_dir = instance_manager::instances_dir();
+                        if report.left_behind.is_empty() {
+                            Ok(format!("Moved {} instances", report.moved))
+                        } else {
+                            Err(format!(
+                                "Moved {} instances. Left in the old folder: {}",
+                                report.moved,
+                                describe_left_behind(&report)
+                            ))
+                        }
+                    }
+                    Err(e) => Err(format!("Failed to move the instances: {}", e)),
+                });
+                Task::none()
+            }
+        }
     }
 }
+
+fn describe_left_behind(report: &InstancesMove) -> String {
+    report
+        .left_behind
+        .iter()
+        .map(|(name, reason)| format!("{} ({})", name, reason))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Builds the "new issue" URL.
+fn bug_report_url(last_error: Option<&str>, secrets: &log_upload::Secrets) -> String {
+    let config = FastmcConfig::load().unwrap_or_default();
+    let java = config
+        .java
+        .java_path
+        .unwrap_or_else(|| "auto-detected".to_string());
+    let body = bug_report_body(&java, last_error, secrets);
+
+    reqwest::Url::parse_with_params(ISSUES_URL, &[("labels", "bug"), ("body", &body)])
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| ISSUES_URL.to_string())
+}
+
+/// Everything in the report ends up public, so the Java path and the error
+/// are scrubbed like uploaded logs, and the error also goes through
+/// [`redact_secrets`].
+pub(crate) fn bug_report_body(
+    java: &str,
+    last_error: Option<&str>,
+    secrets: &log_upload::Secrets,
+) -> String {
+    let last_error = last_error
+        .map(|error| redact_secrets(&log_upload::scrub(error, secrets)))
+        .unwrap_or_else(|| "none".to_string());
+
+    format!(
+        "**Describe the problem**\n\n\n\n\
+         **Environment**\n\
+         - Launcher: fastMC {}\n\
+         - OS: {} ({})\n\
+         - Java: {}\n\n\
+         **Last error**\n```\n{}\n```\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        log_upload::scrub(java, secrets),
+        last_error
+    )
+}
+
+/// Masks anything that looks like a credential: JWTs, bearer tokens and long
+/// opaque strings. Paths are kept, however long.
+fn redact_secrets(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let opaque = word.len() >= 32
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_.=+".contains(c));
+            if opaque || word.to_ascii_lowercase().contains("token=") {
+                "[redacted]"
+            } else {
+                word
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
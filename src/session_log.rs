@@ -0,0 +1,86 @@
+//! Copy of each game session's output in `<instance>/fastmc-logs`, kept apart
+//! from Minecraft's own logs. A session starts `<timestamp>.log` and rolls
+//! over to `<timestamp>.1.log`, `<timestamp>.2.log`, ... once a file is full.
+
+use crate::game_output::Stream;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub const LOGS_DIR: &str = "fastmc-logs";
+
+const MAX_FILE_BYTES: u64 = 8 * 1024 * 1024;
+/// Files kept per instance; the oldest go first when a session starts.
+const MAX_FILES: usize = 30;
+
+pub struct SessionLog {
+    dir: PathBuf,
+    stem: String,
+    part: u32,
+    file: BufWriter<File>,
+    written: u64,
+}
+
+impl SessionLog {
+    pub fn create(instance_dir: &Path, started: u64) -> io::Result<Self> {
+        let dir = instance_dir.join(LOGS_DIR);
+        fs::create_dir_all(&dir)?;
+        prune(&dir, MAX_FILES.saturating_sub(1))?;
+        let stem = crate::format::file_timestamp(started);
+        let file = BufWriter::new(File::create(dir.join(format!("{}.log", stem)))?);
+        Ok(Self {
+            dir,
+            stem,
+            part: 0,
+            file,
+            written: 0,
+        })
+    }
+
+    /// Appends a line; stderr lines are tagged so they stand out.
+    pub fn write_line(&mut self, stream: Stream, line: &str) -> io::Result<()> {
+        if self.written >= MAX_FILE_BYTES {
+            self.rotate()?;
+        }
+        let line = match stream {
+            Stream::Stdout => format!("{}\n", line),
+            Stream::Stderr => format!("[stderr] {}\n", line),
+        };
+        self.file.write_all(line.as_bytes())?;
+        self.written += line.len() as u64;
+        // Keep the file readable while the game runs
+        self.file.flush()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        self.part += 1;
+        let path = self.dir.join(format!("{}.{}.log", self.stem, self.part));
+        self.file = BufWriter::new(File::create(path)?);
+        self.written = 0;
+        prune(&self.dir, MAX_FILES)
+    }
+}
+
+/// Deletes the least recently written logs in `dir` until at most `keep`
+/// are left.
+fn prune(dir: &Path, keep: usize) -> io::Result<()> {
+    let mut logs: Vec<(SystemTime, PathBuf)> = fs::read_dir(dir)?
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "log"))
+        .map(|entry| {
+            let modified = entry
+                .metadata()
+                .and_then(|meta| meta.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (modified, entry.path())
+        })
+        .collect();
+    logs.sort();
+    let excess = logs.len().saturating_sub(keep);
+    for (_, path) in logs.into_iter().take(excess) {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
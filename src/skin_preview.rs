@@ -0,0 +1,350 @@
+//! Rotatable 3D preview of a player skin, drawn on a canvas. Every texel of
+//! the model's boxes becomes a small quad; quads are projected orthographically
+//! and painted back to front.
+
+use iced::widget::canvas;
+use iced::{Color, Element, Length, Point, Rectangle, Vector, mouse};
+use std::path::Path;
+use std::sync::Arc;
+
+/// View angle before any dragging: a three-quarter view from slightly above.
+const INITIAL_YAW: f32 = 0.5;
+const PITCH: f32 = 0.2;
+/// Radians of rotation per pixel dragged.
+const DRAG_SPEED: f32 = 0.01;
+/// Model height in texels, feet to top of the head.
+const MODEL_HEIGHT: f32 = 32.0;
+
+/// A decoded 64x64 skin, or a 64x32 one from before 1.8.
+#[derive(Debug)]
+pub struct SkinTexture {
+    height: u32,
+    pixels: Vec<[u8; 4]>,
+    /// Alex model: 3 texel wide arms.
+    slim: bool,
+}
+
+impl SkinTexture {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let image = image::open(path)
+            .map_err(|e| format!("Failed to read skin {}: {}", path.display(), e))?
+            .to_rgba8();
+        if image.width() != 64 || !matches!(image.height(), 32 | 64) {
+            return Err(format!(
+                "Unsupported skin size {}x{}",
+                image.width(),
+                image.height()
+            ));
+        }
+        let mut texture = Self {
+            height: image.height(),
+            pixels: image.pixels().map(|pixel| pixel.0).collect(),
+            slim: false,
+        };
+        // Slim skins leave the back of the 4 texel arm layout empty
+        texture.slim =
+            texture.height == 64 && (54..56).all(|x| (20..32).all(|y| texture.pixel(x, y)[3] == 0));
+        Ok(texture)
+    }
+
+    /// Whether the skin is for the Alex model.
+    pub fn is_slim(&self) -> bool {
+        self.slim
+    }
+
+    fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        self.pixels[(y * 64 + x) as usize]
+    }
+
+    /// The boxes of the player model with their texture offsets, base layer
+    /// first. Old 64x32 skins reuse the right limbs for the left ones and
+    /// only have the hat as an overlay.
+    fn parts(&self) -> Vec<Part> {
+        let arm = if self.slim { 3.0 } else { 4.0 };
+        let legacy = self.height == 32;
+        let mut parts = vec![
+            Part::new([-4.0, 24.0, -4.0], [8.0, 8.0, 8.0], (0, 0)),
+            Part::new([-4.0, 12.0, -2.0], [8.0, 12.0, 4.0], (16, 16)),
+            Part::new([-4.0 - arm, 12.0, -2.0], [arm, 12.0, 4.0], (40, 16)),
+            Part::new(
+                [4.0, 12.0, -2.0],
+                [arm, 12.0, 4.0],
+                if legacy { (40, 16) } else { (32, 48) },
+            ),
+            Part::new([-4.0, 0.0, -2.0], [4.0, 12.0, 4.0], (0, 16)),
+            Part::new(
+                [0.0, 0.0, -2.0],
+                [4.0, 12.0, 4.0],
+                if legacy { (0, 16) } else { (16, 48) },
+            ),
+            Part::new([-4.0, 24.0, -4.0], [8.0, 8.0, 8.0], (32, 0)).overlay(0.5),
+        ];
+        if !legacy {
+            parts.extend([
+                Part::new([-4.0, 12.0, -2.0], [8.0, 12.0, 4.0], (16, 32)).overlay(0.25),
+                Part::new([-4.0 - arm, 12.0, -2.0], [arm, 12.0, 4.0], (40, 32)).overlay(0.25),
+                Part::new([4.0, 12.0, -2.0], [arm, 12.0, 4.0], (48, 48)).overlay(0.25),
+                Part::new([-4.0, 0.0, -2.0], [4.0, 12.0, 4.0], (0, 32)).overlay(0.25),
+                Part::new([0.0, 0.0, -2.0], [4.0, 12.0, 4.0], (0, 48)).overlay(0.25),
+            ]);
+        }
+        parts
+    }
+}
+
+/// One box of the model, in texels with y up and the front facing +z.
+struct Part {
+    min: [f32; 3],
+    size: [f32; 3],
+    uv: (u32, u32),
+    /// How far an overlay layer sits outside the base layer. Overlays skip
+    /// transparent texels; the base layer is always opaque.
+    inflate: f32,
+}
+
+impl Part {
+    fn new(min: [f32; 3], size: [f32; 3], uv: (u32, u32)) -> Self {
+        Self {
+            min,
+            size,
+            uv,
+            inflate: 0.0,
+        }
+    }
+
+    fn overlay(self, inflate: f32) -> Self {
+        Self { inflate, ..self }
+    }
+
+    /// The six faces in Minecraft's box UV layout: top and bottom in the
+    /// first row, then right, front, left and back.
+    fn faces(&self) -> [Face; 6] {
+        let [w, h, d] = self.size;
+        let (tw, th, td) = (w as u32, h as u32, d as u32);
+        let [x0, y0, z0] = self.min.map(|v| v - self.inflate);
+        let [x1, y1, z1] = [
+            self.min[0] + w + self.inflate,
+            self.min[1] + h + self.inflate,
+            self.min[2] + d + self.inflate,
+        ];
+        // World units per texel along each axis
+        let (sx, sy, sz) = ((x1 - x0) / w, (y1 - y0) / h, (z1 - z0) / d);
+        let (u, v) = self.uv;
+        [
+            Face {
+                origin: [x0, y1, z0],
+                across: [sx, 0.0, 0.0],
+                down: [0.0, 0.0, sz],
+                normal: [0.0, 1.0, 0.0],
+                region: (u + td, v, tw, td),
+            },
+            Face {
+                origin: [x0, y0, z0],
+                across: [sx, 0.0, 0.0],
+                down: [0.0, 0.0, sz],
+                normal: [0.0, -1.0, 0.0],
+                region: (u + td + tw, v, tw, td),
+            },
+            Face {
+                origin: [x0, y1, z0],
+                across: [0.0, 0.0, sz],
+                down: [0.0, -sy, 0.0],
+                normal: [-1.0, 0.0, 0.0],
+                region: (u, v + td, td, th),
+            },
+            Face {
+                origin: [x0, y1, z1],
+                across: [sx, 0.0, 0.0],
+                down: [0.0, -sy, 0.0],
+                normal: [0.0, 0.0, 1.0],
+                region: (u + td, v + td, tw, th),
+            },
+            Face {
+                origin: [x1, y1, z1],
+                across: [0.0, 0.0, -sz],
+                down: [0.0, -sy, 0.0],
+                normal: [1.0, 0.0, 0.0],
+                region: (u + td + tw, v + td, td, th),
+            },
+            Face {
+                origin: [x1, y1, z0],
+                across: [-sx, 0.0, 0.0],
+                down: [0.0, -sy, 0.0],
+                normal: [0.0, 0.0, -1.0],
+                region: (u + 2 * td + tw, v + td, tw, th),
+            },
+        ]
+    }
+}
+
+/// A face of a box: texel `(i, j)` of `region` covers
+/// `origin + across * i + down * j` to one texel further along both.
+struct Face {
+    origin: [f32; 3],
+    across: [f32; 3],
+    down: [f32; 3],
+    normal: [f32; 3],
+    region: (u32, u32, u32, u32),
+}
+
+/// Rotates a model-space point (or direction) into view space, where +z
+/// points at the viewer.
+fn rotate([x, y, z]: [f32; 3], yaw: f32) -> [f32; 3] {
+    let (x, z) = (
+        x * yaw.cos() + z * yaw.sin(),
+        -x * yaw.sin() + z * yaw.cos(),
+    );
+    [
+        x,
+        y * PITCH.cos() - z * PITCH.sin(),
+        y * PITCH.sin() + z * PITCH.cos(),
+    ]
+}
+
+fn along(point: [f32; 3], direction: [f32; 3], amount: f32) -> [f32; 3] {
+    [
+        point[0] + direction[0] * amount,
+        point[1] + direction[1] * amount,
+        point[2] + direction[2] * amount,
+    ]
+}
+
+pub fn skin_preview<'a, Message: 'a>(
+    texture: Arc<SkinTexture>,
+    width: f32,
+    height: f32,
+) -> Element<'a, Message> {
+    canvas(SkinPreview { texture })
+        .width(Length::Fixed(width))
+        .height(Length::Fixed(height))
+        .into()
+}
+
+struct SkinPreview {
+    texture: Arc<SkinTexture>,
+}
+
+#[derive(Debug, Default)]
+struct Rotation {
+    /// Added to [`INITIAL_YAW`] by dragging.
+    yaw: f32,
+    /// Cursor x where the current drag last was.
+    drag_from: Option<f32>,
+}
+
+impl<Message> canvas::Program<Message> for SkinPreview {
+    type State = Rotation;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        match event {
+            iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let position = cursor.position_over(bounds)?;
+                state.drag_from = Some(position.x);
+                Some(canvas::Action::capture())
+            }
+            iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                let from = state.drag_from?;
+                state.yaw += (position.x - from) * DRAG_SPEED;
+                state.drag_from = Some(position.x);
+                Some(canvas::Action::request_redraw().and_capture())
+            }
+            iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                state.drag_from.take().map(|_| canvas::Action::capture())
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &iced::Renderer,
+        _theme: &iced::Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let yaw = INITIAL_YAW + state.yaw;
+        // Leave room for the widest the model gets while turning
+        let scale = (bounds.height / (MODEL_HEIGHT + 4.0)).min(bounds.width / 20.0);
+        let center = Vector::new(bounds.width / 2.0, bounds.height / 2.0);
+        let project = |point: [f32; 3]| {
+            let [x, y, _] = rotate([point[0], point[1] - MODEL_HEIGHT / 2.0, point[2]], yaw);
+            Point::new(x * scale, -y * scale) + center
+        };
+
+        // (depth, corners, color) of every visible texel
+        let mut quads = Vec::new();
+        for part in self.texture.parts() {
+            for face in part.faces() {
+                if rotate(face.normal, yaw)[2] <= 0.0 {
+                    continue;
+                }
+                let (u, v, width, height) = face.region;
+                for j in 0..height {
+                    for i in 0..width {
+                        let [r, g, b, a] = self.texture.pixel(u + i, v + j);
+                        if part.inflate > 0.0 && a == 0 {
+                            continue;
+                        }
+                        let top_left = along(
+                            along(face.origin, face.across, i as f32),
+                            face.down,
+                            j as f32,
+                        );
+                        let top_right = along(top_left, face.across, 1.0);
+                        let bottom_right = along(top_right, face.down, 1.0);
+                        let bottom_left = along(top_left, face.down, 1.0);
+                        let middle = along(along(top_left, face.across, 0.5), face.down, 0.5);
+                        let alpha = if part.inflate > 0.0 { a } else { 255 };
+                        quads.push((
+                            rotate(middle, yaw)[2],
+                            [top_left, top_right, bottom_right, bottom_left].map(&project),
+                            Color::from_rgba8(r, g, b, alpha as f32 / 255.0),
+                        ));
+                    }
+                }
+            }
+        }
+
+        quads.sort_by(|a, b| a.0.total_cmp(&b.0));
+        for (_, corners, color) in quads {
+            let quad = canvas::Path::new(|path| {
+                path.move_to(corners[0]);
+                for corner in &corners[1..] {
+                    path.line_to(*corner);
+                }
+                path.close();
+            });
+            frame.fill(&quad, color);
+            // Hides the hairline seams antialiasing leaves between quads
+            frame.stroke(
+                &quad,
+                canvas::Stroke::default().with_width(0.5).with_color(color),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if state.drag_from.is_some() {
+            mouse::Interaction::Grabbing
+        } else if cursor.is_over(bounds) {
+            mouse::Interaction::Grab
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
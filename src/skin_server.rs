@@ -0,0 +1,189 @@
+//! A tiny Yggdrasil API on localhost that only knows one player: the offline
+//! account being launched, with the skin picked for it. authlib-injector
+//! points the game at it, so offline play shows that skin instead of
+//! Steve/Alex. Everything else the game asks for comes back empty.
+
+use crate::skin_preview::SkinTexture;
+use account_manager::{Account, AccountKind};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+const PROFILE_PATH: &str = "/sessionserver/session/minecraft/profile/";
+const SKIN_PATH: &str = "/textures/skin.png";
+
+/// The running server. Dropping it shuts the server down.
+pub struct SkinServer {
+    api_root: String,
+    task: JoinHandle<()>,
+}
+
+impl SkinServer {
+    /// Root URL to hand authlib-injector.
+    pub fn api_root(&self) -> &str {
+        &self.api_root
+    }
+}
+
+impl Drop for SkinServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+struct Player {
+    /// Without dashes, as the game asks for it.
+    uuid: String,
+    username: String,
+    png: Vec<u8>,
+    slim: bool,
+}
+
+/// Starts a server for `account` when it is an offline account with a skin.
+/// Failures only cost the skin, so they are logged instead of returned.
+pub async fn start(account: &Account) -> Option<SkinServer> {
+    let AccountKind::Offline { username, uuid } = &account.kind else {
+        return None;
+    };
+    let path = account_manager::offline_skin_path(&account.id).ok()?;
+    if !path.exists() {
+        return None;
+    }
+    let texture = match SkinTexture::load(&path) {
+        Ok(texture) => texture,
+        Err(e) => {
+            println!("Skipping offline skin: {}", e);
+            return None;
+        }
+    };
+    let png = match tokio::fs::read(&path).await {
+        Ok(png) => png,
+        Err(e) => {
+            println!("Skipping offline skin: {}", e);
+            return None;
+        }
+    };
+
+    let listener = match TcpListener::bind(("127.0.0.1", 0)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("Failed to start the offline skin server: {}", e);
+            return None;
+        }
+    };
+    let port = listener.local_addr().ok()?.port();
+    let api_root = format!("http://127.0.0.1:{}", port);
+    let player = Arc::new(Player {
+        uuid: uuid.replace('-', ""),
+        username: username.clone(),
+        png,
+        slim: texture.is_slim(),
+    });
+    let root = api_root.clone();
+    let task = tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let player = player.clone();
+            let root = root.clone();
+            tokio::spawn(async move {
+                let _ = serve(stream, &player, &root).await;
+            });
+        }
+    });
+    Some(SkinServer { api_root, task })
+}
+
+/// Answers one request and closes the connection.
+async fn serve(mut stream: TcpStream, player: &Player, root: &str) -> std::io::Result<()> {
+    let mut buf = vec![0; 8192];
+    let read = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..read]);
+    // "GET /path?query HTTP/1.1"
+    let path = request
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or("/");
+    let path = path.split('?').next().unwrap_or(path);
+
+    if path == "/" {
+        let metadata = json!({
+            "meta": {
+                "serverName": "fastMC offline skins",
+                "implementationName": "fastMC",
+                "implementationVersion": env!("CARGO_PKG_VERSION"),
+            },
+            "skinDomains": ["127.0.0.1"],
+        });
+        respond(
+            &mut stream,
+            "200 OK",
+            "application/json",
+            metadata.to_string().as_bytes(),
+        )
+        .await
+    } else if path == SKIN_PATH {
+        respond(&mut stream, "200 OK", "image/png", &player.png).await
+    } else if let Some(id) = path.strip_prefix(PROFILE_PATH)
+        && id.replace('-', "").eq_ignore_ascii_case(&player.uuid)
+    {
+        let profile = profile(player, root);
+        respond(
+            &mut stream,
+            "200 OK",
+            "application/json",
+            profile.to_string().as_bytes(),
+        )
+        .await
+    } else {
+        // Unknown players, name lookups and joins: nothing to report
+        respond(&mut stream, "204 No Content", "text/plain", b"").await
+    }
+}
+
+/// The player's profile with an unsigned `textures` property pointing at
+/// [`SKIN_PATH`].
+fn profile(player: &Player, root: &str) -> serde_json::Value {
+    let mut skin = json!({ "url": format!("{}{}", root, SKIN_PATH) });
+    if player.slim {
+        skin["metadata"] = json!({ "model": "slim" });
+    }
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let textures = json!({
+        "timestamp": timestamp,
+        "profileId": player.uuid,
+        "profileName": player.username,
+        "textures": { "SKIN": skin },
+    });
+    json!({
+        "id": player.uuid,
+        "name": player.username,
+        "properties": [{
+            "name": "textures",
+            "value": BASE64.encode(textures.to_string()),
+        }],
+    })
+}
+
+async fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body).await
+}
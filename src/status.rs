@@ -0,0 +1,48 @@
+//! Status banners shared by the screens. A banner disappears after
+//! [`STATUS_DURATION`]; the app's one-second tick clears it, so no task has to
+//! wait around for each message.
+
+use std::time::{Duration, Instant};
+
+pub const STATUS_DURATION: Duration = Duration::from_secs(5);
+
+/// The banner currently shown, if any, and when it expires.
+#[derive(Debug, Clone)]
+pub struct Status<T = String> {
+    current: Option<(T, Option<Instant>)>,
+}
+
+impl<T> Default for Status<T> {
+    fn default() -> Self {
+        Self { current: None }
+    }
+}
+
+impl<T> Status<T> {
+    /// Shows `value` for [`STATUS_DURATION`], replacing the current banner.
+    pub fn show(&mut self, value: T) {
+        self.current = Some((value, Some(Instant::now() + STATUS_DURATION)));
+    }
+
+    /// Shows `value` until another banner replaces it, for work still in
+    /// progress.
+    pub fn hold(&mut self, value: T) {
+        self.current = Some((value, None));
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        self.current.as_ref().map(|(value, _)| value)
+    }
+
+    /// Whether the banner is waiting to expire, i.e. needs the tick.
+    pub fn expiring(&self) -> bool {
+        matches!(self.current, Some((_, Some(_))))
+    }
+
+    /// Clears the banner if its time is up at `now`.
+    pub fn expire(&mut self, now: Instant) {
+        if matches!(self.current, Some((_, Some(deadline))) if deadline <= now) {
+            self.current = None;
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// The `arguments` block of a 1.13+ version JSON.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct VersionArguments {
+    #[serde(default)]
+    pub game: Vec<Argument>,
+    #[serde(default)]
+    pub jvm: Vec<Argument>,
+}
+
+impl VersionArguments {
+    /// Whether the game understands `--quickPlay*` (1.20+), judging by the
+    /// feature rules of its arguments.
+    pub fn supports_quick_play(&self) -> bool {
+        self.game.iter().any(|arg| match arg {
+            Argument::Conditional { rules, .. } => rules.iter().any(|rule| {
+                rule.features
+                    .as_ref()
+                    .is_some_and(|features| features.contains_key("is_quick_play_multiplayer"))
+            }),
+            Argument::Plain(_) => false,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Argument {
+    Plain(String),
+    Conditional {
+        rules: Vec<Rule>,
+        value: ArgumentValue,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ArgumentValue {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Argument {
+    /// The raw (unsubstituted) values this argument contributes, if its rules
+    /// allow it.
+    pub fn values(&self, features: &Features) -> Vec<&str> {
+        match self {
+            Argument::Plain(value) => vec![value.as_str()],
+            Argument::Conditional { rules, value } if rules_allow(rules, features) => match value {
+                ArgumentValue::One(value) => vec![value.as_str()],
+                ArgumentValue::Many(values) => values.iter().map(String::as_str).collect(),
+            },
+            Argument::Conditional { .. } => vec![],
+        }
+    }
+}
+
+/// Launcher features that `features` rules can test for.
+#[derive(Debug, Clone, Default)]
+pub struct Features {
+    pub has_custom_resolution: bool,
+    pub is_demo_user: bool,
+    pub is_quick_play_singleplayer: bool,
+    pub is_quick_play_multiplayer: bool,
+}
+
+impl Features {
+    fn get(&self, name: &str) -> bool {
+        match name {
+            "has_custom_resolution" => self.has_custom_resolution,
+            "is_demo_user" => self.is_demo_user,
+            "is_quick_play_singleplayer" => self.is_quick_play_singleplayer,
+            "is_quick_play_multiplayer" => self.is_quick_play_multiplayer,
+            // quick play paths, realms... are not supported
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub action: RuleAction,
+    #[serde(default)]
+    pub os: Option<OsRule>,
+    #[serde(default)]
+    pub features: Option<HashMap<String, bool>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleAction {
+    Allow,
+    Disallow,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsRule {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub arch: Option<String>,
+}
+
+impl Rule {
+    pub fn matches(&self, features: &Features) -> bool {
+        let os_matches = self.os.as_ref().is_none_or(|os| {
+            os.name.as_deref().is_none_or(|name| name == current_os())
+                && os
+                    .arch
+                    .as_deref()
+                    .is_none_or(|arch| arch == std::env::consts::ARCH)
+        });
+        let features_match = self.features.as_ref().is_none_or(|wanted| {
+            wanted
+                .iter()
+                .all(|(name, value)| features.get(name) == *value)
+        });
+        os_matches && features_match
+    }
+}
+
+/// Mojang's semantics: no rules means allowed; otherwise start disallowed and
+/// let the last matching rule decide.
+pub fn rules_allow(rules: &[Rule], features: &Features) -> bool {
+    if rules.is_empty() {
+        return true;
+    }
+    rules
+        .iter()
+        .rev()
+        .find(|rule| rule.matches(features))
+        .is_some_and(|rule| rule.action == RuleAction::Allow)
+}
+
+/// OS name as used in version JSON rules and `natives` maps.
+pub fn current_os() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "macos") {
+        "osx"
+    } else {
+        "linux"
+    }
+}
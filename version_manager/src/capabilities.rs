@@ -0,0 +1,42 @@
+/// Launch options that only some game versions understand, judged by the
+/// version's release time against the first snapshot that had each one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionCapabilities {
+    /// `--quickPlaySingleplayer` and friends, needed to open a world on launch.
+    pub quick_play: bool,
+    /// `--server`/`--port`, or Quick Play on newer versions.
+    pub join_server: bool,
+    /// `--width`/`--height`.
+    pub custom_resolution: bool,
+}
+
+/// 23w14a, the first snapshot with Quick Play.
+const QUICK_PLAY_SINCE: &str = "2023-04-05";
+/// 13w16a, the first snapshot for the launcher that passes game arguments by
+/// name. Anything older, alphas and betas included, ignores them.
+const NAMED_ARGUMENTS_SINCE: &str = "2013-04-18";
+
+impl VersionCapabilities {
+    /// Everything supported, for when the release time is unknown.
+    pub const ALL: Self = Self {
+        quick_play: true,
+        join_server: true,
+        custom_resolution: true,
+    };
+
+    /// Capabilities of a version released at `release_time`, an ISO 8601
+    /// timestamp as found in the version manifest and JSON.
+    pub fn from_release_time(release_time: &str) -> Self {
+        if release_time.len() < 10 {
+            return Self::ALL;
+        }
+        // ISO 8601 dates compare chronologically as strings
+        let date = &release_time[..10];
+        let named_arguments = date >= NAMED_ARGUMENTS_SINCE;
+        Self {
+            quick_play: date >= QUICK_PLAY_SINCE,
+            join_server: named_arguments,
+            custom_resolution: named_arguments,
+        }
+    }
+}
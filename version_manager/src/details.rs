@@ -0,0 +1,176 @@
+use crate::arguments::{rules_allow, Features, Rule, VersionArguments};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// A version JSON as served by piston-meta (`versions/<id>/<id>.json`), or
+/// the result of folding a loader profile into its parent with
+/// [`crate::inheritance::resolve`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionDetails {
+    #[serde(default)]
+    pub id: String,
+    /// Only set on loader profiles that haven't been merged yet.
+    #[serde(default)]
+    pub inherits_from: Option<String>,
+    /// Version whose client jar this one runs. Merged loader profiles get
+    /// their root parent's; `None` means the version's own.
+    #[serde(default)]
+    pub jar: Option<String>,
+    /// `release`, `snapshot`, `old_beta`, ...
+    #[serde(rename = "type", default)]
+    pub version_type: Option<String>,
+    pub main_class: String,
+    #[serde(default)]
+    pub release_time: String,
+    #[serde(default)]
+    pub time: String,
+    #[serde(default)]
+    pub libraries: Vec<Library>,
+    pub downloads: VersionDownloads,
+    pub asset_index: AssetIndex,
+    /// Same as `asset_index.id` on every version that has both.
+    #[serde(default)]
+    pub assets: Option<String>,
+    /// 1.13+ only; older versions use `minecraft_arguments`.
+    #[serde(default)]
+    pub arguments: Option<VersionArguments>,
+    #[serde(default)]
+    pub minecraft_arguments: Option<String>,
+    #[serde(default)]
+    pub java_version: Option<JavaVersion>,
+    #[serde(default)]
+    pub logging: Option<Logging>,
+    #[serde(default)]
+    pub compliance_level: Option<u32>,
+    #[serde(default)]
+    pub minimum_launcher_version: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VersionDownloads {
+    pub client: Download,
+    #[serde(default)]
+    pub server: Option<Download>,
+    #[serde(default)]
+    pub client_mappings: Option<Download>,
+    #[serde(default)]
+    pub server_mappings: Option<Download>,
+}
+
+/// A file with its checksum; `path` is only set on library artifacts and
+/// classifiers, relative to `libraries/`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Download {
+    pub url: String,
+    pub sha1: String,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetIndex {
+    pub id: String,
+    pub url: String,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+    #[serde(default)]
+    pub total_size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaVersion {
+    /// Mojang runtime the version ships with, e.g. `java-runtime-delta`.
+    #[serde(default)]
+    pub component: Option<String>,
+    pub major_version: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Logging {
+    #[serde(default)]
+    pub client: Option<LoggingConfig>,
+}
+
+/// The log4j configuration to pass to the client, e.g. `client-1.12.xml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoggingConfig {
+    /// JVM argument with a `${path}` placeholder for the downloaded file.
+    pub argument: String,
+    pub file: LoggingFile,
+    #[serde(rename = "type", default)]
+    pub config_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoggingFile {
+    pub id: String,
+    pub url: String,
+    pub sha1: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Library {
+    /// Missing on maven-style entries (loader JSONs), which only give `name`
+    /// and a repository `url`.
+    #[serde(default)]
+    pub downloads: LibraryDownloads,
+    pub name: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+    /// OS name -> classifier holding that platform's natives, e.g.
+    /// `"windows": "natives-windows-${arch}"`.
+    #[serde(default)]
+    pub natives: HashMap<String, String>,
+    /// Paths to leave out when unpacking the natives jar.
+    #[serde(default)]
+    pub extract: Option<ExtractRules>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LibraryDownloads {
+    #[serde(default)]
+    pub artifact: Option<Download>,
+    /// Classifier -> natives jar, on libraries from before 1.19.
+    #[serde(default)]
+    pub classifiers: Option<HashMap<String, Download>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExtractRules {
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl Library {
+    /// Whether the library's rules allow it on this OS.
+    pub fn applies(&self) -> bool {
+        rules_allow(&self.rules, &Features::default())
+    }
+
+    /// Maven classifier of the coordinate, e.g. `natives-macos-arm64` for
+    /// `org.lwjgl:lwjgl:3.3.1:natives-macos-arm64`.
+    pub fn classifier(&self) -> Option<&str> {
+        self.name.split(':').nth(3)
+    }
+
+    /// Whether the library ships a natives jar under `classifier`.
+    pub fn has_classifier(&self, classifier: &str) -> bool {
+        self.downloads
+            .classifiers
+            .as_ref()
+            .is_some_and(|c| c.contains_key(classifier))
+    }
+}
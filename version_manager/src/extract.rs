@@ -0,0 +1,94 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// One archive entry to write to disk.
+#[derive(Debug, Clone)]
+pub struct Extraction {
+    pub archive: PathBuf,
+    pub entry: String,
+    pub dest: PathBuf,
+}
+
+/// Extracts `jobs` on a pool of worker threads, one per core. Entries are
+/// streamed to disk, so big files are never held in memory. `progress` gets
+/// the entry just written and the number done out of the total. Blocking;
+/// stops at the first failure.
+pub fn extract_all(
+    jobs: &[Extraction],
+    progress: impl Fn(&str, usize, usize) + Sync,
+) -> Result<(), String> {
+    let total = jobs.len();
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let workers = std::thread::available_parallelism()
+        .map_or(4, |n| n.get())
+        .min(total.max(1));
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    // Each worker reads through its own handle of every archive
+                    let mut archives: HashMap<&Path, zip::ZipArchive<File>> = HashMap::new();
+                    while !failed.load(Ordering::Relaxed) {
+                        let Some(job) = jobs.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                            break;
+                        };
+                        if let Err(e) = extract_one(&mut archives, job) {
+                            failed.store(true, Ordering::Relaxed);
+                            return Err(e);
+                        }
+                        progress(&job.entry, done.fetch_add(1, Ordering::Relaxed) + 1, total);
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+        handles.into_iter().try_for_each(|handle| {
+            handle
+                .join()
+                .unwrap_or_else(|_| Err("Extraction worker panicked".to_string()))
+        })
+    })
+}
+
+fn extract_one<'a>(
+    archives: &mut HashMap<&'a Path, zip::ZipArchive<File>>,
+    job: &'a Extraction,
+) -> Result<(), String> {
+    let archive = match archives.entry(job.archive.as_path()) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => {
+            let file = File::open(&job.archive)
+                .map_err(|e| format!("Cannot open {}: {}", job.archive.display(), e))?;
+            let archive = zip::ZipArchive::new(file)
+                .map_err(|e| format!("Invalid archive {}: {}", job.archive.display(), e))?;
+            entry.insert(archive)
+        }
+    };
+    let mut entry = archive
+        .by_name(&job.entry)
+        .map_err(|e| format!("Failed to read {}: {}", job.entry, e))?;
+    // Callers derive `dest` from the entry name, so one that climbs out of
+    // the archive root could be written anywhere
+    if entry.enclosed_name().is_none() {
+        return Err(format!(
+            "Unsafe path in {}: {}",
+            job.archive.display(),
+            job.entry
+        ));
+    }
+    if let Some(parent) = job.dest.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let write = |e: io::Error| format!("Failed to write {}: {}", job.dest.display(), e);
+    let mut out = BufWriter::new(File::create(&job.dest).map_err(write)?);
+    io::copy(&mut entry, &mut out).map_err(write)?;
+    out.flush().map_err(write)
+}
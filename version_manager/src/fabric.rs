@@ -1,35 +1,120 @@
 use crate::models::{FabricGameVersion, FabricLoaderVersion, LoaderLibrary, LoaderProfile};
-use reqwest::Error;
+use reqwest::{Error, StatusCode};
 use serde::Deserialize;
 
-const FABRIC_LOADER_URL: &str = "https://meta.fabricmc.net/v2/versions/loader";
-const FABRIC_GAME_URL: &str = "https://meta.fabricmc.net/v2/versions/game";
+/// A meta server speaking Fabric's v2 API, and the maven its profiles
+/// resolve against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FabricMeta {
+    pub meta: &'static str,
+    pub maven: &'static str,
+    /// Maven group and artifact of the intermediary mappings, with the
+    /// trailing `:` the version follows.
+    pub intermediary: &'static str,
+}
+
+pub const FABRIC: FabricMeta = FabricMeta {
+    meta: "https://meta.fabricmc.net/v2",
+    maven: "https://maven.fabricmc.net/",
+    intermediary: "net.fabricmc:intermediary:",
+};
+
+/// Legacy Fabric: the same loader with its own intermediary mappings for
+/// versions before 1.14, which upstream Fabric doesn't cover.
+pub const LEGACY_FABRIC: FabricMeta = FabricMeta {
+    meta: "https://meta.legacyfabric.net/v2",
+    maven: "https://repo.legacyfabric.net/repository/legacyfabric/",
+    intermediary: "net.legacyfabric:intermediary:",
+};
 
-pub async fn fetch_fabric_loaders() -> Result<Vec<FabricLoaderVersion>, Error> {
+pub async fn fetch_fabric_loaders(meta: &FabricMeta) -> Result<Vec<FabricLoaderVersion>, Error> {
+    let url = format!("{}/versions/loader", meta.meta);
     let client = reqwest::Client::new();
-    let response = client.get(FABRIC_LOADER_URL).send().await?;
+    let response = client.get(downloader::mirrored(&url)).send().await?;
     let loaders: Vec<FabricLoaderVersion> = response.json().await?;
     Ok(loaders)
 }
 
-pub async fn fetch_fabric_game_versions() -> Result<Vec<FabricGameVersion>, Error> {
+pub async fn fetch_fabric_game_versions(
+    meta: &FabricMeta,
+) -> Result<Vec<FabricGameVersion>, Error> {
+    let url = format!("{}/versions/game", meta.meta);
     let client = reqwest::Client::new();
-    let response = client.get(FABRIC_GAME_URL).send().await?;
+    let response = client.get(downloader::mirrored(&url)).send().await?;
     let versions: Vec<FabricGameVersion> = response.json().await?;
     Ok(versions)
 }
 
+/// Entry of `versions/loader/{game_version}`; only the loader half is needed.
+#[derive(Debug, Deserialize)]
+struct FabricCompatibleLoader {
+    loader: FabricLoaderVersion,
+}
+
+/// Loader versions `meta` lists for `game_version`. Empty when meta doesn't
+/// know the version yet, which happens for a while after a release.
 pub async fn fetch_compatible_loaders(
+    meta: &FabricMeta,
     game_version: &str,
 ) -> Result<Vec<FabricLoaderVersion>, Error> {
+    let url = format!("{}/versions/loader/{}", meta.meta, game_version);
+    let client = reqwest::Client::new();
+    let response = client.get(downloader::mirrored(&url)).send().await?;
+    if matches!(
+        response.status(),
+        StatusCode::NOT_FOUND | StatusCode::BAD_REQUEST
+    ) {
+        return Ok(Vec::new());
+    }
+    let entries: Vec<FabricCompatibleLoader> = response.error_for_status()?.json().await?;
+    Ok(entries.into_iter().map(|e| e.loader).collect())
+}
+
+/// Whether intermediary mappings for `game_version` are already published on
+/// the maven of `meta`, even if meta doesn't list the version yet.
+pub async fn intermediary_available(meta: &FabricMeta, game_version: &str) -> Result<bool, Error> {
+    let (group, artifact) = meta
+        .intermediary
+        .trim_end_matches(':')
+        .split_once(':')
+        .unwrap_or_default();
     let url = format!(
-        "https://meta.fabricmc.net/v2/versions/loader/{}",
-        game_version
+        "{}{}/{a}/{v}/{a}-{v}.jar",
+        meta.maven,
+        group.replace('.', "/"),
+        a = artifact,
+        v = game_version
     );
     let client = reqwest::Client::new();
-    let response = client.get(&url).send().await?;
-    let loaders: Vec<FabricLoaderVersion> = response.json().await?;
-    Ok(loaders)
+    let response = client.head(downloader::mirrored(&url)).send().await?;
+    Ok(response.status().is_success())
+}
+
+/// Builds a profile for a game version Fabric meta doesn't know yet by taking
+/// the profile of the newest version it does know and swapping in
+/// intermediary for `game_version`. Experimental: nothing guarantees the
+/// loader actually supports the new release.
+pub async fn fetch_experimental_profile(
+    meta: &FabricMeta,
+    game_version: &str,
+    loader_version: &str,
+) -> Result<LoaderProfile, String> {
+    let known = fetch_fabric_game_versions(meta)
+        .await
+        .map_err(|e| format!("Failed to fetch Fabric game versions: {}", e))?
+        .into_iter()
+        .next()
+        .ok_or("Fabric meta lists no game versions")?;
+
+    let mut profile = fetch_fabric_profile(meta, &known.version, loader_version).await?;
+    let intermediary = meta.intermediary;
+    for lib in &mut profile.libraries {
+        if lib.name.starts_with(intermediary) {
+            lib.name = format!("{}{}", intermediary, game_version);
+            lib.sha1 = None;
+        }
+    }
+    Ok(profile)
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,6 +129,8 @@ struct FabricProfileJson {
 struct FabricProfileLib {
     name: String,
     url: Option<String>,
+    #[serde(default)]
+    sha1: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -53,16 +140,17 @@ struct FabricProfileArguments {
 }
 
 pub async fn fetch_fabric_profile(
+    meta: &FabricMeta,
     game_version: &str,
     loader_version: &str,
 ) -> Result<LoaderProfile, String> {
     let url = format!(
-        "https://meta.fabricmc.net/v2/versions/loader/{}/{}/profile/json",
-        game_version, loader_version
+        "{}/versions/loader/{}/{}/profile/json",
+        meta.meta, game_version, loader_version
     );
     let client = reqwest::Client::new();
     let response = client
-        .get(&url)
+        .get(downloader::mirrored(&url))
         .send()
         .await
         .map_err(|e| format!("Failed to fetch Fabric profile: {}", e))?;
@@ -86,7 +174,8 @@ pub async fn fetch_fabric_profile(
             .into_iter()
             .map(|lib| LoaderLibrary {
                 name: lib.name,
-                url: lib.url.or_else(|| Some("https://maven.fabricmc.net/".to_string())),
+                url: lib.url.or_else(|| Some(meta.maven.to_string())),
+                sha1: lib.sha1,
             })
             .collect(),
         jvm_args: profile
@@ -1,4 +1,6 @@
-use crate::models::{ForgeInstallProfile, ForgeVersionJson};
+use crate::extract::{extract_all, Extraction};
+use crate::models::{ForgeInstallProfile, ForgeVersionJson, LegacyForgeInstallProfile};
+use downloader::{CancellationToken, DownloadRequest, Downloader};
 use serde::Deserialize;
 use std::io::Read;
 use std::path::Path;
@@ -12,7 +14,7 @@ pub async fn fetch_forge_versions(game_version: &str) -> Result<Vec<String>, Str
     let url = "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
     let client = reqwest::Client::new();
     let response = client
-        .get(url)
+        .get(downloader::mirrored(url))
         .send()
         .await
         .map_err(|e| format!("Failed to fetch Forge promotions: {}", e))?;
@@ -38,7 +40,7 @@ pub async fn fetch_forge_versions(game_version: &str) -> Result<Vec<String>, Str
     let maven_url = format!(
         "https://maven.minecraftforge.net/net/minecraftforge/forge/maven-metadata.xml"
     );
-    if let Ok(resp) = client.get(&maven_url).send().await {
+    if let Ok(resp) = client.get(downloader::mirrored(&maven_url)).send().await {
         if let Ok(text) = resp.text().await {
             // Simple XML parsing — extract versions matching game_version
             for line in text.lines() {
@@ -68,9 +70,11 @@ pub async fn fetch_forge_versions(game_version: &str) -> Result<Vec<String>, Str
 }
 
 pub async fn download_forge_installer(
+    downloader: &Downloader,
     game_version: &str,
     forge_version: &str,
     dest: &Path,
+    cancel: &CancellationToken,
 ) -> Result<(), String> {
     let url = format!(
         "https://maven.minecraftforge.net/net/minecraftforge/forge/{game}-{forge}/forge-{game}-{forge}-installer.jar",
@@ -78,61 +82,103 @@ pub async fn download_forge_installer(
         forge = forge_version
     );
 
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&url)
-        .send()
+    downloader
+        .download(&DownloadRequest::new(url, dest), cancel)
         .await
         .map_err(|e| format!("Failed to download Forge installer: {}", e))?;
+    Ok(())
+}
 
-    if !response.status().is_success() {
-        return Err(format!(
-            "Forge installer download failed: {}",
-            response.status()
-        ));
-    }
+/// Generations of the Forge installer's `install_profile.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallerFormat {
+    /// Minecraft 1.12.2 and older: `install` + `versionInfo`, no processors.
+    Legacy,
+    /// Processor-based installers (1.13+), keyed by their `spec` number.
+    Modern { spec: u64 },
+}
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read Forge installer bytes: {}", e))?;
+/// Highest `spec` of the processor-based format this launcher understands.
+pub const MAX_SUPPORTED_SPEC: u64 = 1;
 
-    if let Some(parent) = dest.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create installer dir: {}", e))?;
+pub fn detect_installer_format(profile: &serde_json::Value) -> InstallerFormat {
+    if profile.get("versionInfo").is_some() && profile.get("install").is_some() {
+        return InstallerFormat::Legacy;
+    }
+    InstallerFormat::Modern {
+        spec: profile.get("spec").and_then(|v| v.as_u64()).unwrap_or(0),
     }
+}
 
-    std::fs::write(dest, &bytes).map_err(|e| format!("Failed to write installer: {}", e))?;
-    Ok(())
+fn open_installer(installer_jar: &Path) -> Result<zip::ZipArchive<std::fs::File>, String> {
+    let file =
+        std::fs::File::open(installer_jar).map_err(|e| format!("Cannot open installer: {}", e))?;
+    zip::ZipArchive::new(file).map_err(|e| format!("Invalid installer JAR: {}", e))
+}
+
+fn read_install_profile(
+    archive: &mut zip::ZipArchive<std::fs::File>,
+) -> Result<serde_json::Value, String> {
+    let mut entry = archive
+        .by_name("install_profile.json")
+        .map_err(|e| format!("Missing install_profile.json: {}", e))?;
+    let mut buf = String::new();
+    entry
+        .read_to_string(&mut buf)
+        .map_err(|e| format!("Failed to read install_profile.json: {}", e))?;
+    serde_json::from_str(&buf).map_err(|e| format!("Failed to parse install_profile.json: {}", e))
+}
+
+/// Which generation of installer `installer_jar` is, to pick between
+/// [`extract_forge_installer`] and [`extract_legacy_forge_installer`].
+pub fn installer_format(installer_jar: &Path) -> Result<InstallerFormat, String> {
+    let mut archive = open_installer(installer_jar)?;
+    let profile = read_install_profile(&mut archive)?;
+    Ok(detect_installer_format(&profile))
 }
 
+/// Reads the installer's profiles and extracts its bundled libraries,
+/// reporting entries done out of the total to `progress`.
 pub fn extract_forge_installer(
     installer_jar: &Path,
     libraries_dir: &Path,
+    progress: impl Fn(usize, usize) + Sync,
 ) -> Result<(ForgeInstallProfile, ForgeVersionJson), String> {
-    let file =
-        std::fs::File::open(installer_jar).map_err(|e| format!("Cannot open installer: {}", e))?;
-    let mut archive =
-        zip::ZipArchive::new(file).map_err(|e| format!("Invalid installer JAR: {}", e))?;
+    let mut archive = open_installer(installer_jar)?;
+    let raw_profile = read_install_profile(&mut archive)?;
 
-    // Extract install_profile.json
-    let install_profile: ForgeInstallProfile = {
-        let mut entry = archive
-            .by_name("install_profile.json")
-            .map_err(|e| format!("Missing install_profile.json: {}", e))?;
-        let mut buf = String::new();
-        entry
-            .read_to_string(&mut buf)
-            .map_err(|e| format!("Failed to read install_profile.json: {}", e))?;
-        serde_json::from_str(&buf)
-            .map_err(|e| format!("Failed to parse install_profile.json: {}", e))?
-    };
+    match detect_installer_format(&raw_profile) {
+        InstallerFormat::Legacy => {
+            return Err(
+                "Legacy Forge installers (Minecraft 1.12.2 and older) go through extract_legacy_forge_installer"
+                    .to_string(),
+            );
+        }
+        InstallerFormat::Modern { spec } if spec > MAX_SUPPORTED_SPEC => {
+            return Err(format!(
+                "This Forge generation (installer spec {}) isn't supported yet",
+                spec
+            ));
+        }
+        InstallerFormat::Modern { .. } => {}
+    }
+
+    // Modern profiles point at their version JSON; older ones always used /version.json
+    let version_json_name = raw_profile
+        .get("json")
+        .and_then(|v| v.as_str())
+        .unwrap_or("/version.json")
+        .trim_start_matches('/')
+        .to_string();
+
+    let install_profile: ForgeInstallProfile = serde_json::from_value(raw_profile)
+        .map_err(|e| format!("Failed to parse install_profile.json: {}", e))?;
 
     // Extract version.json
     let version_json: ForgeVersionJson = {
         let mut entry = archive
-            .by_name("version.json")
-            .map_err(|e| format!("Missing version.json: {}", e))?;
+            .by_name(&version_json_name)
+            .map_err(|e| format!("Missing {}: {}", version_json_name, e))?;
         let mut buf = String::new();
         entry
             .read_to_string(&mut buf)
@@ -141,47 +187,89 @@ pub fn extract_forge_installer(
             .map_err(|e| format!("Failed to parse version.json: {}", e))?
     };
 
-    // Extract maven/ directory contents to libraries
-    for i in 0..archive.len() {
-        let mut entry = archive
-            .by_index(i)
-            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
-        let name = entry.name().to_string();
-        if name.starts_with("maven/") && !entry.is_dir() {
-            let rel = name.strip_prefix("maven/").unwrap_or(&name);
-            let dest = libraries_dir.join(rel);
-            if let Some(parent) = dest.parent() {
-                std::fs::create_dir_all(parent)
-                    .map_err(|e| format!("Failed to create lib dir: {}", e))?;
+    // maven/ directory contents go to libraries, and data entries that point
+    // inside the JAR (start with /) to a location relative to libraries_dir
+    let mut jobs: Vec<Extraction> = archive
+        .file_names()
+        .filter(|name| !name.ends_with('/'))
+        .filter_map(|name| {
+            let rel = name.strip_prefix("maven/")?;
+            Some(Extraction {
+                archive: installer_jar.to_path_buf(),
+                entry: name.to_string(),
+                dest: libraries_dir.join(rel),
+            })
+        })
+        .collect();
+    for entry in install_profile.data.values() {
+        if let Some(jar_path) = entry.client.strip_prefix('/') {
+            if archive.by_name(jar_path).is_ok() {
+                jobs.push(Extraction {
+                    archive: installer_jar.to_path_buf(),
+                    entry: jar_path.to_string(),
+                    dest: libraries_dir.join("forge_extracted").join(jar_path),
+                });
             }
-            let mut buf = Vec::new();
-            entry
-                .read_to_end(&mut buf)
-                .map_err(|e| format!("Failed to read {}: {}", name, e))?;
-            std::fs::write(&dest, &buf)
-                .map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
         }
     }
+    extract_all(&jobs, |_, done, total| progress(done, total))?;
 
-    // Also extract data entries that reference paths inside the JAR (start with /)
-    // These get extracted to a temp location relative to libraries_dir
-    for (_key, entry) in &install_profile.data {
-        let client_val = &entry.client;
-        if client_val.starts_with('/') {
-            let jar_path = client_val.trim_start_matches('/');
-            if let Ok(mut zip_entry) = archive.by_name(jar_path) {
-                let dest = libraries_dir.join("forge_extracted").join(jar_path);
-                if let Some(parent) = dest.parent() {
-                    std::fs::create_dir_all(parent).ok();
-                }
-                let mut buf = Vec::new();
-                zip_entry.read_to_end(&mut buf).ok();
-                std::fs::write(&dest, &buf).ok();
+    Ok((install_profile, version_json))
+}
+
+/// Reads a legacy installer's profile and extracts the universal jar to its
+/// Maven path under `libraries_dir`. Nothing needs to run afterwards: the
+/// jar goes on the classpath and LaunchWrapper loads Forge through the tweak
+/// class in `minecraftArguments`.
+pub fn extract_legacy_forge_installer(
+    installer_jar: &Path,
+    libraries_dir: &Path,
+    progress: impl Fn(usize, usize) + Sync,
+) -> Result<LegacyForgeInstallProfile, String> {
+    let mut archive = open_installer(installer_jar)?;
+    let profile: LegacyForgeInstallProfile =
+        serde_json::from_value(read_install_profile(&mut archive)?)
+            .map_err(|e| format!("Failed to parse legacy install_profile.json: {}", e))?;
+
+    let dest = libraries_dir.join(maven_path(&profile.install.path)?);
+    let job = Extraction {
+        archive: installer_jar.to_path_buf(),
+        entry: profile.install.file_path.clone(),
+        dest,
+    };
+    extract_all(&[job], |_, done, total| progress(done, total))?;
+    Ok(profile)
+}
+
+/// The `--tweakClass` pairs of a `minecraftArguments` template: all Forge
+/// adds to the vanilla arguments, apart from `--versionType`.
+pub fn tweak_class_args(minecraft_arguments: &str) -> Vec<String> {
+    let mut args = vec![];
+    let mut tokens = minecraft_arguments.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token == "--tweakClass" {
+            if let Some(class) = tokens.next() {
+                args.push(token.to_string());
+                args.push(class.to_string());
             }
         }
     }
+    args
+}
 
-    Ok((install_profile, version_json))
+/// Repository path of the jar for a `group:artifact:version` coordinate.
+fn maven_path(coordinate: &str) -> Result<String, String> {
+    match coordinate.split(':').collect::<Vec<_>>()[..] {
+        [group, artifact, version] => Ok(format!(
+            "{}/{}/{}/{}-{}.jar",
+            group.replace('.', "/"),
+            artifact,
+            version,
+            artifact,
+            version
+        )),
+        _ => Err(format!("Invalid Maven coordinate: {}", coordinate)),
+    }
 }
 
 pub fn extract_jar_main_class(jar_path: &Path) -> Result<String, String> {
@@ -0,0 +1,124 @@
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Guards against `inheritsFrom` cycles in hand-edited version JSONs.
+pub const MAX_INHERITANCE_DEPTH: usize = 8;
+
+/// Loads `versions/<id>/<id>.json` and folds in every parent named by
+/// `inheritsFrom`. All JSONs of the chain must already be on disk.
+pub fn resolve(versions_dir: &Path, id: &str) -> Result<Value, String> {
+    let mut merged = read_version(versions_dir, id)?;
+    for _ in 0..MAX_INHERITANCE_DEPTH {
+        let Some(parent_id) = parent_of(&merged) else {
+            return Ok(merged);
+        };
+        let parent = read_version(versions_dir, &parent_id)?;
+        merged = merge(merged, parent);
+    }
+    Err(format!(
+        "Version {} inherits more than {} levels deep",
+        id, MAX_INHERITANCE_DEPTH
+    ))
+}
+
+/// `inheritsFrom` of a version JSON, if any.
+pub fn parent_of(version: &Value) -> Option<String> {
+    version
+        .get("inheritsFrom")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Merges a child version JSON onto its parent, the way the vanilla launcher
+/// does: the child's scalar fields win, its libraries come first and replace
+/// parent libraries of the same artifact, and its arguments are appended.
+/// Unless the child names its own `jar`, it runs the parent's client jar.
+/// The result keeps the parent's own `inheritsFrom`, if it has one.
+pub fn merge(child: Value, parent: Value) -> Value {
+    let (Value::Object(mut child), Value::Object(mut merged)) = (child, parent) else {
+        return Value::Null;
+    };
+    if let Some(jar) = merged.get("jar").or_else(|| merged.get("id")) {
+        child.entry("jar").or_insert_with(|| jar.clone());
+    }
+
+    let libraries = merge_libraries(child.remove("libraries"), merged.remove("libraries"));
+    let arguments = merge_arguments(child.remove("arguments"), merged.remove("arguments"));
+    child.remove("inheritsFrom");
+
+    for (key, value) in child {
+        merged.insert(key, value);
+    }
+    if let Some(libraries) = libraries {
+        merged.insert("libraries".to_string(), libraries);
+    }
+    if let Some(arguments) = arguments {
+        merged.insert("arguments".to_string(), arguments);
+    }
+    Value::Object(merged)
+}
+
+/// Child libraries first; parent libraries are dropped when the child ships
+/// the same artifact. Duplicates within one JSON are kept, since vanilla lists
+/// some artifacts twice with different OS rules.
+fn merge_libraries(child: Option<Value>, parent: Option<Value>) -> Option<Value> {
+    let as_vec = |libs: Option<Value>| match libs {
+        Some(Value::Array(libs)) => libs,
+        _ => Vec::new(),
+    };
+    let mut libraries = as_vec(child);
+    let overridden: HashSet<String> = libraries.iter().filter_map(library_key).collect();
+    libraries.extend(
+        as_vec(parent)
+            .into_iter()
+            .filter(|lib| library_key(lib).is_none_or(|key| !overridden.contains(&key))),
+    );
+    (!libraries.is_empty()).then_some(Value::Array(libraries))
+}
+
+fn library_key(lib: &Value) -> Option<String> {
+    lib.get("name").and_then(Value::as_str).map(artifact_key)
+}
+
+/// `group:artifact[:classifier]`, i.e. the maven coordinate without version,
+/// so a child can override the version of a parent library.
+fn artifact_key(name: &str) -> String {
+    let parts: Vec<&str> = name.split(':').collect();
+    match parts.as_slice() {
+        [group, artifact, _version, classifier, ..] => {
+            format!("{}:{}:{}", group, artifact, classifier)
+        }
+        [group, artifact, ..] => format!("{}:{}", group, artifact),
+        _ => name.to_string(),
+    }
+}
+
+fn merge_arguments(child: Option<Value>, parent: Option<Value>) -> Option<Value> {
+    let (child, parent) = match (child, parent) {
+        (Some(Value::Object(child)), Some(Value::Object(parent))) => (child, parent),
+        (child, None) => return child,
+        (None, parent) => return parent,
+        (Some(child), Some(_)) => return Some(child),
+    };
+
+    let mut merged = Map::new();
+    for key in ["game", "jvm"] {
+        let values: Vec<Value> = [parent.get(key), child.get(key)]
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_array)
+            .flatten()
+            .cloned()
+            .collect();
+        merged.insert(key.to_string(), Value::Array(values));
+    }
+    Some(Value::Object(merged))
+}
+
+fn read_version(versions_dir: &Path, id: &str) -> Result<Value, String> {
+    let path = versions_dir.join(id).join(format!("{}.json", id));
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read version {}: {}", id, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Invalid version JSON {}: {}", id, e))
+}
@@ -1,10 +1,17 @@
+pub mod arguments;
+pub mod capabilities;
+pub mod details;
+pub mod extract;
 pub mod fabric;
 pub mod forge;
+pub mod inheritance;
 pub mod models;
 pub mod neoforge;
 pub mod quilt;
 pub mod vanilla;
 
+pub use capabilities::*;
+pub use details::*;
 pub use fabric::*;
 pub use models::*;
 pub use vanilla::*;
@@ -23,12 +30,234 @@ mod tests {
         println!("Latest: {:?}", versions.first());
     }
 
+    #[test]
+    fn test_detect_forge_installer_format() {
+        let legacy = serde_json::json!({ "install": {}, "versionInfo": {} });
+        assert_eq!(
+            forge::detect_installer_format(&legacy),
+            forge::InstallerFormat::Legacy
+        );
+
+        let v1 = serde_json::json!({ "processors": [], "json": "/version.json" });
+        assert_eq!(
+            forge::detect_installer_format(&v1),
+            forge::InstallerFormat::Modern { spec: 0 }
+        );
+
+        let v2 = serde_json::json!({ "spec": 1, "processors": [] });
+        assert_eq!(
+            forge::detect_installer_format(&v2),
+            forge::InstallerFormat::Modern { spec: 1 }
+        );
+    }
+
+    #[test]
+    fn test_forge_tweak_class_args() {
+        let legacy = "--username ${auth_player_name} --version ${version_name} \
+            --tweakClass net.minecraftforge.fml.common.launcher.FMLTweaker --versionType Forge";
+        assert_eq!(
+            forge::tweak_class_args(legacy),
+            [
+                "--tweakClass",
+                "net.minecraftforge.fml.common.launcher.FMLTweaker"
+            ]
+        );
+        assert!(forge::tweak_class_args("--username ${auth_player_name}").is_empty());
+    }
+
+    #[test]
+    fn test_version_capabilities() {
+        let modern = VersionCapabilities::from_release_time("2023-06-07T09:35:21+00:00");
+        assert_eq!(modern, VersionCapabilities::ALL);
+
+        let legacy = VersionCapabilities::from_release_time("2016-06-23T09:17:32+00:00");
+        assert!(!legacy.quick_play);
+        assert!(legacy.join_server && legacy.custom_resolution);
+
+        let alpha = VersionCapabilities::from_release_time("2010-09-10T22:00:00+00:00");
+        assert!(!alpha.join_server && !alpha.custom_resolution);
+
+        assert_eq!(
+            VersionCapabilities::from_release_time(""),
+            VersionCapabilities::ALL
+        );
+    }
+
     #[tokio::test]
     async fn test_fetch_fabric() {
-        let loaders = fabric::fetch_fabric_loaders().await;
+        let loaders = fabric::fetch_fabric_loaders(&fabric::FABRIC).await;
         assert!(loaders.is_ok());
         let loaders = loaders.unwrap();
         assert!(!loaders.is_empty());
         println!("Found {} fabric loaders", loaders.len());
     }
+
+    #[tokio::test]
+    async fn test_fetch_legacy_fabric() {
+        let loaders = fabric::fetch_compatible_loaders(&fabric::LEGACY_FABRIC, "1.8.9").await;
+        assert!(loaders.is_ok());
+        let loaders = loaders.unwrap();
+        assert!(!loaders.is_empty());
+        println!("Found {} legacy fabric loaders for 1.8.9", loaders.len());
+    }
+
+    #[test]
+    fn test_neoforge_installer_url() {
+        assert_eq!(
+            neoforge::installer_url("21.4.10"),
+            "https://maven.neoforged.net/releases/net/neoforged/neoforge/21.4.10/neoforge-21.4.10-installer.jar"
+        );
+        assert_eq!(
+            neoforge::installer_url("1.20.1-47.1.106"),
+            "https://maven.neoforged.net/releases/net/neoforged/forge/1.20.1-47.1.106/forge-1.20.1-47.1.106-installer.jar"
+        );
+    }
+
+    #[test]
+    fn test_merge_inherited_version() {
+        let parent = serde_json::json!({
+            "id": "1.20.1",
+            "mainClass": "net.minecraft.client.main.Main",
+            "assetIndex": { "id": "5" },
+            "libraries": [
+                { "name": "org.ow2.asm:asm:9.3" },
+                { "name": "org.lwjgl:lwjgl:3.3.1" }
+            ],
+            "arguments": { "game": ["--username"], "jvm": ["-cp"] }
+        });
+        let child = serde_json::json!({
+            "id": "fabric-loader-0.15.0-1.20.1",
+            "inheritsFrom": "1.20.1",
+            "mainClass": "net.fabricmc.loader.impl.launch.knot.KnotClient",
+            "libraries": [
+                { "name": "org.ow2.asm:asm:9.6" },
+                { "name": "net.fabricmc:fabric-loader:0.15.0" }
+            ],
+            "arguments": { "game": [], "jvm": ["-DFabricMcEmu=net.minecraft.client.main.Main"] }
+        });
+
+        let merged = inheritance::merge(child, parent);
+        assert_eq!(merged["id"], "fabric-loader-0.15.0-1.20.1");
+        assert_eq!(
+            merged["mainClass"],
+            "net.fabricmc.loader.impl.launch.knot.KnotClient"
+        );
+        assert_eq!(merged["assetIndex"]["id"], "5");
+        assert_eq!(merged["jar"], "1.20.1");
+        assert!(merged.get("inheritsFrom").is_none());
+
+        let names: Vec<&str> = merged["libraries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|lib| lib["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "org.ow2.asm:asm:9.6",
+                "net.fabricmc:fabric-loader:0.15.0",
+                "org.lwjgl:lwjgl:3.3.1"
+            ]
+        );
+        assert_eq!(
+            merged["arguments"]["jvm"],
+            serde_json::json!(["-cp", "-DFabricMcEmu=net.minecraft.client.main.Main"])
+        );
+    }
+
+    /// Both fixtures follow piston-meta's layout with the library lists
+    /// trimmed to a few representative entries.
+    fn details(json: &str) -> VersionDetails {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_version_details_modern() {
+        let version = details(include_str!("../testdata/1.20.4.json"));
+        assert_eq!(version.id, "1.20.4");
+        assert_eq!(version.version_type.as_deref(), Some("release"));
+        assert_eq!(version.main_class, "net.minecraft.client.main.Main");
+        assert!(version.minecraft_arguments.is_none());
+
+        let java = version.java_version.unwrap();
+        assert_eq!(java.component.as_deref(), Some("java-runtime-gamma"));
+        assert_eq!(java.major_version, 17);
+
+        assert_eq!(version.asset_index.id, "12");
+        assert_eq!(version.asset_index.total_size, Some(623713413));
+        assert_eq!(version.assets.as_deref(), Some("12"));
+        assert_eq!(version.downloads.client.size, 24445539);
+        assert!(version.downloads.server.is_some());
+        assert!(version.downloads.client_mappings.is_some());
+
+        let logging = version.logging.unwrap().client.unwrap();
+        assert_eq!(logging.argument, "-Dlog4j.configurationFile=${path}");
+        assert_eq!(logging.file.id, "client-1.12.xml");
+        assert_eq!(logging.config_type.as_deref(), Some("log4j2-xml"));
+
+        let arguments = version.arguments.unwrap();
+        assert!(arguments.supports_quick_play());
+        let jvm: Vec<&str> = arguments
+            .jvm
+            .iter()
+            .flat_map(|arg| arg.values(&arguments::Features::default()))
+            .collect();
+        assert!(jvm.contains(&"-cp"));
+        assert_eq!(
+            jvm.contains(&"-XstartOnFirstThread"),
+            arguments::current_os() == "osx"
+        );
+
+        let natives: Vec<&Library> = version
+            .libraries
+            .iter()
+            .filter(|lib| lib.classifier().is_some())
+            .collect();
+        assert_eq!(natives.len(), 3);
+        let applying: Vec<&str> = natives
+            .iter()
+            .filter(|lib| lib.applies())
+            .filter_map(|lib| lib.classifier())
+            .collect();
+        assert_eq!(applying.len(), 1);
+        assert!(applying[0].contains(match arguments::current_os() {
+            "osx" => "macos",
+            os => os,
+        }));
+    }
+
+    #[test]
+    fn test_version_details_legacy() {
+        let version = details(include_str!("../testdata/1.12.2.json"));
+        assert_eq!(version.id, "1.12.2");
+        assert!(version.arguments.is_none());
+        assert!(version
+            .minecraft_arguments
+            .as_deref()
+            .unwrap()
+            .starts_with("--username ${auth_player_name}"));
+        assert_eq!(version.java_version.unwrap().major_version, 8);
+        assert!(version.downloads.client_mappings.is_none());
+        assert_eq!(version.minimum_launcher_version, Some(18));
+
+        let lwjgl = &version.libraries[1];
+        assert_eq!(lwjgl.rules.len(), 2);
+        assert_eq!(lwjgl.applies(), arguments::current_os() != "osx");
+
+        let platform = &version.libraries[2];
+        assert!(platform.downloads.artifact.is_none());
+        assert_eq!(platform.natives["windows"], "natives-windows");
+        assert!(platform.has_classifier("natives-linux"));
+        assert!(!platform.has_classifier("natives-linux-arm64"));
+        let windows = &platform.downloads.classifiers.as_ref().unwrap()["natives-windows"];
+        assert_eq!(windows.sha1, "b84d5102b9dbfabfeb5e43c7e2828d98a7fc80e0");
+        assert!(windows
+            .path
+            .as_deref()
+            .unwrap()
+            .ends_with("natives-windows.jar"));
+        assert_eq!(platform.extract.as_ref().unwrap().exclude, ["META-INF/"]);
+        assert_eq!(platform.applies(), arguments::current_os() == "osx");
+    }
 }
@@ -24,6 +24,8 @@ pub struct VanillaVersion {
     pub time: String,
     #[serde(rename = "releaseTime")]
     pub release_time: String,
+    #[serde(default)]
+    pub sha1: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +72,8 @@ pub struct LoaderProfile {
 pub struct LoaderLibrary {
     pub name: String,
     pub url: Option<String>,
+    #[serde(default)]
+    pub sha1: Option<String>,
 }
 
 // === Quilt ===
@@ -96,6 +100,10 @@ pub struct ForgeProcessor {
     pub args: Vec<String>,
     #[serde(default)]
     pub sides: Option<Vec<String>>,
+    /// Files the processor writes, mapped to their expected SHA1. Both sides
+    /// are tokens resolved like `args`.
+    #[serde(default)]
+    pub outputs: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -128,6 +136,9 @@ pub struct ForgeVersionJson {
     pub main_class: String,
     pub libraries: Vec<ForgeLibEntry>,
     pub arguments: Option<ForgeArguments>,
+    /// Set instead of `arguments` by the 1.12.2 builds of the new installer.
+    #[serde(rename = "minecraftArguments")]
+    pub minecraft_arguments: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -135,3 +146,41 @@ pub struct ForgeArguments {
     pub game: Option<Vec<serde_json::Value>>,
     pub jvm: Option<Vec<serde_json::Value>>,
 }
+
+// === Legacy Forge (1.12.2 and older) ===
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyForgeInstallProfile {
+    pub install: LegacyForgeInstall,
+    #[serde(rename = "versionInfo")]
+    pub version_info: LegacyForgeVersionInfo,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyForgeInstall {
+    /// Maven coordinate of the universal jar.
+    pub path: String,
+    /// Name of the universal jar inside the installer.
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    pub minecraft: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyForgeVersionInfo {
+    #[serde(rename = "mainClass")]
+    pub main_class: String,
+    #[serde(rename = "minecraftArguments")]
+    pub minecraft_arguments: String,
+    pub libraries: Vec<LegacyForgeLibrary>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyForgeLibrary {
+    pub name: String,
+    /// Maven repository root; Mojang's library server when absent.
+    pub url: Option<String>,
+    /// Only LWJGL's natives carry this, and the vanilla version already
+    /// provides them.
+    pub natives: Option<serde_json::Value>,
+}
@@ -1,17 +1,54 @@
+use downloader::{CancellationToken, DownloadRequest, Downloader};
 use serde::Deserialize;
 use std::path::Path;
 
+const MAVEN_RELEASES: &str = "https://maven.neoforged.net/releases/net/neoforged";
+const MAVEN_VERSIONS_API: &str =
+    "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged";
+
+/// The only Minecraft version served by the early `net.neoforged:forge`
+/// artifact, before NeoForge moved to its own coordinate and numbering.
+const LEGACY_GAME_VERSION: &str = "1.20.1";
+
 #[derive(Debug, Deserialize)]
 struct NeoForgeMavenVersions {
     versions: Vec<String>,
 }
 
+/// Maven artifact id a NeoForge version is published under. Legacy 1.20.1
+/// builds keep Forge's `<mc>-<forge>` numbering, e.g. `1.20.1-47.1.106`.
+fn maven_artifact(neoforge_version: &str) -> &'static str {
+    if neoforge_version.starts_with(&format!("{}-", LEGACY_GAME_VERSION)) {
+        "forge"
+    } else {
+        "neoforge"
+    }
+}
+
+pub fn installer_url(neoforge_version: &str) -> String {
+    let artifact = maven_artifact(neoforge_version);
+    format!(
+        "{base}/{artifact}/{v}/{artifact}-{v}-installer.jar",
+        base = MAVEN_RELEASES,
+        artifact = artifact,
+        v = neoforge_version
+    )
+}
+
 pub async fn fetch_neoforge_versions(game_version: &str) -> Result<Vec<String>, String> {
-    let url =
-        "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge";
+    let (artifact, prefix_dot) = if game_version == LEGACY_GAME_VERSION {
+        ("forge", format!("{}-", LEGACY_GAME_VERSION))
+    } else {
+        // NeoForge versions use MC version without the leading "1." as prefix
+        // e.g. MC 1.21.4 -> NeoForge prefix "21.4."
+        let prefix = game_version.strip_prefix("1.").unwrap_or(game_version);
+        ("neoforge", format!("{}.", prefix))
+    };
+
+    let url = format!("{}/{}", MAVEN_VERSIONS_API, artifact);
     let client = reqwest::Client::new();
     let response = client
-        .get(url)
+        .get(downloader::mirrored(&url))
         .send()
         .await
         .map_err(|e| format!("Failed to fetch NeoForge versions: {}", e))?;
@@ -21,13 +58,6 @@ pub async fn fetch_neoforge_versions(game_version: &str) -> Result<Vec<String>,
         .await
         .map_err(|e| format!("Failed to parse NeoForge versions: {}", e))?;
 
-    // NeoForge versions use MC version without the leading "1." as prefix
-    // e.g. MC 1.21.4 -> NeoForge prefix "21.4."
-    let prefix = game_version
-        .strip_prefix("1.")
-        .unwrap_or(game_version);
-    let prefix_dot = format!("{}.", prefix);
-
     let mut versions: Vec<String> = data
         .versions
         .into_iter()
@@ -39,39 +69,16 @@ pub async fn fetch_neoforge_versions(game_version: &str) -> Result<Vec<String>,
 }
 
 pub async fn download_neoforge_installer(
+    downloader: &Downloader,
     neoforge_version: &str,
     dest: &Path,
+    cancel: &CancellationToken,
 ) -> Result<(), String> {
-    let url = format!(
-        "https://maven.neoforged.net/releases/net/neoforged/neoforge/{v}/neoforge-{v}-installer.jar",
-        v = neoforge_version
-    );
+    let url = installer_url(neoforge_version);
 
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&url)
-        .send()
+    downloader
+        .download(&DownloadRequest::new(url, dest), cancel)
         .await
         .map_err(|e| format!("Failed to download NeoForge installer: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!(
-            "NeoForge installer download failed: {}",
-            response.status()
-        ));
-    }
-
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read NeoForge installer bytes: {}", e))?;
-
-    if let Some(parent) = dest.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create installer dir: {}", e))?;
-    }
-
-    std::fs::write(dest, &bytes)
-        .map_err(|e| format!("Failed to write NeoForge installer: {}", e))?;
     Ok(())
 }
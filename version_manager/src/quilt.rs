@@ -7,7 +7,7 @@ pub async fn fetch_quilt_loaders() -> Result<Vec<QuiltLoaderVersion>, String> {
     let url = format!("{}/versions/loader", QUILT_META_BASE);
     let client = reqwest::Client::new();
     let response = client
-        .get(&url)
+        .get(downloader::mirrored(&url))
         .send()
         .await
         .map_err(|e| format!("Failed to fetch Quilt loaders: {}", e))?;
@@ -18,6 +18,39 @@ pub async fn fetch_quilt_loaders() -> Result<Vec<QuiltLoaderVersion>, String> {
     Ok(loaders)
 }
 
+/// Entry of `versions/loader/{game_version}`; only the loader half is needed.
+#[derive(Debug, Deserialize)]
+struct QuiltCompatibleLoader {
+    loader: QuiltLoaderVersion,
+}
+
+/// Loader versions Quilt publishes for `game_version`, newest first. Empty
+/// when Quilt doesn't support that Minecraft version at all.
+pub async fn fetch_compatible_loaders(
+    game_version: &str,
+) -> Result<Vec<QuiltLoaderVersion>, String> {
+    let url = format!("{}/versions/loader/{}", QUILT_META_BASE, game_version);
+    let client = reqwest::Client::new();
+    let response = client
+        .get(downloader::mirrored(&url))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch Quilt loaders: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    if !response.status().is_success() {
+        return Err(format!("Quilt loader API returned {}", response.status()));
+    }
+
+    let entries: Vec<QuiltCompatibleLoader> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Quilt loaders: {}", e))?;
+    Ok(entries.into_iter().map(|e| e.loader).collect())
+}
+
 #[derive(Debug, Deserialize)]
 struct QuiltProfileJson {
     #[serde(rename = "mainClass")]
@@ -30,6 +63,8 @@ struct QuiltProfileJson {
 struct QuiltProfileLib {
     name: String,
     url: Option<String>,
+    #[serde(default)]
+    sha1: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -48,7 +83,7 @@ pub async fn fetch_quilt_profile(
     );
     let client = reqwest::Client::new();
     let response = client
-        .get(&url)
+        .get(downloader::mirrored(&url))
         .send()
         .await
         .map_err(|e| format!("Failed to fetch Quilt profile: {}", e))?;
@@ -72,6 +107,7 @@ pub async fn fetch_quilt_profile(
                 url: lib
                     .url
                     .or_else(|| Some("https://maven.quiltmc.org/repository/release/".to_string())),
+                sha1: lib.sha1,
             })
             .collect(),
         jvm_args: profile
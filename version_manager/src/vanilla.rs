@@ -1,21 +1,52 @@
 use crate::models::{VanillaVersion, VersionManifestV2};
-use reqwest::Error;
+use std::path::PathBuf;
+use std::time::Duration;
 
 const MANIFEST_URL: &str = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
 
-pub async fn fetch_vanilla_versions() -> Result<Vec<VanillaVersion>, Error> {
-    let client = reqwest::Client::new();
-    let response = client
-        .get("https://piston-meta.mojang.com/mc/game/version_manifest_v2.json")
-        .send()
-        .await?;
-    let manifest: VersionManifestV2 = response.json().await?;
-    Ok(manifest.versions)
+/// A cached manifest younger than this is used without revalidating it.
+const MANIFEST_MAX_AGE: Duration = Duration::from_secs(10 * 60);
+
+pub async fn fetch_vanilla_versions() -> Result<Vec<VanillaVersion>, String> {
+    Ok(fetch_manifest().await?.versions)
 }
 
-pub async fn fetch_manifest() -> Result<VersionManifestV2, Error> {
+/// Fetches the version manifest through an on-disk ETag cache, falling back to
+/// the cached copy when piston-meta is unreachable.
+pub async fn fetch_manifest() -> Result<VersionManifestV2, String> {
     let client = reqwest::Client::new();
-    let response = client.get(MANIFEST_URL).send().await?;
-    let manifest: VersionManifestV2 = response.json().await?;
-    Ok(manifest)
+
+    let content = match manifest_cache_path() {
+        Some(path) => {
+            let available =
+                downloader::fetch_cached(&client, MANIFEST_URL, &path, MANIFEST_MAX_AGE)
+                    .await
+                    .map_err(|e| format!("Failed to fetch version manifest: {}", e))?;
+            if !available {
+                return Err("Version manifest is unavailable".to_string());
+            }
+            std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read cached manifest: {}", e))?
+        }
+        None => client
+            .get(downloader::mirrored(MANIFEST_URL))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("Failed to fetch version manifest: {}", e))?
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read version manifest: {}", e))?,
+    };
+
+    serde_json::from_str(&content).map_err(|e| format!("Invalid version manifest: {}", e))
+}
+
+/// Directory for cached launcher metadata (manifests, version JSONs).
+pub fn meta_cache_dir() -> Option<PathBuf> {
+    directories::ProjectDirs::from("com", "fastmc", "fastmc").map(|d| d.cache_dir().join("meta"))
+}
+
+fn manifest_cache_path() -> Option<PathBuf> {
+    meta_cache_dir().map(|dir| dir.join("version_manifest_v2.json"))
 }